@@ -0,0 +1,2 @@
+-- Add migration script here
+ALTER TABLE tasks ADD COLUMN IF NOT EXISTS primer_content TEXT;
@@ -0,0 +1,8 @@
+-- Add migration script here
+CREATE TABLE IF NOT EXISTS task_archives (
+    task_id BIGINT PRIMARY KEY,
+    summary TEXT NOT NULL,
+    tags JSONB NOT NULL DEFAULT '[]',
+    completed_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (task_id) REFERENCES tasks (id) ON DELETE CASCADE
+);
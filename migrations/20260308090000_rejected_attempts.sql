@@ -0,0 +1,10 @@
+-- Add migration script here
+CREATE TABLE IF NOT EXISTS rejected_attempts (
+    id BIGSERIAL PRIMARY KEY,
+    topic TEXT NOT NULL,
+    motivation TEXT NOT NULL,
+    reason TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX idx_rejected_attempts_created_at ON rejected_attempts (created_at);
@@ -0,0 +1,32 @@
+-- Add migration script here
+-- Goals saved as free text before they were structured are kept as the specific part,
+-- so are JSON values that are not a complete SMART goal
+CREATE FUNCTION smart_goal_to_jsonb(goal TEXT) RETURNS JSONB
+LANGUAGE plpgsql STRICT AS $$
+DECLARE
+    parsed JSONB;
+BEGIN
+    BEGIN
+        parsed := goal::JSONB;
+    EXCEPTION WHEN invalid_text_representation THEN
+        parsed := NULL;
+    END;
+
+    IF jsonb_typeof(parsed) = 'object'
+        AND parsed ?& array['specific', 'measurable', 'achievable', 'relevant', 'time_bound'] THEN
+        RETURN parsed;
+    END IF;
+
+    RETURN jsonb_build_object(
+        'specific', goal,
+        'measurable', '',
+        'achievable', '',
+        'relevant', '',
+        'time_bound', ''
+    );
+END;
+$$;
+
+ALTER TABLE tasks ALTER COLUMN smart_goal TYPE JSONB USING smart_goal_to_jsonb(smart_goal);
+
+DROP FUNCTION smart_goal_to_jsonb(TEXT);
@@ -0,0 +1,2 @@
+-- Add migration script here
+ALTER TABLE tasks ADD COLUMN deadline DATE;
@@ -0,0 +1,15 @@
+-- Add migration script here
+-- Notes mix languages, so the 'simple' configuration is used to index words without stemming
+ALTER TABLE tasks ADD COLUMN search_vector tsvector GENERATED ALWAYS AS (
+    setweight(to_tsvector('simple', topic), 'A') ||
+    setweight(to_tsvector('simple', coalesce(motivation, '')), 'B') ||
+    setweight(jsonb_to_tsvector('simple', coalesce(smart_goal, '{}'::jsonb), '["string"]'), 'C')
+) STORED;
+
+CREATE INDEX idx_tasks_search_vector ON tasks USING GIN (search_vector);
+
+ALTER TABLE records ADD COLUMN search_vector tsvector GENERATED ALWAYS AS (
+    to_tsvector('simple', content)
+) STORED;
+
+CREATE INDEX idx_records_search_vector ON records USING GIN (search_vector);
@@ -0,0 +1 @@
+ALTER TABLE tasks ADD COLUMN primer_content TEXT;
@@ -0,0 +1,7 @@
+CREATE TABLE IF NOT EXISTS task_archives (
+    task_id INTEGER PRIMARY KEY,
+    summary TEXT NOT NULL,
+    tags TEXT NOT NULL DEFAULT '[]',
+    completed_at DATETIME NOT NULL,
+    FOREIGN KEY (task_id) REFERENCES tasks (id) ON DELETE CASCADE
+);
@@ -0,0 +1,9 @@
+CREATE TABLE IF NOT EXISTS rejected_attempts (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    topic TEXT NOT NULL,
+    motivation TEXT NOT NULL,
+    reason TEXT NOT NULL,
+    created_at DATETIME NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_rejected_attempts_created_at ON rejected_attempts (created_at);
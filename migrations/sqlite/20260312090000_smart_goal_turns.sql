@@ -0,0 +1,10 @@
+CREATE TABLE IF NOT EXISTS smart_goal_turns (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    task_id INTEGER NOT NULL,
+    role TEXT NOT NULL,
+    content TEXT NOT NULL,
+    created_at DATETIME NOT NULL,
+    FOREIGN KEY (task_id) REFERENCES tasks (id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_smart_goal_turns_task_id ON smart_goal_turns (task_id);
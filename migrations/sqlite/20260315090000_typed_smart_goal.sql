@@ -0,0 +1,20 @@
+-- Goals saved as free text before they were structured are kept as the specific part,
+-- so are JSON values that are not a complete SMART goal
+UPDATE tasks
+SET smart_goal = json_object(
+    'specific', smart_goal,
+    'measurable', '',
+    'achievable', '',
+    'relevant', '',
+    'time_bound', ''
+)
+WHERE smart_goal IS NOT NULL
+    AND CASE
+        WHEN json_valid(smart_goal) AND json_type(smart_goal) = 'object' THEN
+            json_type(smart_goal, '$.specific') IS NULL
+            OR json_type(smart_goal, '$.measurable') IS NULL
+            OR json_type(smart_goal, '$.achievable') IS NULL
+            OR json_type(smart_goal, '$.relevant') IS NULL
+            OR json_type(smart_goal, '$.time_bound') IS NULL
+        ELSE 1
+    END;
@@ -0,0 +1,46 @@
+-- The indexes keep their own copy of the text, rowid is the id of the task or record.
+-- Only the values of the SMART goal JSON are indexed, a legacy free-text goal as it is.
+CREATE VIRTUAL TABLE IF NOT EXISTS tasks_fts USING fts5 (topic, motivation, smart_goal);
+
+CREATE VIRTUAL TABLE IF NOT EXISTS records_fts USING fts5 (content);
+
+INSERT INTO tasks_fts (rowid, topic, motivation, smart_goal)
+SELECT id, topic, motivation, CASE WHEN json_valid(tasks.smart_goal)
+        THEN (SELECT group_concat(value, ' ') FROM json_each(tasks.smart_goal))
+        ELSE tasks.smart_goal END
+FROM tasks;
+
+INSERT INTO records_fts (rowid, content)
+SELECT id, content FROM records;
+
+CREATE TRIGGER IF NOT EXISTS tasks_fts_insert AFTER INSERT ON tasks
+BEGIN
+    INSERT INTO tasks_fts (rowid, topic, motivation, smart_goal)
+    VALUES (new.id, new.topic, new.motivation, CASE WHEN json_valid(new.smart_goal)
+        THEN (SELECT group_concat(value, ' ') FROM json_each(new.smart_goal))
+        ELSE new.smart_goal END);
+END;
+
+CREATE TRIGGER IF NOT EXISTS tasks_fts_update AFTER UPDATE OF topic, motivation, smart_goal ON tasks
+BEGIN
+    DELETE FROM tasks_fts WHERE rowid = old.id;
+    INSERT INTO tasks_fts (rowid, topic, motivation, smart_goal)
+    VALUES (new.id, new.topic, new.motivation, CASE WHEN json_valid(new.smart_goal)
+        THEN (SELECT group_concat(value, ' ') FROM json_each(new.smart_goal))
+        ELSE new.smart_goal END);
+END;
+
+CREATE TRIGGER IF NOT EXISTS tasks_fts_delete AFTER DELETE ON tasks
+BEGIN
+    DELETE FROM tasks_fts WHERE rowid = old.id;
+END;
+
+CREATE TRIGGER IF NOT EXISTS records_fts_insert AFTER INSERT ON records
+BEGIN
+    INSERT INTO records_fts (rowid, content) VALUES (new.id, new.content);
+END;
+
+CREATE TRIGGER IF NOT EXISTS records_fts_delete AFTER DELETE ON records
+BEGIN
+    DELETE FROM records_fts WHERE rowid = old.id;
+END;
@@ -0,0 +1,1038 @@
+//! Shared test-suite every `Repository` implementation runs, so the adapters can't drift.
+//!
+//! Each adapter's test module provides an async `test_repo()` returning the repository and a
+//! guard that keeps its backing database alive, then calls
+//! `repository_conformance_tests!(test_repo)`.
+
+use std::collections::HashMap;
+
+use chrono::NaiveDate;
+
+use crate::{
+    domain::{
+        backup::{Backup, TaskBackup},
+        models::{
+            HIGHLIGHT_END, HIGHLIGHT_START, SearchFilter, SearchHitKind, SmartGoalDetail,
+            TaskField, TurnRole,
+        },
+        state::TaskStatus,
+    },
+    ports::{llm::MilestonePlan, repository::Repository},
+};
+
+macro_rules! repository_conformance_tests {
+    ($factory:ident) => {
+        mod conformance {
+            use super::*;
+
+            $crate::adapters::conformance::repository_conformance_tests!(
+                @cases $factory,
+                create_task_should_start_planning,
+                get_missing_task_should_return_none,
+                update_smart_goal_should_keep_status,
+                approve_smart_goal_should_save_the_plan_and_activate,
+                approve_smart_goal_should_do_nothing_from_a_stale_status,
+                update_deadline_should_set_and_clear,
+                update_task_pause_should_set_and_clear_the_date,
+                task_revisions_should_keep_previous_values_and_cascade,
+                task_primer_should_be_replaced,
+                smart_goal_turns_should_keep_order_and_cascade,
+                milestones_should_sort_by_due_date_and_be_checked_off,
+                get_tasks_by_status_should_filter_and_order_by_updated_at,
+                update_task_status_should_record_transition,
+                update_task_status_should_reject_stale_status,
+                task_records_should_link_records_to_tasks,
+                task_record_should_reference_existing_task,
+                delete_task_should_cascade,
+                search_should_rank_highlight_and_filter,
+                submit_summary_should_replace_previous_questions,
+                submit_summary_should_do_nothing_from_a_stale_status,
+                complete_task_should_archive_and_be_listed,
+                complete_task_should_do_nothing_from_a_stale_status,
+                rejected_attempts_should_be_listed_newest_first,
+                restore_should_remap_ids_and_keep_timestamps,
+                restore_should_reject_dangling_records,
+            );
+        }
+    };
+    (@cases $factory:ident, $($case:ident),* $(,)?) => {
+        $(
+            #[tokio::test]
+            async fn $case() {
+                let (repo, _guard) = $factory().await;
+                $crate::adapters::conformance::$case(&repo).await;
+            }
+        )*
+    };
+}
+
+pub(crate) use repository_conformance_tests;
+
+pub async fn create_task_should_start_planning(repo: &impl Repository) {
+    let task = repo.create_task("topic", "motivation").await.unwrap();
+    assert_eq!(task.topic, "topic".to_string());
+    assert_eq!(task.motivation, Some("motivation".to_string()));
+    assert_eq!(task.smart_goal, None);
+    assert_eq!(task.status, TaskStatus::Planning);
+    assert!(task.created_at.timestamp() > 0);
+    assert!(task.updated_at.timestamp() > 0);
+
+    let fetched = repo.get_task(task.id).await.unwrap().unwrap();
+    assert_eq!(fetched.id, task.id);
+    assert_eq!(fetched.topic, task.topic);
+    assert_eq!(fetched.status, TaskStatus::Planning);
+
+    let other = repo.create_task("other", "motivation").await.unwrap();
+    assert!(other.id > task.id);
+}
+
+pub async fn get_missing_task_should_return_none(repo: &impl Repository) {
+    assert!(repo.get_task(404).await.unwrap().is_none());
+}
+
+pub async fn update_smart_goal_should_keep_status(repo: &impl Repository) {
+    let task = repo.create_task("topic", "motivation").await.unwrap();
+
+    let goal = SmartGoalDetail {
+        specific: "Build a Redis clone".to_string(),
+        measurable: "GET/SET/EXPIRE pass redis-benchmark".to_string(),
+        achievable: "2 hours a day".to_string(),
+        relevant: "Tokio at work".to_string(),
+        time_bound: "4 weeks".to_string(),
+    };
+    repo.update_task_smart_goal(task.id, &goal).await.unwrap();
+
+    let task = repo.get_task(task.id).await.unwrap().unwrap();
+    assert_eq!(task.smart_goal, Some(goal));
+    assert_eq!(task.status, TaskStatus::Planning);
+}
+
+fn smart_goal() -> SmartGoalDetail {
+    SmartGoalDetail {
+        specific: "Build a Redis clone".to_string(),
+        measurable: "GET/SET/EXPIRE pass redis-benchmark".to_string(),
+        achievable: "2 hours a day".to_string(),
+        relevant: "Tokio at work".to_string(),
+        time_bound: "4 weeks".to_string(),
+    }
+}
+
+fn milestone_plans() -> Vec<MilestonePlan> {
+    vec![
+        MilestonePlan {
+            title: "EXPIRE".to_string(),
+            due_date: NaiveDate::from_ymd_opt(2026, 4, 28).unwrap(),
+            criterion: "redis-benchmark passes".to_string(),
+        },
+        MilestonePlan {
+            title: "GET and SET".to_string(),
+            due_date: NaiveDate::from_ymd_opt(2026, 4, 14).unwrap(),
+            criterion: "redis-cli can GET and SET".to_string(),
+        },
+    ]
+}
+
+pub async fn approve_smart_goal_should_save_the_plan_and_activate(repo: &impl Repository) {
+    let task = repo.create_task("topic", "motivation").await.unwrap();
+    let deadline = NaiveDate::from_ymd_opt(2026, 4, 30).unwrap();
+
+    repo.approve_smart_goal(
+        task.id,
+        TaskStatus::Planning,
+        &smart_goal(),
+        &milestone_plans(),
+        Some(deadline),
+        "approved",
+    )
+    .await
+    .unwrap();
+
+    let fetched = repo.get_task(task.id).await.unwrap().unwrap();
+    assert_eq!(fetched.status, TaskStatus::Active);
+    assert_eq!(fetched.smart_goal, Some(smart_goal()));
+    assert_eq!(fetched.deadline, Some(deadline));
+    let milestones = repo.get_milestones(task.id).await.unwrap();
+    assert_eq!(
+        milestones
+            .iter()
+            .map(|m| m.title.as_str())
+            .collect::<Vec<_>>(),
+        vec!["GET and SET", "EXPIRE"]
+    );
+    assert!(milestones.iter().all(|m| m.completed_at.is_none()));
+    let transitions = repo.get_task_transitions(task.id).await.unwrap();
+    assert_eq!(transitions.len(), 1);
+    assert_eq!(transitions[0].to_status, TaskStatus::Active);
+    assert_eq!(transitions[0].reason, Some("approved".to_string()));
+
+    // Without a deadline the current one is kept
+    let other = repo.create_task("other", "motivation").await.unwrap();
+    repo.update_task_deadline(other.id, Some(deadline))
+        .await
+        .unwrap();
+    repo.approve_smart_goal(
+        other.id,
+        TaskStatus::Planning,
+        &smart_goal(),
+        &[],
+        None,
+        "approved",
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        repo.get_task(other.id).await.unwrap().unwrap().deadline,
+        Some(deadline)
+    );
+}
+
+pub async fn approve_smart_goal_should_do_nothing_from_a_stale_status(repo: &impl Repository) {
+    let task = repo.create_task("topic", "motivation").await.unwrap();
+    repo.update_task_status(task.id, TaskStatus::Planning, TaskStatus::Paused, None)
+        .await
+        .unwrap();
+
+    assert!(
+        repo.approve_smart_goal(
+            task.id,
+            TaskStatus::Planning,
+            &smart_goal(),
+            &milestone_plans(),
+            NaiveDate::from_ymd_opt(2026, 4, 30),
+            "approved",
+        )
+        .await
+        .is_err()
+    );
+
+    let fetched = repo.get_task(task.id).await.unwrap().unwrap();
+    assert_eq!(fetched.status, TaskStatus::Paused);
+    assert_eq!(fetched.smart_goal, None);
+    assert_eq!(fetched.deadline, None);
+    assert!(repo.get_milestones(task.id).await.unwrap().is_empty());
+    assert_eq!(repo.get_task_transitions(task.id).await.unwrap().len(), 1);
+}
+
+pub async fn update_deadline_should_set_and_clear(repo: &impl Repository) {
+    let task = repo.create_task("topic", "motivation").await.unwrap();
+    assert_eq!(task.deadline, None);
+
+    let deadline = NaiveDate::from_ymd_opt(2026, 4, 28).unwrap();
+    repo.update_task_deadline(task.id, Some(deadline))
+        .await
+        .unwrap();
+    let fetched = repo.get_task(task.id).await.unwrap().unwrap();
+    assert_eq!(fetched.deadline, Some(deadline));
+    assert!(fetched.updated_at >= task.updated_at);
+    let listed = repo
+        .get_tasks_by_status(&[TaskStatus::Planning])
+        .await
+        .unwrap();
+    assert_eq!(listed[0].deadline, Some(deadline));
+
+    repo.update_task_deadline(task.id, None).await.unwrap();
+    assert_eq!(
+        repo.get_task(task.id).await.unwrap().unwrap().deadline,
+        None
+    );
+}
+
+pub async fn update_task_pause_should_set_and_clear_the_date(repo: &impl Repository) {
+    let task = repo.create_task("topic", "motivation").await.unwrap();
+    assert_eq!(task.paused_until, None);
+
+    let until = NaiveDate::from_ymd_opt(2026, 5, 1).unwrap();
+    repo.update_task_pause(
+        task.id,
+        TaskStatus::Planning,
+        TaskStatus::Paused,
+        Some(until),
+        "Paused",
+    )
+    .await
+    .unwrap();
+    let fetched = repo.get_task(task.id).await.unwrap().unwrap();
+    assert_eq!(fetched.status, TaskStatus::Paused);
+    assert_eq!(fetched.paused_until, Some(until));
+    assert_eq!(
+        repo.get_task_transitions(task.id).await.unwrap()[0].reason,
+        Some("Paused".to_string())
+    );
+
+    // A stale status changes neither the status nor the date
+    assert!(
+        repo.update_task_pause(
+            task.id,
+            TaskStatus::Planning,
+            TaskStatus::Paused,
+            None,
+            "Paused",
+        )
+        .await
+        .is_err()
+    );
+    assert_eq!(
+        repo.get_task(task.id).await.unwrap().unwrap().paused_until,
+        Some(until)
+    );
+
+    repo.update_task_pause(
+        task.id,
+        TaskStatus::Paused,
+        TaskStatus::Planning,
+        None,
+        "Resumed",
+    )
+    .await
+    .unwrap();
+    let fetched = repo.get_task(task.id).await.unwrap().unwrap();
+    assert_eq!(fetched.status, TaskStatus::Planning);
+    assert_eq!(fetched.paused_until, None);
+}
+
+pub async fn task_revisions_should_keep_previous_values_and_cascade(repo: &impl Repository) {
+    let task = repo.create_task("Rsut", "motivation").await.unwrap();
+
+    repo.update_task_topic(task.id, "Rust").await.unwrap();
+    repo.create_task_revision(task.id, TaskField::Topic, Some("Rsut"))
+        .await
+        .unwrap();
+    repo.update_task_motivation(task.id, "a better motivation")
+        .await
+        .unwrap();
+    let revision = repo
+        .create_task_revision(task.id, TaskField::Motivation, Some("motivation"))
+        .await
+        .unwrap();
+    assert_eq!(revision.task_id, task.id);
+    assert_eq!(revision.field, TaskField::Motivation);
+
+    let fetched = repo.get_task(task.id).await.unwrap().unwrap();
+    assert_eq!(fetched.topic, "Rust");
+    assert_eq!(fetched.motivation, Some("a better motivation".to_string()));
+
+    let revisions = repo.get_task_revisions(task.id).await.unwrap();
+    let fields: Vec<_> = revisions.iter().map(|r| r.field).collect();
+    assert_eq!(fields, vec![TaskField::Topic, TaskField::Motivation]);
+    assert_eq!(revisions[0].previous, Some("Rsut".to_string()));
+
+    assert!(
+        repo.create_task_revision(404, TaskField::Topic, None)
+            .await
+            .is_err()
+    );
+
+    repo.delete_task(task.id).await.unwrap();
+    assert!(repo.get_task_revisions(task.id).await.unwrap().is_empty());
+}
+
+pub async fn task_primer_should_be_replaced(repo: &impl Repository) {
+    let task = repo.create_task("topic", "motivation").await.unwrap();
+    assert_eq!(repo.get_task_primer(task.id).await.unwrap(), None);
+
+    repo.update_task_primer(task.id, "first").await.unwrap();
+    repo.update_task_primer(task.id, "second").await.unwrap();
+    assert_eq!(
+        repo.get_task_primer(task.id).await.unwrap(),
+        Some("second".to_string())
+    );
+    assert_eq!(repo.get_task_primer(404).await.unwrap(), None);
+}
+
+pub async fn smart_goal_turns_should_keep_order_and_cascade(repo: &impl Repository) {
+    let task = repo.create_task("topic", "motivation").await.unwrap();
+    let other = repo.create_task("other", "motivation").await.unwrap();
+    assert!(
+        repo.create_smart_goal_turn(404, TurnRole::Learner, "goal")
+            .await
+            .is_err()
+    );
+
+    repo.create_smart_goal_turn(task.id, TurnRole::Learner, "draft")
+        .await
+        .unwrap();
+    repo.create_smart_goal_turn(other.id, TurnRole::Learner, "other draft")
+        .await
+        .unwrap();
+    let reply = repo
+        .create_smart_goal_turn(task.id, TurnRole::Strategist, "{}")
+        .await
+        .unwrap();
+    assert_eq!(reply.task_id, task.id);
+    assert_eq!(reply.role, TurnRole::Strategist);
+
+    let turns = repo.get_smart_goal_turns(task.id).await.unwrap();
+    assert_eq!(
+        turns
+            .iter()
+            .map(|t| (t.role, t.content.as_str()))
+            .collect::<Vec<_>>(),
+        vec![(TurnRole::Learner, "draft"), (TurnRole::Strategist, "{}")]
+    );
+
+    repo.delete_task(task.id).await.unwrap();
+    assert!(repo.get_smart_goal_turns(task.id).await.unwrap().is_empty());
+    assert_eq!(repo.get_smart_goal_turns(other.id).await.unwrap().len(), 1);
+}
+
+pub async fn milestones_should_sort_by_due_date_and_be_checked_off(repo: &impl Repository) {
+    let task = repo.create_task("topic", "motivation").await.unwrap();
+    repo.approve_smart_goal(
+        task.id,
+        TaskStatus::Planning,
+        &smart_goal(),
+        &milestone_plans(),
+        None,
+        "approved",
+    )
+    .await
+    .unwrap();
+
+    let milestones = repo.get_milestones(task.id).await.unwrap();
+    let (first, last) = (&milestones[0], &milestones[1]);
+    assert_eq!(first.title, "GET and SET".to_string());
+    assert_eq!(
+        first.due_date,
+        NaiveDate::from_ymd_opt(2026, 4, 14).unwrap()
+    );
+    assert!(!first.is_completed());
+
+    repo.update_milestone_completed(first.id, true)
+        .await
+        .unwrap();
+    let milestones = repo.get_milestones(task.id).await.unwrap();
+    assert_eq!(
+        milestones.iter().map(|m| m.id).collect::<Vec<_>>(),
+        vec![first.id, last.id]
+    );
+    assert!(milestones[0].is_completed());
+    assert!(!milestones[1].is_completed());
+
+    repo.update_milestone_completed(first.id, false)
+        .await
+        .unwrap();
+    assert!(!repo.get_milestones(task.id).await.unwrap()[0].is_completed());
+
+    repo.delete_task(task.id).await.unwrap();
+    assert!(repo.get_milestones(task.id).await.unwrap().is_empty());
+}
+
+pub async fn get_tasks_by_status_should_filter_and_order_by_updated_at(repo: &impl Repository) {
+    let planning = repo.create_task("planning", "motivation").await.unwrap();
+    let active = repo.create_task("active", "motivation").await.unwrap();
+    let discarded = repo.create_task("discarded", "motivation").await.unwrap();
+    repo.update_task_status(
+        discarded.id,
+        TaskStatus::Planning,
+        TaskStatus::Discarded,
+        None,
+    )
+    .await
+    .unwrap();
+    repo.update_task_status(active.id, TaskStatus::Planning, TaskStatus::Active, None)
+        .await
+        .unwrap();
+
+    let tasks = repo
+        .get_tasks_by_status(&[TaskStatus::Active])
+        .await
+        .unwrap();
+    assert_eq!(ids(&tasks), vec![active.id]);
+
+    // Most recently updated first
+    let tasks = repo
+        .get_tasks_by_status(&[TaskStatus::Planning, TaskStatus::Active])
+        .await
+        .unwrap();
+    assert_eq!(ids(&tasks), vec![active.id, planning.id]);
+
+    let tasks = repo.get_tasks_by_status(&[]).await.unwrap();
+    assert!(tasks.is_empty());
+}
+
+pub async fn update_task_status_should_record_transition(repo: &impl Repository) {
+    let task = repo.create_task("topic", "motivation").await.unwrap();
+
+    repo.update_task_status(
+        task.id,
+        TaskStatus::Planning,
+        TaskStatus::Active,
+        Some("goal approved"),
+    )
+    .await
+    .unwrap();
+    repo.update_task_status(task.id, TaskStatus::Active, TaskStatus::Reviewing, None)
+        .await
+        .unwrap();
+
+    let task = repo.get_task(task.id).await.unwrap().unwrap();
+    assert_eq!(task.status, TaskStatus::Reviewing);
+
+    let transitions = repo.get_task_transitions(task.id).await.unwrap();
+    assert_eq!(transitions.len(), 2);
+    assert_eq!(transitions[0].task_id, task.id);
+    assert_eq!(transitions[0].from_status, TaskStatus::Planning);
+    assert_eq!(transitions[0].to_status, TaskStatus::Active);
+    assert_eq!(transitions[0].reason, Some("goal approved".to_string()));
+    assert_eq!(transitions[1].from_status, TaskStatus::Active);
+    assert_eq!(transitions[1].to_status, TaskStatus::Reviewing);
+    assert_eq!(transitions[1].reason, None);
+}
+
+pub async fn update_task_status_should_reject_stale_status(repo: &impl Repository) {
+    let task = repo.create_task("topic", "motivation").await.unwrap();
+
+    let result = repo
+        .update_task_status(task.id, TaskStatus::Active, TaskStatus::Reviewing, None)
+        .await;
+    assert!(result.is_err());
+
+    let task = repo.get_task(task.id).await.unwrap().unwrap();
+    assert_eq!(task.status, TaskStatus::Planning);
+    assert!(repo.get_task_transitions(task.id).await.unwrap().is_empty());
+}
+
+pub async fn task_records_should_link_records_to_tasks(repo: &impl Repository) {
+    let first = repo.create_task("first", "motivation").await.unwrap();
+    let second = repo.create_task("second", "motivation").await.unwrap();
+    let shared = repo.create_record("shared").await.unwrap();
+    let only_first = repo.create_record("only first").await.unwrap();
+    assert_eq!(shared.content, "shared".to_string());
+
+    repo.create_task_record(first.id, shared.id).await.unwrap();
+    repo.create_task_record(second.id, shared.id).await.unwrap();
+    repo.create_task_record(first.id, only_first.id)
+        .await
+        .unwrap();
+
+    let mut records = ids_of_records(&repo.get_task_records(first.id).await.unwrap());
+    records.sort();
+    assert_eq!(records, vec![shared.id, only_first.id]);
+    assert_eq!(
+        ids_of_records(&repo.get_task_records(second.id).await.unwrap()),
+        vec![shared.id]
+    );
+}
+
+pub async fn task_record_should_reference_existing_task(repo: &impl Repository) {
+    let record = repo.create_record("record").await.unwrap();
+    assert!(repo.create_task_record(404, record.id).await.is_err());
+}
+
+pub async fn search_should_rank_highlight_and_filter(repo: &impl Repository) {
+    let tokio = repo
+        .create_task("Tokio runtime", "Build a Redis clone")
+        .await
+        .unwrap();
+    let macros = repo
+        .create_task("Rust macros", "Write derive macros")
+        .await
+        .unwrap();
+    repo.update_task_smart_goal(
+        tokio.id,
+        &SmartGoalDetail {
+            specific: "Ship GET and SET".to_string(),
+            measurable: "benchmark".to_string(),
+            achievable: String::new(),
+            relevant: String::new(),
+            time_bound: String::new(),
+        },
+    )
+    .await
+    .unwrap();
+    let insight = repo
+        .create_record("Tokio tasks are cheap, tokio spawns them on a pool")
+        .await
+        .unwrap();
+    let once = repo.create_record("Notes on macros").await.unwrap();
+    let often = repo
+        .create_record("macros macros macros everywhere")
+        .await
+        .unwrap();
+    repo.create_task_record(tokio.id, insight.id).await.unwrap();
+    repo.create_task_record(macros.id, once.id).await.unwrap();
+    let all = SearchFilter::default();
+
+    let hits = repo.search("tokio", &all, 20).await.unwrap();
+    let found: Vec<_> = hits.iter().map(|h| (h.kind, h.id)).collect();
+    assert_eq!(found.len(), 2);
+    assert!(found.contains(&(SearchHitKind::Task, tokio.id)));
+    assert!(found.contains(&(SearchHitKind::Record, insight.id)));
+    for hit in &hits {
+        let marked = format!("{}tokio{}", HIGHLIGHT_START, HIGHLIGHT_END);
+        assert!(
+            hit.snippet.to_lowercase().contains(&marked),
+            "{}",
+            hit.snippet
+        );
+        assert_eq!(hit.title, Some("Tokio runtime".to_string()));
+    }
+
+    // the SMART goal is indexed by its values, not by its keys
+    let hits = repo.search("ship", &all, 20).await.unwrap();
+    assert_eq!(hits.len(), 1);
+    assert_eq!(hits[0].status, Some(TaskStatus::Planning));
+    assert!(
+        repo.search("measurable", &all, 20)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+    assert!(repo.search("tokio redis", &all, 20).await.unwrap().len() == 1);
+
+    // more occurrences rank higher
+    let records: Vec<_> = repo
+        .search("macros", &all, 20)
+        .await
+        .unwrap()
+        .into_iter()
+        .filter(|h| h.kind == SearchHitKind::Record)
+        .map(|h| h.id)
+        .collect();
+    assert_eq!(records, vec![often.id, once.id]);
+    assert_eq!(repo.search("macros", &all, 1).await.unwrap().len(), 1);
+
+    // records are filtered by the status of their tasks
+    let active = SearchFilter {
+        statuses: vec![TaskStatus::Active],
+        ..SearchFilter::default()
+    };
+    assert!(repo.search("tokio", &active, 20).await.unwrap().is_empty());
+    let planning = SearchFilter {
+        statuses: vec![TaskStatus::Planning],
+        ..SearchFilter::default()
+    };
+    assert_eq!(repo.search("macros", &planning, 20).await.unwrap().len(), 2);
+
+    let today = chrono::Utc::now().date_naive();
+    let tomorrow = SearchFilter {
+        from: today.succ_opt(),
+        ..SearchFilter::default()
+    };
+    assert!(
+        repo.search("tokio", &tomorrow, 20)
+            .await
+            .unwrap()
+            .is_empty()
+    );
+    let today_only = SearchFilter {
+        from: Some(today),
+        to: Some(today),
+        ..SearchFilter::default()
+    };
+    assert_eq!(
+        repo.search("tokio", &today_only, 20).await.unwrap().len(),
+        2
+    );
+}
+
+pub async fn delete_task_should_cascade(repo: &impl Repository) {
+    let task = repo.create_task("topic", "motivation").await.unwrap();
+    let other = repo.create_task("other", "motivation").await.unwrap();
+    let record = repo.create_record("record").await.unwrap();
+    repo.create_task_record(task.id, record.id).await.unwrap();
+    repo.create_task_record(other.id, record.id).await.unwrap();
+    repo.update_task_status(task.id, TaskStatus::Planning, TaskStatus::Active, None)
+        .await
+        .unwrap();
+    repo.submit_summary(
+        task.id,
+        TaskStatus::Active,
+        "summary",
+        &["question".to_string()],
+        "submitted",
+    )
+    .await
+    .unwrap();
+
+    repo.delete_task(task.id).await.unwrap();
+
+    assert!(repo.get_task(task.id).await.unwrap().is_none());
+    assert!(repo.get_task_records(task.id).await.unwrap().is_empty());
+    assert!(repo.get_task_reviews(task.id).await.unwrap().is_empty());
+    assert!(repo.get_task_transitions(task.id).await.unwrap().is_empty());
+    // The record itself is kept for the other task
+    assert_eq!(repo.get_task_records(other.id).await.unwrap().len(), 1);
+}
+
+/// Walk a new task through Planning to Active.
+async fn active_task(repo: &impl Repository, topic: &str) -> i64 {
+    let task = repo.create_task(topic, "motivation").await.unwrap();
+    repo.update_task_status(task.id, TaskStatus::Planning, TaskStatus::Active, None)
+        .await
+        .unwrap();
+    task.id
+}
+
+pub async fn submit_summary_should_replace_previous_questions(repo: &impl Repository) {
+    let id = active_task(repo, "topic").await;
+
+    repo.submit_summary(
+        id,
+        TaskStatus::Active,
+        "first summary",
+        &["q1".to_string(), "q2".to_string()],
+        "submitted",
+    )
+    .await
+    .unwrap();
+    // the review failed, the learner studies and submits again
+    repo.update_task_status(id, TaskStatus::Reviewing, TaskStatus::Active, None)
+        .await
+        .unwrap();
+    let reviews = repo
+        .submit_summary(
+            id,
+            TaskStatus::Active,
+            "second summary",
+            &["q3".to_string(), "q4".to_string()],
+            "submitted again",
+        )
+        .await
+        .unwrap();
+    assert_eq!(reviews.len(), 2);
+    assert!(
+        reviews
+            .iter()
+            .all(|r| !r.is_passed && r.user_answer.is_none())
+    );
+
+    repo.update_review_answer(reviews[0].id, "answer", "feedback", true)
+        .await
+        .unwrap();
+
+    let reviews = repo.get_task_reviews(id).await.unwrap();
+    assert_eq!(reviews.len(), 2);
+    assert_eq!(reviews[0].question, "q3".to_string());
+    assert_eq!(reviews[0].user_answer, Some("answer".to_string()));
+    assert_eq!(reviews[0].ai_feedback, Some("feedback".to_string()));
+    assert!(reviews[0].is_passed);
+    assert_eq!(reviews[1].question, "q4".to_string());
+    assert!(!reviews[1].is_passed);
+
+    let task = repo.get_task(id).await.unwrap().unwrap();
+    assert_eq!(task.status, TaskStatus::Reviewing);
+    let mut summaries: Vec<String> = repo
+        .get_task_records(id)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|r| r.content)
+        .collect();
+    summaries.sort();
+    assert_eq!(summaries, ["first summary", "second summary"]);
+    let transitions = repo.get_task_transitions(id).await.unwrap();
+    let last = transitions.last().unwrap();
+    assert_eq!(last.to_status, TaskStatus::Reviewing);
+    assert_eq!(last.reason, Some("submitted again".to_string()));
+}
+
+pub async fn submit_summary_should_do_nothing_from_a_stale_status(repo: &impl Repository) {
+    let id = active_task(repo, "topic").await;
+    repo.submit_summary(
+        id,
+        TaskStatus::Active,
+        "summary",
+        &["q1".to_string()],
+        "submitted",
+    )
+    .await
+    .unwrap();
+
+    // a second commit still believes the task is active
+    assert!(
+        repo.submit_summary(
+            id,
+            TaskStatus::Active,
+            "late summary",
+            &["q2".to_string()],
+            "submitted",
+        )
+        .await
+        .is_err()
+    );
+
+    let task = repo.get_task(id).await.unwrap().unwrap();
+    assert_eq!(task.status, TaskStatus::Reviewing);
+    let reviews = repo.get_task_reviews(id).await.unwrap();
+    assert_eq!(reviews.len(), 1);
+    assert_eq!(reviews[0].question, "q1".to_string());
+    assert_eq!(repo.get_task_records(id).await.unwrap().len(), 1);
+    assert_eq!(repo.get_records().await.unwrap().len(), 1);
+    assert_eq!(repo.get_task_transitions(id).await.unwrap().len(), 2);
+}
+
+/// Walk a new task through Planning and Active to Reviewing.
+async fn reviewing_task(repo: &impl Repository, topic: &str) -> i64 {
+    let task = repo.create_task(topic, "motivation").await.unwrap();
+    for (from, to) in [
+        (TaskStatus::Planning, TaskStatus::Active),
+        (TaskStatus::Active, TaskStatus::Reviewing),
+    ] {
+        repo.update_task_status(task.id, from, to, None)
+            .await
+            .unwrap();
+    }
+    task.id
+}
+
+pub async fn complete_task_should_archive_and_be_listed(repo: &impl Repository) {
+    let first = reviewing_task(repo, "first").await;
+    let second = reviewing_task(repo, "second").await;
+    assert!(repo.get_task_archives().await.unwrap().is_empty());
+    assert!(
+        repo.complete_task(404, TaskStatus::Reviewing, "summary", &[], "passed")
+            .await
+            .is_err()
+    );
+
+    let archive = repo
+        .complete_task(
+            first,
+            TaskStatus::Reviewing,
+            "summary",
+            &["Rust".to_string(), "Async".to_string()],
+            "passed",
+        )
+        .await
+        .unwrap();
+    assert_eq!(archive.task_id, first);
+    assert_eq!(archive.topic, "first".to_string());
+    assert_eq!(archive.summary, "summary".to_string());
+    assert_eq!(archive.tags, vec!["Rust".to_string(), "Async".to_string()]);
+    assert_eq!(
+        repo.get_task(first).await.unwrap().unwrap().status,
+        TaskStatus::Completed
+    );
+    let transitions = repo.get_task_transitions(first).await.unwrap();
+    assert_eq!(transitions.last().unwrap().to_status, TaskStatus::Completed);
+    assert_eq!(
+        transitions.last().unwrap().reason,
+        Some("passed".to_string())
+    );
+    repo.complete_task(
+        second,
+        TaskStatus::Reviewing,
+        "second summary",
+        &["Rust".to_string()],
+        "passed",
+    )
+    .await
+    .unwrap();
+
+    // Most recently completed first
+    let archives = repo.get_task_archives().await.unwrap();
+    assert_eq!(
+        archives.iter().map(|a| a.task_id).collect::<Vec<_>>(),
+        vec![second, first]
+    );
+    assert_eq!(archives[1].summary, "summary".to_string());
+    assert_eq!(archives[1].tags.len(), 2);
+
+    repo.delete_task(first).await.unwrap();
+    assert_eq!(repo.get_task_archives().await.unwrap().len(), 1);
+}
+
+pub async fn complete_task_should_do_nothing_from_a_stale_status(repo: &impl Repository) {
+    let task = repo.create_task("topic", "motivation").await.unwrap();
+
+    assert!(
+        repo.complete_task(task.id, TaskStatus::Reviewing, "summary", &[], "passed")
+            .await
+            .is_err()
+    );
+    assert!(repo.get_task_archives().await.unwrap().is_empty());
+    assert_eq!(
+        repo.get_task(task.id).await.unwrap().unwrap().status,
+        TaskStatus::Planning
+    );
+    assert!(repo.get_task_transitions(task.id).await.unwrap().is_empty());
+}
+
+pub async fn rejected_attempts_should_be_listed_newest_first(repo: &impl Repository) {
+    assert!(repo.get_rejected_attempts().await.unwrap().is_empty());
+
+    let first = repo
+        .create_rejected_attempt("Kubernetes", "Everyone uses it", "No use case")
+        .await
+        .unwrap();
+    assert_eq!(first.topic, "Kubernetes".to_string());
+    assert_eq!(first.motivation, "Everyone uses it".to_string());
+    assert_eq!(first.reason, "No use case".to_string());
+    let second = repo
+        .create_rejected_attempt("Kubernetes", "Might need it", "Still no use case")
+        .await
+        .unwrap();
+
+    let attempts = repo.get_rejected_attempts().await.unwrap();
+    assert_eq!(
+        attempts.iter().map(|a| a.id).collect::<Vec<_>>(),
+        vec![second.id, first.id]
+    );
+}
+
+pub async fn restore_should_remap_ids_and_keep_timestamps(repo: &impl Repository) {
+    let task = repo.create_task("Rust", "motivation").await.unwrap();
+    let goal = SmartGoalDetail {
+        specific: "Build a Redis clone".to_string(),
+        measurable: "GET/SET pass redis-benchmark".to_string(),
+        achievable: "2 hours a day".to_string(),
+        relevant: "Tokio at work".to_string(),
+        time_bound: "4 weeks".to_string(),
+    };
+    repo.update_task_primer(task.id, "primer").await.unwrap();
+    repo.create_task_revision(task.id, TaskField::Topic, Some("Go"))
+        .await
+        .unwrap();
+    repo.create_smart_goal_turn(task.id, TurnRole::Learner, "goal")
+        .await
+        .unwrap();
+    repo.approve_smart_goal(
+        task.id,
+        TaskStatus::Planning,
+        &goal,
+        &[MilestonePlan {
+            title: "Book".to_string(),
+            due_date: NaiveDate::from_ymd_opt(2026, 5, 1).unwrap(),
+            criterion: "Read it".to_string(),
+        }],
+        NaiveDate::from_ymd_opt(2026, 6, 1),
+        "approved",
+    )
+    .await
+    .unwrap();
+    let reviews = repo
+        .submit_summary(
+            task.id,
+            TaskStatus::Active,
+            "copied",
+            &["Why?".to_string()],
+            "submitted",
+        )
+        .await
+        .unwrap();
+    repo.update_review_answer(reviews[0].id, "Because", "Good", true)
+        .await
+        .unwrap();
+    repo.complete_task(
+        task.id,
+        TaskStatus::Reviewing,
+        "summary",
+        &["Rust".to_string()],
+        "passed",
+    )
+    .await
+    .unwrap();
+    // the summary is the task's own record
+    let copied = repo.get_task_records(task.id).await.unwrap().remove(0);
+    let shared = repo.create_record("shared").await.unwrap();
+    repo.create_task_record(task.id, shared.id).await.unwrap();
+    repo.create_rejected_attempt("Kubernetes", "Everyone uses it", "No use case")
+        .await
+        .unwrap();
+
+    let task = repo.get_task(task.id).await.unwrap().unwrap();
+    let backup = Backup::new(
+        vec![TaskBackup {
+            task: task.clone(),
+            primer: repo.get_task_primer(task.id).await.unwrap(),
+            record_ids: ids_of_records(&repo.get_task_records(task.id).await.unwrap()),
+            transitions: repo.get_task_transitions(task.id).await.unwrap(),
+            smart_goal_turns: repo.get_smart_goal_turns(task.id).await.unwrap(),
+            milestones: repo.get_milestones(task.id).await.unwrap(),
+            reviews: repo.get_task_reviews(task.id).await.unwrap(),
+            revisions: repo.get_task_revisions(task.id).await.unwrap(),
+            archive: repo.get_task_archives().await.unwrap().pop(),
+        }],
+        repo.get_records().await.unwrap(),
+        repo.get_rejected_attempts().await.unwrap(),
+    );
+    assert_eq!(ids_of_records(&backup.records), vec![copied.id, shared.id]);
+
+    // the shared record is already stored, only the other one is copied
+    repo.restore(&backup, &HashMap::from([(shared.id, shared.id)]))
+        .await
+        .unwrap();
+
+    let tasks = repo.get_tasks_by_status(&TaskStatus::ALL).await.unwrap();
+    assert_eq!(tasks.len(), 2);
+    let restored = tasks.iter().find(|t| t.id != task.id).unwrap();
+    assert_eq!(restored.topic, task.topic);
+    assert_eq!(restored.status, TaskStatus::Completed);
+    assert_eq!(restored.deadline, task.deadline);
+    assert_eq!(restored.smart_goal, task.smart_goal);
+    assert_eq!(restored.created_at, task.created_at);
+    assert_eq!(restored.updated_at, task.updated_at);
+    assert_eq!(
+        repo.get_task_primer(restored.id).await.unwrap(),
+        Some("primer".to_string())
+    );
+
+    let records = repo.get_records().await.unwrap();
+    assert_eq!(records.len(), 3);
+    let linked = repo.get_task_records(restored.id).await.unwrap();
+    assert_eq!(linked.len(), 2);
+    assert!(linked.iter().any(|r| r.id == shared.id));
+    let copy = linked.iter().find(|r| r.id != shared.id).unwrap();
+    assert_eq!(copy.content, "copied".to_string());
+    assert_eq!(copy.created_at, copied.created_at);
+
+    let transitions = repo.get_task_transitions(restored.id).await.unwrap();
+    assert_eq!(transitions.len(), 3);
+    assert_eq!(transitions[0].task_id, restored.id);
+    assert_eq!(
+        transitions[0].created_at,
+        backup.tasks[0].transitions[0].created_at
+    );
+    assert_eq!(
+        repo.get_smart_goal_turns(restored.id).await.unwrap().len(),
+        1
+    );
+    assert_eq!(repo.get_milestones(restored.id).await.unwrap().len(), 1);
+    let reviews = repo.get_task_reviews(restored.id).await.unwrap();
+    assert_eq!(reviews.len(), 1);
+    assert!(reviews[0].is_passed);
+    let revisions = repo.get_task_revisions(restored.id).await.unwrap();
+    assert_eq!(revisions[0].previous, Some("Go".to_string()));
+    let archives = repo.get_task_archives().await.unwrap();
+    assert_eq!(archives.len(), 2);
+    assert!(archives.iter().any(|a| a.task_id == restored.id));
+    assert_eq!(repo.get_rejected_attempts().await.unwrap().len(), 2);
+}
+
+pub async fn restore_should_reject_dangling_records(repo: &impl Repository) {
+    let task = repo.create_task("Rust", "motivation").await.unwrap();
+    let backup = Backup::new(
+        vec![TaskBackup {
+            task,
+            primer: None,
+            record_ids: vec![404],
+            transitions: vec![],
+            smart_goal_turns: vec![],
+            milestones: vec![],
+            reviews: vec![],
+            revisions: vec![],
+            archive: None,
+        }],
+        vec![],
+        vec![],
+    );
+
+    assert!(repo.restore(&backup, &HashMap::new()).await.is_err());
+    // nothing of a failed restore is kept
+    assert_eq!(
+        repo.get_tasks_by_status(&TaskStatus::ALL)
+            .await
+            .unwrap()
+            .len(),
+        1
+    );
+}
+
+fn ids(tasks: &[crate::domain::models::Task]) -> Vec<i64> {
+    tasks.iter().map(|t| t.id).collect()
+}
+
+fn ids_of_records(records: &[crate::domain::models::Record]) -> Vec<i64> {
+    records.iter().map(|r| r.id).collect()
+}
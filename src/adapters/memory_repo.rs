@@ -0,0 +1,844 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::{Mutex, MutexGuard},
+};
+
+use async_trait::async_trait;
+use chrono::{DateTime, NaiveDate, Utc};
+
+use crate::{
+    domain::{
+        backup::Backup,
+        models::{
+            HIGHLIGHT_END, HIGHLIGHT_START, Milestone, Record, RejectedAttempt, Review,
+            SearchFilter, SearchHit, SearchHitKind, SmartGoalDetail, SmartGoalTurn, Task,
+            TaskArchive, TaskField, TaskRevision, TaskTransition, TurnRole,
+        },
+        state::TaskStatus,
+    },
+    ports::{
+        error::{GewuError, Result},
+        llm::MilestonePlan,
+        repository::Repository,
+    },
+};
+
+/// Repository kept in memory, for unit tests and ephemeral sessions.
+/// Mirrors the SQL adapters: ids are sequential, deleting a task cascades to its links.
+#[derive(Default)]
+pub struct MemoryRepo {
+    state: Mutex<State>,
+}
+
+#[derive(Default)]
+struct State {
+    tasks: Table<Task>,
+    /// primer_content of the tasks, kept apart like the column is kept out of `Task`
+    primers: BTreeMap<i64, String>,
+    records: Table<Record>,
+    /// (task_id, record_id) pairs in insertion order
+    task_records: Vec<(i64, i64)>,
+    reviews: Table<Review>,
+    transitions: Table<TaskTransition>,
+    /// Archives by task id, the topic is read from the task like the SQL join does
+    archives: BTreeMap<i64, TaskArchive>,
+    rejected_attempts: Table<RejectedAttempt>,
+    smart_goal_turns: Table<SmartGoalTurn>,
+    revisions: Table<TaskRevision>,
+    milestones: Table<Milestone>,
+}
+
+/// Rows by id, with a sequence that never reuses ids like `BIGSERIAL`.
+struct Table<T> {
+    sequence: i64,
+    rows: BTreeMap<i64, T>,
+}
+
+impl<T> Default for Table<T> {
+    fn default() -> Self {
+        Self {
+            sequence: 0,
+            rows: BTreeMap::new(),
+        }
+    }
+}
+
+impl<T> Table<T> {
+    fn next_id(&mut self) -> i64 {
+        self.sequence += 1;
+        self.sequence
+    }
+}
+
+impl MemoryRepo {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn state(&self) -> Result<MutexGuard<'_, State>> {
+        self.state
+            .lock()
+            .map_err(|e| GewuError::storage(format!("Memory repository lock poisoned: {}", e)))
+    }
+}
+
+impl State {
+    /// Move the task from `from` to `to` and record the transition
+    fn transition(
+        &mut self,
+        id: i64,
+        from: TaskStatus,
+        to: TaskStatus,
+        reason: Option<&str>,
+    ) -> Result<()> {
+        let now = Utc::now();
+        match self.tasks.rows.get_mut(&id) {
+            Some(task) if task.status == from => {
+                task.status = to.clone();
+                task.updated_at = now;
+            }
+            _ => {
+                return Err(GewuError::status_conflict(format!(
+                    "Task #{} is not in {} status",
+                    id, from
+                )));
+            }
+        }
+
+        let transition = TaskTransition {
+            id: self.transitions.next_id(),
+            task_id: id,
+            from_status: from,
+            to_status: to,
+            reason: reason.map(String::from),
+            created_at: now,
+        };
+        self.transitions.rows.insert(transition.id, transition);
+        Ok(())
+    }
+
+    /// Replace the review questions of the task with unanswered ones
+    fn replace_reviews(&mut self, task_id: i64, questions: &[String]) -> Vec<Review> {
+        self.reviews.rows.retain(|_, r| r.task_id != task_id);
+
+        let now = Utc::now();
+        let mut reviews = Vec::with_capacity(questions.len());
+        for question in questions {
+            let review = Review {
+                id: self.reviews.next_id(),
+                task_id,
+                question: question.clone(),
+                user_answer: None,
+                ai_feedback: None,
+                is_passed: false,
+                created_at: now,
+                updated_at: now,
+            };
+            self.reviews.rows.insert(review.id, review.clone());
+            reviews.push(review);
+        }
+        reviews
+    }
+}
+
+/// The lowercased words of a text, split like the SQL full-text indexes do
+fn words(text: &str) -> impl Iterator<Item = String> + '_ {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+}
+
+/// How often the terms occur in the text, `None` unless every term occurs
+fn occurrences(text: &str, terms: &[String]) -> Option<usize> {
+    let matched: Vec<String> = words(text).filter(|word| terms.contains(word)).collect();
+    terms
+        .iter()
+        .all(|term| matched.contains(term))
+        .then_some(matched.len())
+}
+
+/// Wrap every word of the text that is one of the terms in the highlight markers
+fn highlight(text: &str, terms: &[String]) -> String {
+    let mut highlighted = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find(char::is_alphanumeric) {
+        let end = rest[start..]
+            .find(|c: char| !c.is_alphanumeric())
+            .map_or(rest.len(), |len| start + len);
+        let word = &rest[start..end];
+        highlighted.push_str(&rest[..start]);
+        if terms.contains(&word.to_lowercase()) {
+            highlighted.push_str(HIGHLIGHT_START);
+            highlighted.push_str(word);
+            highlighted.push_str(HIGHLIGHT_END);
+        } else {
+            highlighted.push_str(word);
+        }
+        rest = &rest[end..];
+    }
+    highlighted.push_str(rest);
+    highlighted
+}
+
+#[async_trait]
+impl Repository for MemoryRepo {
+    async fn create_task(&self, topic: &str, motivation: &str) -> Result<Task> {
+        let mut state = self.state()?;
+        let now = Utc::now();
+        let task = Task {
+            id: state.tasks.next_id(),
+            topic: topic.to_string(),
+            motivation: Some(motivation.to_string()),
+            smart_goal: None,
+            deadline: None,
+            paused_until: None,
+            status: TaskStatus::Planning,
+            created_at: now,
+            updated_at: now,
+        };
+        state.tasks.rows.insert(task.id, task.clone());
+        Ok(task)
+    }
+
+    async fn update_task_topic(&self, id: i64, topic: &str) -> Result<()> {
+        let mut state = self.state()?;
+        if let Some(task) = state.tasks.rows.get_mut(&id) {
+            task.topic = topic.to_string();
+            task.updated_at = Utc::now();
+        }
+        Ok(())
+    }
+
+    async fn update_task_motivation(&self, id: i64, motivation: &str) -> Result<()> {
+        let mut state = self.state()?;
+        if let Some(task) = state.tasks.rows.get_mut(&id) {
+            task.motivation = Some(motivation.to_string());
+            task.updated_at = Utc::now();
+        }
+        Ok(())
+    }
+
+    async fn update_task_smart_goal(&self, id: i64, smart_goal: &SmartGoalDetail) -> Result<()> {
+        let mut state = self.state()?;
+        if let Some(task) = state.tasks.rows.get_mut(&id) {
+            task.smart_goal = Some(smart_goal.clone());
+            task.updated_at = Utc::now();
+        }
+        Ok(())
+    }
+
+    async fn approve_smart_goal(
+        &self,
+        id: i64,
+        from: TaskStatus,
+        smart_goal: &SmartGoalDetail,
+        milestones: &[MilestonePlan],
+        deadline: Option<NaiveDate>,
+        reason: &str,
+    ) -> Result<()> {
+        let mut state = self.state()?;
+        state.transition(id, from, TaskStatus::Active, Some(reason))?;
+        let now = Utc::now();
+        if let Some(task) = state.tasks.rows.get_mut(&id) {
+            task.smart_goal = Some(smart_goal.clone());
+            task.deadline = deadline.or(task.deadline);
+            task.updated_at = now;
+        }
+        for plan in milestones {
+            let milestone = Milestone {
+                id: state.milestones.next_id(),
+                task_id: id,
+                title: plan.title.clone(),
+                criterion: plan.criterion.clone(),
+                due_date: plan.due_date,
+                completed_at: None,
+                created_at: now,
+            };
+            state.milestones.rows.insert(milestone.id, milestone);
+        }
+        Ok(())
+    }
+
+    async fn update_task_deadline(&self, id: i64, deadline: Option<NaiveDate>) -> Result<()> {
+        let mut state = self.state()?;
+        if let Some(task) = state.tasks.rows.get_mut(&id) {
+            task.deadline = deadline;
+            task.updated_at = Utc::now();
+        }
+        Ok(())
+    }
+
+    async fn update_task_pause(
+        &self,
+        id: i64,
+        from: TaskStatus,
+        to: TaskStatus,
+        paused_until: Option<NaiveDate>,
+        reason: &str,
+    ) -> Result<()> {
+        let mut state = self.state()?;
+        state.transition(id, from, to, Some(reason))?;
+        if let Some(task) = state.tasks.rows.get_mut(&id) {
+            task.paused_until = paused_until;
+        }
+        Ok(())
+    }
+
+    async fn create_task_revision(
+        &self,
+        task_id: i64,
+        field: TaskField,
+        previous: Option<&str>,
+    ) -> Result<TaskRevision> {
+        let mut state = self.state()?;
+        if !state.tasks.rows.contains_key(&task_id) {
+            return Err(GewuError::task_not_found(task_id));
+        }
+        let revision = TaskRevision {
+            id: state.revisions.next_id(),
+            task_id,
+            field,
+            previous: previous.map(str::to_string),
+            created_at: Utc::now(),
+        };
+        state.revisions.rows.insert(revision.id, revision.clone());
+        Ok(revision)
+    }
+
+    async fn get_task_revisions(&self, task_id: i64) -> Result<Vec<TaskRevision>> {
+        let state = self.state()?;
+        Ok(state
+            .revisions
+            .rows
+            .values()
+            .filter(|r| r.task_id == task_id)
+            .cloned()
+            .collect())
+    }
+
+    async fn create_smart_goal_turn(
+        &self,
+        task_id: i64,
+        role: TurnRole,
+        content: &str,
+    ) -> Result<SmartGoalTurn> {
+        let mut state = self.state()?;
+        if !state.tasks.rows.contains_key(&task_id) {
+            return Err(GewuError::task_not_found(task_id));
+        }
+        let turn = SmartGoalTurn {
+            id: state.smart_goal_turns.next_id(),
+            task_id,
+            role,
+            content: content.to_string(),
+            created_at: Utc::now(),
+        };
+        state.smart_goal_turns.rows.insert(turn.id, turn.clone());
+        Ok(turn)
+    }
+
+    async fn get_smart_goal_turns(&self, task_id: i64) -> Result<Vec<SmartGoalTurn>> {
+        let state = self.state()?;
+        Ok(state
+            .smart_goal_turns
+            .rows
+            .values()
+            .filter(|t| t.task_id == task_id)
+            .cloned()
+            .collect())
+    }
+
+    async fn get_milestones(&self, task_id: i64) -> Result<Vec<Milestone>> {
+        let state = self.state()?;
+        let mut milestones: Vec<Milestone> = state
+            .milestones
+            .rows
+            .values()
+            .filter(|m| m.task_id == task_id)
+            .cloned()
+            .collect();
+        milestones.sort_by_key(|m| (m.due_date, m.id));
+        Ok(milestones)
+    }
+
+    async fn update_milestone_completed(&self, id: i64, completed: bool) -> Result<()> {
+        let mut state = self.state()?;
+        if let Some(milestone) = state.milestones.rows.get_mut(&id) {
+            milestone.completed_at = completed.then(Utc::now);
+        }
+        Ok(())
+    }
+
+    async fn get_task_primer(&self, id: i64) -> Result<Option<String>> {
+        Ok(self.state()?.primers.get(&id).cloned())
+    }
+
+    async fn update_task_primer(&self, id: i64, primer: &str) -> Result<()> {
+        let mut state = self.state()?;
+        if let Some(task) = state.tasks.rows.get_mut(&id) {
+            task.updated_at = Utc::now();
+            state.primers.insert(id, primer.to_string());
+        }
+        Ok(())
+    }
+
+    async fn update_task_status(
+        &self,
+        id: i64,
+        from: TaskStatus,
+        to: TaskStatus,
+        reason: Option<&str>,
+    ) -> Result<()> {
+        self.state()?.transition(id, from, to, reason)
+    }
+
+    async fn get_task_transitions(&self, task_id: i64) -> Result<Vec<TaskTransition>> {
+        let state = self.state()?;
+        Ok(state
+            .transitions
+            .rows
+            .values()
+            .filter(|t| t.task_id == task_id)
+            .cloned()
+            .collect())
+    }
+
+    async fn get_task(&self, id: i64) -> Result<Option<Task>> {
+        Ok(self.state()?.tasks.rows.get(&id).cloned())
+    }
+
+    async fn get_tasks_by_status(&self, status: &[TaskStatus]) -> Result<Vec<Task>> {
+        let state = self.state()?;
+        let mut tasks: Vec<Task> = state
+            .tasks
+            .rows
+            .values()
+            .filter(|t| status.contains(&t.status))
+            .cloned()
+            .collect();
+        tasks.sort_by(|a, b| b.updated_at.cmp(&a.updated_at).then(b.id.cmp(&a.id)));
+        Ok(tasks)
+    }
+
+    async fn search(
+        &self,
+        query: &str,
+        filter: &SearchFilter,
+        limit: i64,
+    ) -> Result<Vec<SearchHit>> {
+        let terms: Vec<String> = words(query).collect();
+        if terms.is_empty() {
+            return Ok(vec![]);
+        }
+        let state = self.state()?;
+        let in_range = |at: DateTime<Utc>| {
+            filter.from.is_none_or(|from| at.date_naive() >= from)
+                && filter.to.is_none_or(|to| at.date_naive() <= to)
+        };
+        let status_matches =
+            |status: &TaskStatus| filter.statuses.is_empty() || filter.statuses.contains(status);
+
+        let mut hits = Vec::new();
+        for task in state.tasks.rows.values() {
+            if !status_matches(&task.status) || !in_range(task.created_at) {
+                continue;
+            }
+            let goal = task
+                .smart_goal
+                .as_ref()
+                .map(|goal| {
+                    [
+                        goal.specific.as_str(),
+                        &goal.measurable,
+                        &goal.achievable,
+                        &goal.relevant,
+                        &goal.time_bound,
+                    ]
+                    .join(" ")
+                })
+                .unwrap_or_default();
+            let motivation = task.motivation.as_deref().unwrap_or_default();
+            let text = [task.topic.as_str(), motivation, &goal].join(" ");
+            if occurrences(&text, &terms).is_none() {
+                continue;
+            }
+            // Weighted like the SQL indexes: topic over motivation over goal
+            let count = |text: &str| words(text).filter(|w| terms.contains(w)).count() as f32;
+            hits.push(SearchHit {
+                kind: SearchHitKind::Task,
+                id: task.id,
+                title: Some(task.topic.clone()),
+                snippet: highlight(&text, &terms),
+                status: Some(task.status.clone()),
+                rank: 4.0 * count(&task.topic) + 2.0 * count(motivation) + count(&goal),
+                created_at: task.created_at,
+            });
+        }
+
+        for record in state.records.rows.values() {
+            if !in_range(record.created_at) {
+                continue;
+            }
+            let Some(rank) = occurrences(&record.content, &terms) else {
+                continue;
+            };
+            let tasks: Vec<&Task> = state
+                .task_records
+                .iter()
+                .filter(|(_, record_id)| *record_id == record.id)
+                .filter_map(|(task_id, _)| state.tasks.rows.get(task_id))
+                .collect();
+            if !filter.statuses.is_empty() && !tasks.iter().any(|t| status_matches(&t.status)) {
+                continue;
+            }
+            let mut topics: Vec<(i64, &str)> =
+                tasks.iter().map(|t| (t.id, t.topic.as_str())).collect();
+            topics.sort();
+            hits.push(SearchHit {
+                kind: SearchHitKind::Record,
+                id: record.id,
+                title: (!topics.is_empty()).then(|| {
+                    topics
+                        .iter()
+                        .map(|(_, topic)| *topic)
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                }),
+                snippet: highlight(&record.content, &terms),
+                status: None,
+                rank: rank as f32,
+                created_at: record.created_at,
+            });
+        }
+
+        hits.sort_by(|a, b| {
+            b.rank
+                .total_cmp(&a.rank)
+                .then(b.created_at.cmp(&a.created_at))
+        });
+        hits.truncate(limit.max(0) as usize);
+        Ok(hits)
+    }
+
+    async fn delete_task(&self, id: i64) -> Result<()> {
+        let mut state = self.state()?;
+        state.tasks.rows.remove(&id);
+        state.primers.remove(&id);
+        state.task_records.retain(|(task_id, _)| *task_id != id);
+        state.reviews.rows.retain(|_, r| r.task_id != id);
+        state.transitions.rows.retain(|_, t| t.task_id != id);
+        state.archives.remove(&id);
+        state.smart_goal_turns.rows.retain(|_, t| t.task_id != id);
+        state.revisions.rows.retain(|_, r| r.task_id != id);
+        state.milestones.rows.retain(|_, m| m.task_id != id);
+        Ok(())
+    }
+
+    async fn create_record(&self, content: &str) -> Result<Record> {
+        let mut state = self.state()?;
+        let record = Record {
+            id: state.records.next_id(),
+            content: content.to_string(),
+            created_at: Utc::now(),
+        };
+        state.records.rows.insert(record.id, record.clone());
+        Ok(record)
+    }
+
+    async fn get_records(&self) -> Result<Vec<Record>> {
+        let state = self.state()?;
+        let mut records: Vec<Record> = state.records.rows.values().cloned().collect();
+        records.sort_by(|a, b| a.created_at.cmp(&b.created_at).then(a.id.cmp(&b.id)));
+        Ok(records)
+    }
+
+    async fn create_task_record(&self, task_id: i64, record_id: i64) -> Result<()> {
+        let mut state = self.state()?;
+        if !state.tasks.rows.contains_key(&task_id) {
+            return Err(GewuError::task_not_found(task_id));
+        }
+        if !state.records.rows.contains_key(&record_id) {
+            return Err(GewuError::NotFound {
+                kind: "Record",
+                id: record_id,
+            });
+        }
+        state.task_records.push((task_id, record_id));
+        Ok(())
+    }
+
+    async fn get_task_records(&self, task_id: i64) -> Result<Vec<Record>> {
+        let state = self.state()?;
+        Ok(state
+            .task_records
+            .iter()
+            .filter(|(id, _)| *id == task_id)
+            .filter_map(|(_, record_id)| state.records.rows.get(record_id).cloned())
+            .collect())
+    }
+
+    async fn get_task_reviews(&self, task_id: i64) -> Result<Vec<Review>> {
+        let state = self.state()?;
+        Ok(state
+            .reviews
+            .rows
+            .values()
+            .filter(|r| r.task_id == task_id)
+            .cloned()
+            .collect())
+    }
+
+    async fn update_review_answer(
+        &self,
+        id: i64,
+        answer: &str,
+        feedback: &str,
+        passed: bool,
+    ) -> Result<()> {
+        let mut state = self.state()?;
+        if let Some(review) = state.reviews.rows.get_mut(&id) {
+            review.user_answer = Some(answer.to_string());
+            review.ai_feedback = Some(feedback.to_string());
+            review.is_passed = passed;
+            review.updated_at = Utc::now();
+        }
+        Ok(())
+    }
+
+    async fn submit_summary(
+        &self,
+        task_id: i64,
+        from: TaskStatus,
+        summary: &str,
+        questions: &[String],
+        reason: &str,
+    ) -> Result<Vec<Review>> {
+        let mut state = self.state()?;
+        state.transition(task_id, from, TaskStatus::Reviewing, Some(reason))?;
+        let record = Record {
+            id: state.records.next_id(),
+            content: summary.to_string(),
+            created_at: Utc::now(),
+        };
+        state.task_records.push((task_id, record.id));
+        state.records.rows.insert(record.id, record);
+        Ok(state.replace_reviews(task_id, questions))
+    }
+
+    async fn complete_task(
+        &self,
+        task_id: i64,
+        from: TaskStatus,
+        summary: &str,
+        tags: &[String],
+        reason: &str,
+    ) -> Result<TaskArchive> {
+        let mut state = self.state()?;
+        state.transition(task_id, from, TaskStatus::Completed, Some(reason))?;
+        let archive = TaskArchive {
+            task_id,
+            topic: state.tasks.rows[&task_id].topic.clone(),
+            summary: summary.to_string(),
+            tags: tags.to_vec(),
+            completed_at: Utc::now(),
+        };
+        state.archives.insert(task_id, archive.clone());
+        Ok(archive)
+    }
+
+    async fn get_task_archives(&self) -> Result<Vec<TaskArchive>> {
+        let state = self.state()?;
+        let mut archives: Vec<TaskArchive> = state
+            .archives
+            .values()
+            .filter_map(|a| {
+                let task = state.tasks.rows.get(&a.task_id)?;
+                Some(TaskArchive {
+                    topic: task.topic.clone(),
+                    ..a.clone()
+                })
+            })
+            .collect();
+        archives.sort_by(|a, b| {
+            b.completed_at
+                .cmp(&a.completed_at)
+                .then(b.task_id.cmp(&a.task_id))
+        });
+        Ok(archives)
+    }
+
+    async fn create_rejected_attempt(
+        &self,
+        topic: &str,
+        motivation: &str,
+        reason: &str,
+    ) -> Result<RejectedAttempt> {
+        let mut state = self.state()?;
+        let attempt = RejectedAttempt {
+            id: state.rejected_attempts.next_id(),
+            topic: topic.to_string(),
+            motivation: motivation.to_string(),
+            reason: reason.to_string(),
+            created_at: Utc::now(),
+        };
+        state
+            .rejected_attempts
+            .rows
+            .insert(attempt.id, attempt.clone());
+        Ok(attempt)
+    }
+
+    async fn get_rejected_attempts(&self) -> Result<Vec<RejectedAttempt>> {
+        let state = self.state()?;
+        let mut attempts: Vec<RejectedAttempt> =
+            state.rejected_attempts.rows.values().cloned().collect();
+        attempts.sort_by(|a, b| b.created_at.cmp(&a.created_at).then(b.id.cmp(&a.id)));
+        Ok(attempts)
+    }
+    async fn restore(&self, backup: &Backup, existing_records: &HashMap<i64, i64>) -> Result<()> {
+        let mut state = self.state()?;
+
+        // Check every link before touching the state, so a bad backup leaves nothing behind
+        for (backup_id, id) in existing_records {
+            if !state.records.rows.contains_key(id) {
+                return Err(GewuError::invalid(format!(
+                    "Record #{} (#{} in the backup) not found",
+                    id, backup_id
+                )));
+            }
+        }
+        for entry in &backup.tasks {
+            for record_id in &entry.record_ids {
+                if !existing_records.contains_key(record_id)
+                    && !backup.records.iter().any(|r| r.id == *record_id)
+                {
+                    return Err(GewuError::invalid(format!(
+                        "Record #{} of task #{} is not in the backup",
+                        record_id, entry.task.id
+                    )));
+                }
+            }
+        }
+
+        let mut record_ids = existing_records.clone();
+        for record in &backup.records {
+            if record_ids.contains_key(&record.id) {
+                continue;
+            }
+            let id = state.records.next_id();
+            state.records.rows.insert(
+                id,
+                Record {
+                    id,
+                    ..record.clone()
+                },
+            );
+            record_ids.insert(record.id, id);
+        }
+
+        for entry in &backup.tasks {
+            let task_id = state.tasks.next_id();
+            state.tasks.rows.insert(
+                task_id,
+                Task {
+                    id: task_id,
+                    ..entry.task.clone()
+                },
+            );
+            if let Some(primer) = &entry.primer {
+                state.primers.insert(task_id, primer.clone());
+            }
+            for record_id in &entry.record_ids {
+                state.task_records.push((task_id, record_ids[record_id]));
+            }
+            for transition in &entry.transitions {
+                let id = state.transitions.next_id();
+                state.transitions.rows.insert(
+                    id,
+                    TaskTransition {
+                        id,
+                        task_id,
+                        ..transition.clone()
+                    },
+                );
+            }
+            for turn in &entry.smart_goal_turns {
+                let id = state.smart_goal_turns.next_id();
+                state.smart_goal_turns.rows.insert(
+                    id,
+                    SmartGoalTurn {
+                        id,
+                        task_id,
+                        ..turn.clone()
+                    },
+                );
+            }
+            for milestone in &entry.milestones {
+                let id = state.milestones.next_id();
+                state.milestones.rows.insert(
+                    id,
+                    Milestone {
+                        id,
+                        task_id,
+                        ..milestone.clone()
+                    },
+                );
+            }
+            for review in &entry.reviews {
+                let id = state.reviews.next_id();
+                state.reviews.rows.insert(
+                    id,
+                    Review {
+                        id,
+                        task_id,
+                        ..review.clone()
+                    },
+                );
+            }
+            for revision in &entry.revisions {
+                let id = state.revisions.next_id();
+                state.revisions.rows.insert(
+                    id,
+                    TaskRevision {
+                        id,
+                        task_id,
+                        ..revision.clone()
+                    },
+                );
+            }
+            if let Some(archive) = &entry.archive {
+                state.archives.insert(
+                    task_id,
+                    TaskArchive {
+                        task_id,
+                        ..archive.clone()
+                    },
+                );
+            }
+        }
+
+        for attempt in &backup.rejected_attempts {
+            let id = state.rejected_attempts.next_id();
+            state.rejected_attempts.rows.insert(
+                id,
+                RejectedAttempt {
+                    id,
+                    ..attempt.clone()
+                },
+            );
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::conformance::repository_conformance_tests;
+
+    repository_conformance_tests!(test_repo);
+
+    async fn test_repo() -> (MemoryRepo, ()) {
+        (MemoryRepo::new(), ())
+    }
+}
@@ -0,0 +1,513 @@
+use std::{
+    collections::{BTreeMap, VecDeque},
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+};
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    domain::models::{PastAttempt, Record, Review, SmartGoalTurn, Task},
+    ports::{
+        error::{GewuError, Result},
+        llm::{AnswerVerdict, GatekeeperVerdict, LlmClient, SmartGoalVerdict, TaskSummary},
+    },
+};
+
+/// Canned LLM responses queued per method, the format of the replay fixture files.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Cassette {
+    #[serde(default)]
+    pub audit_motivation: VecDeque<GatekeeperVerdict>,
+    #[serde(default)]
+    pub negotiate_smart_goal: VecDeque<SmartGoalVerdict>,
+    #[serde(default)]
+    pub match_tasks: VecDeque<Vec<i64>>,
+    /// The streamed chunks of each guide
+    #[serde(default)]
+    pub generate_guide_stream: VecDeque<Vec<String>>,
+    /// The streamed chunks of each primer
+    #[serde(default)]
+    pub generate_primer_stream: VecDeque<Vec<String>>,
+    #[serde(default)]
+    pub generate_questions: VecDeque<Vec<String>>,
+    #[serde(default)]
+    pub score_answer: VecDeque<AnswerVerdict>,
+    #[serde(default)]
+    pub summarize_task: VecDeque<TaskSummary>,
+}
+
+impl Cassette {
+    pub fn load(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        let content = std::fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&content)?)
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> anyhow::Result<()> {
+        if let Some(parent) = path.as_ref().parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// Drop the responses a previous run already replayed.
+    fn skip(&mut self, consumed: &BTreeMap<String, usize>) {
+        fn drop_front<T>(queue: &mut VecDeque<T>, n: Option<&usize>) {
+            let n = n.copied().unwrap_or_default().min(queue.len());
+            queue.drain(..n);
+        }
+        drop_front(&mut self.audit_motivation, consumed.get("audit_motivation"));
+        drop_front(
+            &mut self.negotiate_smart_goal,
+            consumed.get("negotiate_smart_goal"),
+        );
+        drop_front(&mut self.match_tasks, consumed.get("match_tasks"));
+        drop_front(
+            &mut self.generate_guide_stream,
+            consumed.get("generate_guide_stream"),
+        );
+        drop_front(
+            &mut self.generate_primer_stream,
+            consumed.get("generate_primer_stream"),
+        );
+        drop_front(
+            &mut self.generate_questions,
+            consumed.get("generate_questions"),
+        );
+        drop_front(&mut self.score_answer, consumed.get("score_answer"));
+        drop_front(&mut self.summarize_task, consumed.get("summarize_task"));
+    }
+}
+
+/// How many responses of each method the previous commands of a session have replayed,
+/// saved next to the fixture as `<fixture>.cursor`.
+#[derive(Debug)]
+struct Cursor {
+    path: PathBuf,
+    consumed: BTreeMap<String, usize>,
+}
+
+impl Cursor {
+    fn of(fixture: &Path) -> Self {
+        let mut path = fixture.as_os_str().to_owned();
+        path.push(".cursor");
+        Self {
+            path: path.into(),
+            consumed: BTreeMap::new(),
+        }
+    }
+
+    fn load(mut self) -> anyhow::Result<Self> {
+        if self.path.exists() {
+            self.consumed = serde_json::from_str(&std::fs::read_to_string(&self.path)?)?;
+        }
+        Ok(self)
+    }
+
+    fn advance(&mut self, method: &str) -> Result<()> {
+        *self.consumed.entry(method.to_string()).or_default() += 1;
+        let content = serde_json::to_string_pretty(&self.consumed)
+            .map_err(|e| GewuError::storage(e.to_string()))?;
+        std::fs::write(&self.path, content).map_err(GewuError::storage)
+    }
+}
+
+/// Deterministic `LlmClient` replaying canned responses in order, so flows can be tested
+/// without network. Fails when a method is called without a response left in its queue.
+#[derive(Debug, Default)]
+pub struct MockLlm {
+    cassette: Cassette,
+    cursor: Option<Cursor>,
+}
+
+impl MockLlm {
+    /// Replay a fixture across several commands: each command continues where the previous one
+    /// stopped, as tracked by the `<fixture>.cursor` file. Delete that file to start over.
+    pub fn resume_fixture(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        let cursor = Cursor::of(path.as_ref()).load()?;
+        let mut cassette = Cassette::load(path)?;
+        cassette.skip(&cursor.consumed);
+        Ok(Self {
+            cassette,
+            cursor: Some(cursor),
+        })
+    }
+
+    /// Take the next response of the method, moving the cursor past it.
+    fn next<T>(&mut self, queue: fn(&mut Cassette) -> &mut VecDeque<T>, method: &str) -> Result<T> {
+        let reply = queue(&mut self.cassette).pop_front().ok_or_else(|| {
+            GewuError::LlmUnavailable(format!("No canned response left for {}", method))
+        })?;
+        if let Some(cursor) = &mut self.cursor {
+            cursor.advance(method)?;
+        }
+        Ok(reply)
+    }
+}
+
+#[cfg(test)]
+impl MockLlm {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Replay the responses saved by [`Recorder`] from the start.
+    pub fn from_fixture(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        Ok(Self {
+            cassette: Cassette::load(path)?,
+            cursor: None,
+        })
+    }
+
+    pub fn with_audit_motivation(mut self, verdict: GatekeeperVerdict) -> Self {
+        self.cassette.audit_motivation.push_back(verdict);
+        self
+    }
+
+    pub fn with_negotiate_smart_goal(mut self, verdict: SmartGoalVerdict) -> Self {
+        self.cassette.negotiate_smart_goal.push_back(verdict);
+        self
+    }
+
+    pub fn with_match_tasks(mut self, task_ids: Vec<i64>) -> Self {
+        self.cassette.match_tasks.push_back(task_ids);
+        self
+    }
+
+    pub fn with_guide(mut self, chunks: Vec<String>) -> Self {
+        self.cassette.generate_guide_stream.push_back(chunks);
+        self
+    }
+
+    pub fn with_primer(mut self, chunks: Vec<String>) -> Self {
+        self.cassette.generate_primer_stream.push_back(chunks);
+        self
+    }
+
+    pub fn with_questions(mut self, questions: Vec<String>) -> Self {
+        self.cassette.generate_questions.push_back(questions);
+        self
+    }
+
+    pub fn with_score_answer(mut self, verdict: AnswerVerdict) -> Self {
+        self.cassette.score_answer.push_back(verdict);
+        self
+    }
+
+    pub fn with_summary(mut self, summary: TaskSummary) -> Self {
+        self.cassette.summarize_task.push_back(summary);
+        self
+    }
+
+    /// Whether every canned response has been consumed.
+    pub fn is_exhausted(&self) -> bool {
+        let c = &self.cassette;
+        c.audit_motivation.is_empty()
+            && c.negotiate_smart_goal.is_empty()
+            && c.match_tasks.is_empty()
+            && c.generate_guide_stream.is_empty()
+            && c.generate_primer_stream.is_empty()
+            && c.generate_questions.is_empty()
+            && c.score_answer.is_empty()
+            && c.summarize_task.is_empty()
+    }
+}
+
+/// Replay the canned chunks of a stream.
+async fn stream(chunks: Vec<String>) -> Result<tokio::sync::mpsc::Receiver<String>> {
+    let (tx, rx) = tokio::sync::mpsc::channel(chunks.len().max(1));
+    for chunk in chunks {
+        tx.send(chunk)
+            .await
+            .map_err(|e| GewuError::LlmUnavailable(e.to_string()))?;
+    }
+    Ok(rx)
+}
+
+#[async_trait]
+impl LlmClient for MockLlm {
+    async fn audit_motivation(
+        &mut self,
+        _topic: &str,
+        _motivation: &str,
+        _history: &[PastAttempt],
+    ) -> Result<GatekeeperVerdict> {
+        self.next(|c| &mut c.audit_motivation, "audit_motivation")
+    }
+
+    async fn negotiate_smart_goal(
+        &mut self,
+        _topic: &str,
+        _motivation: &str,
+        _transcript: &[SmartGoalTurn],
+        _message: &str,
+    ) -> Result<SmartGoalVerdict> {
+        self.next(|c| &mut c.negotiate_smart_goal, "negotiate_smart_goal")
+    }
+
+    async fn match_tasks(&mut self, _tasks: &[Task], _record: &str) -> Result<Vec<i64>> {
+        self.next(|c| &mut c.match_tasks, "match_tasks")
+    }
+
+    async fn generate_guide_stream(
+        &mut self,
+        _task: &Task,
+        _records: &[Record],
+    ) -> Result<tokio::sync::mpsc::Receiver<String>> {
+        stream(self.next(|c| &mut c.generate_guide_stream, "generate_guide_stream")?).await
+    }
+
+    async fn generate_primer_stream(
+        &mut self,
+        _task: &Task,
+    ) -> Result<tokio::sync::mpsc::Receiver<String>> {
+        stream(self.next(|c| &mut c.generate_primer_stream, "generate_primer_stream")?).await
+    }
+
+    async fn generate_questions(&mut self, _task: &Task, _summary: &str) -> Result<Vec<String>> {
+        self.next(|c| &mut c.generate_questions, "generate_questions")
+    }
+
+    async fn score_answer(
+        &mut self,
+        _task: &Task,
+        _question: &str,
+        _answer: &str,
+    ) -> Result<AnswerVerdict> {
+        self.next(|c| &mut c.score_answer, "score_answer")
+    }
+
+    async fn summarize_task(
+        &mut self,
+        _task: &Task,
+        _records: &[Record],
+        _reviews: &[Review],
+    ) -> Result<TaskSummary> {
+        self.next(|c| &mut c.summarize_task, "summarize_task")
+    }
+}
+
+/// Wraps a real `LlmClient` and saves every response to a fixture file for [`MockLlm`] to replay.
+pub struct Recorder<L: LlmClient> {
+    inner: L,
+    path: PathBuf,
+    cassette: Arc<Mutex<Cassette>>,
+}
+
+impl<L: LlmClient> Recorder<L> {
+    /// Append the responses to the fixture, so a session of several commands is recorded
+    /// into one file. Delete the file to record a fresh session.
+    pub fn new(inner: L, path: impl Into<PathBuf>) -> anyhow::Result<Self> {
+        let path = path.into();
+        let cassette = if path.exists() {
+            Cassette::load(&path)?
+        } else {
+            Cassette::default()
+        };
+        Ok(Self {
+            inner,
+            path,
+            cassette: Arc::new(Mutex::new(cassette)),
+        })
+    }
+
+    fn record(&self, f: impl FnOnce(&mut Cassette)) -> Result<()> {
+        let mut cassette = self
+            .cassette
+            .lock()
+            .map_err(|e| GewuError::storage(format!("Cassette lock poisoned: {}", e)))?;
+        f(&mut cassette);
+        cassette.save(&self.path).map_err(GewuError::storage)
+    }
+
+    /// Forward the chunks of a stream as they arrive and save the whole reply once it ends.
+    fn tee(
+        &self,
+        mut inner_rx: tokio::sync::mpsc::Receiver<String>,
+        push: fn(&mut Cassette, Vec<String>),
+    ) -> tokio::sync::mpsc::Receiver<String> {
+        let (tx, rx) = tokio::sync::mpsc::channel(100);
+        let cassette = self.cassette.clone();
+        let path = self.path.clone();
+
+        // `tx` lives until the end of the task, so the receiver only sees the end of the
+        // stream after the fixture is written.
+        tokio::spawn(async move {
+            let mut chunks = Vec::new();
+            while let Some(chunk) = inner_rx.recv().await {
+                chunks.push(chunk.clone());
+                if tx.send(chunk).await.is_err() {
+                    break;
+                }
+            }
+            if let Ok(mut cassette) = cassette.lock() {
+                push(&mut cassette, chunks);
+                let _ = cassette.save(&path);
+            }
+            drop(tx);
+        });
+
+        rx
+    }
+}
+
+#[async_trait]
+impl<L: LlmClient> LlmClient for Recorder<L> {
+    async fn audit_motivation(
+        &mut self,
+        topic: &str,
+        motivation: &str,
+        history: &[PastAttempt],
+    ) -> Result<GatekeeperVerdict> {
+        let verdict = self
+            .inner
+            .audit_motivation(topic, motivation, history)
+            .await?;
+        self.record(|c| c.audit_motivation.push_back(verdict.clone()))?;
+        Ok(verdict)
+    }
+
+    async fn negotiate_smart_goal(
+        &mut self,
+        topic: &str,
+        motivation: &str,
+        transcript: &[SmartGoalTurn],
+        message: &str,
+    ) -> Result<SmartGoalVerdict> {
+        let verdict = self
+            .inner
+            .negotiate_smart_goal(topic, motivation, transcript, message)
+            .await?;
+        self.record(|c| c.negotiate_smart_goal.push_back(verdict.clone()))?;
+        Ok(verdict)
+    }
+
+    async fn match_tasks(&mut self, tasks: &[Task], record: &str) -> Result<Vec<i64>> {
+        let task_ids = self.inner.match_tasks(tasks, record).await?;
+        self.record(|c| c.match_tasks.push_back(task_ids.clone()))?;
+        Ok(task_ids)
+    }
+
+    async fn generate_guide_stream(
+        &mut self,
+        task: &Task,
+        records: &[Record],
+    ) -> Result<tokio::sync::mpsc::Receiver<String>> {
+        let rx = self.inner.generate_guide_stream(task, records).await?;
+        Ok(self.tee(rx, |c, chunks| c.generate_guide_stream.push_back(chunks)))
+    }
+
+    async fn generate_primer_stream(
+        &mut self,
+        task: &Task,
+    ) -> Result<tokio::sync::mpsc::Receiver<String>> {
+        let rx = self.inner.generate_primer_stream(task).await?;
+        Ok(self.tee(rx, |c, chunks| c.generate_primer_stream.push_back(chunks)))
+    }
+
+    async fn generate_questions(&mut self, task: &Task, summary: &str) -> Result<Vec<String>> {
+        let questions = self.inner.generate_questions(task, summary).await?;
+        self.record(|c| c.generate_questions.push_back(questions.clone()))?;
+        Ok(questions)
+    }
+
+    async fn score_answer(
+        &mut self,
+        task: &Task,
+        question: &str,
+        answer: &str,
+    ) -> Result<AnswerVerdict> {
+        let verdict = self.inner.score_answer(task, question, answer).await?;
+        self.record(|c| c.score_answer.push_back(verdict.clone()))?;
+        Ok(verdict)
+    }
+
+    async fn summarize_task(
+        &mut self,
+        task: &Task,
+        records: &[Record],
+        reviews: &[Review],
+    ) -> Result<TaskSummary> {
+        let summary = self.inner.summarize_task(task, records, reviews).await?;
+        self.record(|c| c.summarize_task.push_back(summary.clone()))?;
+        Ok(summary)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+
+    use super::*;
+    use crate::domain::state::TaskStatus;
+
+    #[tokio::test]
+    async fn recorded_responses_should_replay_in_order() {
+        let path = std::env::temp_dir().join(format!("gewu-recorder-{}.json", std::process::id()));
+        let task = Task {
+            id: 1,
+            topic: "topic".to_string(),
+            motivation: None,
+            smart_goal: None,
+            deadline: None,
+            paused_until: None,
+            status: TaskStatus::Active,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        };
+
+        let inner = MockLlm::new()
+            .with_match_tasks(vec![1, 2])
+            .with_match_tasks(vec![])
+            .with_guide(vec!["### Next".to_string(), " Step".to_string()]);
+        let mut recorder = Recorder::new(inner, &path).unwrap();
+        assert_eq!(recorder.match_tasks(&[], "a").await.unwrap(), vec![1, 2]);
+        assert!(recorder.match_tasks(&[], "b").await.unwrap().is_empty());
+        let mut rx = recorder.generate_guide_stream(&task, &[]).await.unwrap();
+        while rx.recv().await.is_some() {}
+
+        let mut replay = MockLlm::from_fixture(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(replay.match_tasks(&[], "a").await.unwrap(), vec![1, 2]);
+        assert!(replay.match_tasks(&[], "b").await.unwrap().is_empty());
+        let mut rx = replay.generate_guide_stream(&task, &[]).await.unwrap();
+        let mut guide = String::new();
+        while let Some(chunk) = rx.recv().await {
+            guide.push_str(&chunk);
+        }
+        assert_eq!(guide, "### Next Step");
+        assert!(replay.is_exhausted());
+        assert!(replay.match_tasks(&[], "c").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn a_session_should_record_and_replay_across_commands() {
+        let path = std::env::temp_dir().join(format!("gewu-session-{}.json", std::process::id()));
+        let cursor = Cursor::of(&path).path;
+        let _ = std::fs::remove_file(&path);
+        let _ = std::fs::remove_file(&cursor);
+
+        // Each command of the session runs in its own process with a new recorder
+        let first = MockLlm::new().with_match_tasks(vec![1]);
+        let mut recorder = Recorder::new(first, &path).unwrap();
+        recorder.match_tasks(&[], "a").await.unwrap();
+        let second = MockLlm::new().with_match_tasks(vec![2]);
+        let mut recorder = Recorder::new(second, &path).unwrap();
+        recorder.match_tasks(&[], "b").await.unwrap();
+
+        let mut replay = MockLlm::resume_fixture(&path).unwrap();
+        assert_eq!(replay.match_tasks(&[], "a").await.unwrap(), vec![1]);
+        let mut replay = MockLlm::resume_fixture(&path).unwrap();
+        assert_eq!(replay.match_tasks(&[], "b").await.unwrap(), vec![2]);
+        assert!(replay.is_exhausted());
+
+        std::fs::remove_file(&cursor).unwrap();
+        let mut replay = MockLlm::resume_fixture(&path).unwrap();
+        assert_eq!(replay.match_tasks(&[], "a").await.unwrap(), vec![1]);
+
+        std::fs::remove_file(&path).unwrap();
+        std::fs::remove_file(&cursor).unwrap();
+    }
+}
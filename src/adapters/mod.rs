@@ -1,3 +1,9 @@
-pub mod deepseek;
+#[cfg(test)]
+mod conformance;
+pub mod memory_repo;
+pub mod mock_llm;
 pub mod models;
+pub mod openai_compatible;
 pub mod postgres_repo;
+pub mod sqlite_repo;
+pub mod structured_output;
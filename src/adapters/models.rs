@@ -1,8 +1,11 @@
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
-use sqlx::FromRow;
+use sqlx::{FromRow, types::Json};
 
-use crate::domain::state::TaskStatus;
+use crate::domain::{
+    models::{SearchHitKind, SmartGoalDetail, TaskField, TurnRole},
+    state::TaskStatus,
+};
 
 #[derive(Debug, FromRow, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "camelCase")]
@@ -10,7 +13,9 @@ pub struct Task {
     pub id: i64,
     pub topic: String,
     pub motivation: Option<String>,
-    pub smart_goal: Option<String>,
+    pub smart_goal: Option<Json<SmartGoalDetail>>,
+    pub deadline: Option<NaiveDate>,
+    pub paused_until: Option<NaiveDate>,
     pub status: TaskStatus,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
@@ -22,7 +27,9 @@ impl From<Task> for crate::domain::models::Task {
             id: value.id,
             topic: value.topic,
             motivation: value.motivation,
-            smart_goal: value.smart_goal,
+            smart_goal: value.smart_goal.map(|goal| goal.0),
+            deadline: value.deadline,
+            paused_until: value.paused_until,
             status: value.status,
             created_at: value.created_at,
             updated_at: value.updated_at,
@@ -47,3 +54,189 @@ impl From<Record> for crate::domain::models::Record {
         }
     }
 }
+
+#[derive(Debug, FromRow, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Review {
+    pub id: i64,
+    pub task_id: i64,
+    pub question: String,
+    pub user_answer: Option<String>,
+    pub ai_feedback: Option<String>,
+    pub is_passed: Option<bool>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<Review> for crate::domain::models::Review {
+    fn from(value: Review) -> Self {
+        crate::domain::models::Review {
+            id: value.id,
+            task_id: value.task_id,
+            question: value.question,
+            user_answer: value.user_answer,
+            ai_feedback: value.ai_feedback,
+            is_passed: value.is_passed.unwrap_or_default(),
+            created_at: value.created_at,
+            updated_at: value.updated_at,
+        }
+    }
+}
+
+#[derive(Debug, FromRow, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskTransition {
+    pub id: i64,
+    pub task_id: i64,
+    pub from_status: TaskStatus,
+    pub to_status: TaskStatus,
+    pub reason: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<TaskTransition> for crate::domain::models::TaskTransition {
+    fn from(value: TaskTransition) -> Self {
+        crate::domain::models::TaskTransition {
+            id: value.id,
+            task_id: value.task_id,
+            from_status: value.from_status,
+            to_status: value.to_status,
+            reason: value.reason,
+            created_at: value.created_at,
+        }
+    }
+}
+
+#[derive(Debug, FromRow)]
+pub struct TaskArchive {
+    pub task_id: i64,
+    pub topic: String,
+    pub summary: String,
+    pub tags: Json<Vec<String>>,
+    pub completed_at: DateTime<Utc>,
+}
+
+impl From<TaskArchive> for crate::domain::models::TaskArchive {
+    fn from(value: TaskArchive) -> Self {
+        crate::domain::models::TaskArchive {
+            task_id: value.task_id,
+            topic: value.topic,
+            summary: value.summary,
+            tags: value.tags.0,
+            completed_at: value.completed_at,
+        }
+    }
+}
+
+#[derive(Debug, FromRow)]
+pub struct RejectedAttempt {
+    pub id: i64,
+    pub topic: String,
+    pub motivation: String,
+    pub reason: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<RejectedAttempt> for crate::domain::models::RejectedAttempt {
+    fn from(value: RejectedAttempt) -> Self {
+        crate::domain::models::RejectedAttempt {
+            id: value.id,
+            topic: value.topic,
+            motivation: value.motivation,
+            reason: value.reason,
+            created_at: value.created_at,
+        }
+    }
+}
+
+#[derive(Debug, FromRow)]
+pub struct SmartGoalTurn {
+    pub id: i64,
+    pub task_id: i64,
+    pub role: TurnRole,
+    pub content: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<SmartGoalTurn> for crate::domain::models::SmartGoalTurn {
+    fn from(value: SmartGoalTurn) -> Self {
+        crate::domain::models::SmartGoalTurn {
+            id: value.id,
+            task_id: value.task_id,
+            role: value.role,
+            content: value.content,
+            created_at: value.created_at,
+        }
+    }
+}
+
+#[derive(Debug, FromRow)]
+pub struct SearchHit {
+    pub kind: SearchHitKind,
+    pub id: i64,
+    pub title: Option<String>,
+    pub snippet: String,
+    pub status: Option<TaskStatus>,
+    pub rank: f32,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<SearchHit> for crate::domain::models::SearchHit {
+    fn from(value: SearchHit) -> Self {
+        crate::domain::models::SearchHit {
+            kind: value.kind,
+            id: value.id,
+            title: value.title,
+            snippet: value.snippet,
+            status: value.status,
+            rank: value.rank,
+            created_at: value.created_at,
+        }
+    }
+}
+
+#[derive(Debug, FromRow)]
+pub struct TaskRevision {
+    pub id: i64,
+    pub task_id: i64,
+    pub field: TaskField,
+    pub previous: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<TaskRevision> for crate::domain::models::TaskRevision {
+    fn from(value: TaskRevision) -> Self {
+        crate::domain::models::TaskRevision {
+            id: value.id,
+            task_id: value.task_id,
+            field: value.field,
+            previous: value.previous,
+            created_at: value.created_at,
+        }
+    }
+}
+
+#[derive(Debug, FromRow)]
+pub struct Milestone {
+    pub id: i64,
+    pub task_id: i64,
+    pub title: String,
+    pub criterion: String,
+    pub due_date: NaiveDate,
+    pub completed_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<Milestone> for crate::domain::models::Milestone {
+    fn from(value: Milestone) -> Self {
+        crate::domain::models::Milestone {
+            id: value.id,
+            task_id: value.task_id,
+            title: value.title,
+            criterion: value.criterion,
+            due_date: value.due_date,
+            completed_at: value.completed_at,
+            created_at: value.created_at,
+        }
+    }
+}
@@ -0,0 +1,472 @@
+use async_trait::async_trait;
+use futures_util::StreamExt;
+use openai_api_rs::v1::{
+    api::OpenAIClient,
+    chat_completion::{
+        ChatCompletionMessage, Content, MessageRole,
+        chat_completion::ChatCompletionRequest,
+        chat_completion_stream::{ChatCompletionStreamRequest, ChatCompletionStreamResponse},
+    },
+};
+
+use crate::{
+    adapters::structured_output::{self, MAX_ATTEMPTS, StructuredOutput, StructuredOutputError},
+    config::LlmConfig,
+    domain::models::{PastAttempt, Record, Review, SmartGoalTurn, Task, TurnRole},
+    ports::{
+        error::{GewuError, Result},
+        llm::{
+            AnswerVerdict, GatekeeperVerdict, GenerateQuestionsResult, LlmClient, MatchTasksResult,
+            SmartGoalVerdict, TaskSummary,
+        },
+    },
+    services::prompts::{
+        audit_motivation_prompt, generate_guide_prompt, generate_primer_prompt,
+        generate_questions_prompt, match_tasks_prompt, negotiate_smart_goal_prompt,
+        score_answer_prompt, summarize_task_prompt,
+    },
+};
+
+/// LLM client for any endpoint speaking the OpenAI chat completion API,
+/// e.g. DeepSeek, OpenAI, Moonshot, vLLM or Ollama.
+pub struct OpenAiCompatible {
+    client: OpenAIClient,
+    model: String,
+    temperature: Option<f64>,
+}
+
+impl OpenAiCompatible {
+    pub fn try_new(config: LlmConfig) -> anyhow::Result<Self> {
+        let mut builder = OpenAIClient::builder().with_endpoint(config.base_url);
+        if let Some(api_key) = config.api_key {
+            builder = builder.with_api_key(api_key);
+        }
+        let client = builder
+            .build()
+            .map_err(|e| anyhow::anyhow!("Failed to build OpenAIClient: {}", e))?;
+
+        Ok(Self {
+            client,
+            model: config.model,
+            temperature: config.temperature,
+        })
+    }
+
+    fn chat_request(&self, messages: Vec<ChatCompletionMessage>) -> ChatCompletionRequest {
+        let mut request = ChatCompletionRequest::new(self.model.clone(), messages);
+        request.temperature = self.temperature;
+        request
+    }
+
+    fn stream_chat_request(&self, content: String) -> ChatCompletionStreamRequest {
+        let mut request = ChatCompletionStreamRequest::new(
+            self.model.clone(),
+            vec![message(MessageRole::user, content)],
+        );
+        request.temperature = self.temperature;
+        request
+    }
+
+    /// Send the prompt and parse the reply as `T`.
+    async fn complete_json<T: StructuredOutput>(&mut self, prompt: String) -> Result<T> {
+        self.complete_conversation_json(vec![message(MessageRole::user, prompt)])
+            .await
+    }
+
+    /// Send the conversation and parse the reply as `T`.
+    async fn complete_conversation_json<T: StructuredOutput>(
+        &mut self,
+        messages: Vec<ChatCompletionMessage>,
+    ) -> Result<T> {
+        Ok(complete_with_retries(self, messages).await??)
+    }
+
+    /// Send the prompt and forward the streamed reply chunk by chunk.
+    async fn stream(&mut self, prompt: String) -> Result<tokio::sync::mpsc::Receiver<String>> {
+        let mut stream = self
+            .client
+            .chat_completion_stream(self.stream_chat_request(prompt))
+            .await
+            .map_err(|e| GewuError::LlmUnavailable(format!("Failed to start streaming: {}", e)))?;
+
+        let (tx, rx) = tokio::sync::mpsc::channel(100);
+
+        tokio::spawn(async move {
+            while let Some(response) = stream.next().await {
+                let content = match response {
+                    ChatCompletionStreamResponse::Content(content) => content,
+                    ChatCompletionStreamResponse::Done => break,
+                    _ => continue,
+                };
+                if tx.send(content).await.is_err() {
+                    break;
+                }
+            }
+        });
+
+        Ok(rx)
+    }
+}
+
+/// Sends a conversation to the model and returns the text of its reply.
+#[async_trait]
+trait ChatTransport: Send {
+    async fn send(&mut self, messages: Vec<ChatCompletionMessage>) -> Result<String>;
+}
+
+#[async_trait]
+impl ChatTransport for OpenAiCompatible {
+    async fn send(&mut self, messages: Vec<ChatCompletionMessage>) -> Result<String> {
+        let response = self
+            .client
+            .chat_completion(self.chat_request(messages))
+            .await
+            .map_err(|e| GewuError::LlmUnavailable(e.to_string()))?;
+        Ok(response
+            .choices
+            .first()
+            .and_then(|choice| choice.message.content.clone())
+            .unwrap_or_default())
+    }
+}
+
+/// Send the conversation and parse the reply as `T`. A malformed reply is sent back to the
+/// model together with the parse error, up to [`MAX_ATTEMPTS`] times.
+/// The outer error is the transport failing, the inner one the model never answering valid JSON.
+async fn complete_with_retries<T: StructuredOutput>(
+    transport: &mut impl ChatTransport,
+    mut messages: Vec<ChatCompletionMessage>,
+) -> Result<std::result::Result<T, StructuredOutputError>> {
+    let mut last = StructuredOutputError::Empty;
+
+    for _ in 0..MAX_ATTEMPTS {
+        let content = transport.send(messages.clone()).await?;
+
+        match structured_output::parse(&content) {
+            Ok(value) => return Ok(Ok(value)),
+            Err(e) => {
+                messages.push(message(MessageRole::assistant, content));
+                messages.push(message(
+                    MessageRole::user,
+                    format!(
+                        "Your reply could not be parsed: {}. Reply again with only the JSON object described above, without markdown or any other text.",
+                        e
+                    ),
+                ));
+                last = e;
+            }
+        }
+    }
+
+    Ok(Err(StructuredOutputError::RetriesExhausted {
+        attempts: MAX_ATTEMPTS,
+        last: Box::new(last),
+    }))
+}
+
+#[async_trait]
+impl LlmClient for OpenAiCompatible {
+    async fn audit_motivation(
+        &mut self,
+        topic: &str,
+        motivation: &str,
+        history: &[PastAttempt],
+    ) -> Result<GatekeeperVerdict> {
+        let history_json_str = if history.is_empty() {
+            "None".to_string()
+        } else {
+            to_json(history)?
+        };
+        let prompt = audit_motivation_prompt(topic, motivation, &history_json_str);
+        self.complete_json(prompt).await
+    }
+
+    async fn negotiate_smart_goal(
+        &mut self,
+        topic: &str,
+        motivation: &str,
+        transcript: &[SmartGoalTurn],
+        content: &str,
+    ) -> Result<SmartGoalVerdict> {
+        let mut messages = vec![message(
+            MessageRole::system,
+            negotiate_smart_goal_prompt(
+                topic,
+                motivation,
+                &chrono::Local::now().date_naive().to_string(),
+            ),
+        )];
+        for turn in transcript {
+            let role = match turn.role {
+                TurnRole::Learner => MessageRole::user,
+                TurnRole::Strategist => MessageRole::assistant,
+            };
+            messages.push(message(role, turn.content.clone()));
+        }
+        messages.push(message(MessageRole::user, content.to_string()));
+        self.complete_conversation_json(messages).await
+    }
+
+    async fn match_tasks(&mut self, tasks: &[Task], record: &str) -> Result<Vec<i64>> {
+        let task_json_str = to_json(tasks)?;
+        let prompt = match_tasks_prompt(&task_json_str, record);
+        let result: MatchTasksResult = self.complete_json(prompt).await?;
+        Ok(result.task_ids)
+    }
+
+    async fn generate_guide_stream(
+        &mut self,
+        task: &Task,
+        records: &[Record],
+    ) -> Result<tokio::sync::mpsc::Receiver<String>> {
+        let task_json_str = to_json(task)?;
+        let record_json_str = to_json(records)?;
+        let prompt = generate_guide_prompt(&task_json_str, &record_json_str);
+
+        self.stream(prompt).await
+    }
+
+    async fn generate_primer_stream(
+        &mut self,
+        task: &Task,
+    ) -> Result<tokio::sync::mpsc::Receiver<String>> {
+        let task_json_str = to_json(task)?;
+        let prompt = generate_primer_prompt(&task_json_str);
+        self.stream(prompt).await
+    }
+
+    async fn generate_questions(&mut self, task: &Task, summary: &str) -> Result<Vec<String>> {
+        let task_json_str = to_json(task)?;
+        let prompt = generate_questions_prompt(&task_json_str, summary);
+        let result: GenerateQuestionsResult = self.complete_json(prompt).await?;
+        Ok(result.questions)
+    }
+
+    async fn score_answer(
+        &mut self,
+        task: &Task,
+        question: &str,
+        answer: &str,
+    ) -> Result<AnswerVerdict> {
+        let task_json_str = to_json(task)?;
+        let prompt = score_answer_prompt(&task_json_str, question, answer);
+        self.complete_json(prompt).await
+    }
+
+    async fn summarize_task(
+        &mut self,
+        task: &Task,
+        records: &[Record],
+        reviews: &[Review],
+    ) -> Result<TaskSummary> {
+        let task_json_str = to_json(task)?;
+        let record_json_str = to_json(records)?;
+        let review_json_str = to_json(reviews)?;
+        let prompt = summarize_task_prompt(&task_json_str, &record_json_str, &review_json_str);
+        self.complete_json(prompt).await
+    }
+}
+
+fn message(role: MessageRole, content: String) -> ChatCompletionMessage {
+    ChatCompletionMessage {
+        role,
+        content: Content::Text(content),
+        name: None,
+        tool_calls: None,
+        tool_call_id: None,
+    }
+}
+
+/// Encode a value embedded in a prompt.
+fn to_json<T: serde::Serialize + ?Sized>(value: &T) -> Result<String> {
+    serde_json::to_string(value)
+        .map_err(|e| GewuError::LlmUnavailable(format!("Failed to encode the prompt: {}", e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::VecDeque;
+
+    use super::*;
+    use crate::config::LlmProvider;
+
+    /// Answers with canned replies and keeps the conversations it was sent.
+    #[derive(Default)]
+    struct ScriptedTransport {
+        replies: VecDeque<String>,
+        sent: Vec<Vec<ChatCompletionMessage>>,
+    }
+
+    impl ScriptedTransport {
+        fn new(replies: &[&str]) -> Self {
+            Self {
+                replies: replies.iter().map(|r| r.to_string()).collect(),
+                sent: Vec::new(),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl ChatTransport for ScriptedTransport {
+        async fn send(&mut self, messages: Vec<ChatCompletionMessage>) -> Result<String> {
+            self.sent.push(messages);
+            self.replies
+                .pop_front()
+                .ok_or_else(|| GewuError::LlmUnavailable("No reply left".to_string()))
+        }
+    }
+
+    fn prompt() -> Vec<ChatCompletionMessage> {
+        vec![message(
+            MessageRole::user,
+            "Audit this motivation".to_string(),
+        )]
+    }
+
+    #[tokio::test]
+    async fn a_malformed_reply_should_be_retried_with_the_parse_error() {
+        let mut transport = ScriptedTransport::new(&[
+            "Sure! The motivation looks fine.",
+            r#"{"passed": true, "reason": "Concrete", "recommendation": "Go"}"#,
+        ]);
+
+        let verdict: GatekeeperVerdict = complete_with_retries(&mut transport, prompt())
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert!(verdict.passed);
+        assert_eq!(transport.sent.len(), 2);
+        let retry = &transport.sent[1];
+        assert_eq!(retry.len(), 3);
+        assert!(matches!(retry[1].role, MessageRole::assistant));
+        assert!(matches!(retry[2].role, MessageRole::user));
+        match &retry[2].content {
+            Content::Text(text) => assert!(text.contains("No JSON object found")),
+            _ => panic!("expected a text correction"),
+        }
+    }
+
+    #[tokio::test]
+    async fn three_malformed_replies_should_exhaust_the_retries() {
+        let mut transport = ScriptedTransport::new(&["no", "still no", r#"{"passed": true}"#]);
+
+        let error = complete_with_retries::<GatekeeperVerdict>(&mut transport, prompt())
+            .await
+            .unwrap()
+            .unwrap_err();
+
+        assert!(matches!(
+            error,
+            StructuredOutputError::RetriesExhausted { attempts: 3, .. }
+        ));
+        assert_eq!(transport.sent.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn a_failing_transport_should_not_be_retried() {
+        let mut transport = ScriptedTransport::new(&[]);
+
+        let error = complete_with_retries::<GatekeeperVerdict>(&mut transport, prompt())
+            .await
+            .unwrap_err();
+
+        assert!(matches!(error, GewuError::LlmUnavailable(_)));
+        assert_eq!(transport.sent.len(), 1);
+    }
+
+    #[tokio::test]
+    #[ignore = "skip test that requires third party service"]
+    async fn audit_motivation_too_vague_should_reject() {
+        let mut deepseek = deepseek();
+
+        let result = deepseek
+            .audit_motivation("Rust", "I want to learn Rust", &[])
+            .await
+            .unwrap();
+
+        println!("{}", serde_json::to_string_pretty(&result).unwrap());
+
+        assert!(!result.passed);
+        assert!(!result.reason.is_empty());
+        assert!(!result.recommendation.is_empty());
+    }
+
+    #[tokio::test]
+    #[ignore = "skip test that requires third party service"]
+    async fn audit_motivation_addresses_current_pain_point_should_approve() {
+        let mut deepseek = deepseek();
+
+        let result = deepseek
+            .audit_motivation(
+                "building a code review ai agent",
+                "I want to build a code review ai agent to improve my code review skills, as well as learn how to build ai agents",
+                &[],
+            )
+            .await
+            .unwrap();
+
+        println!("{}", serde_json::to_string_pretty(&result).unwrap());
+
+        assert!(result.passed);
+        assert!(!result.reason.is_empty());
+        assert!(!result.recommendation.is_empty());
+    }
+
+    #[tokio::test]
+    #[ignore = "skip test that requires third party service"]
+    async fn negotiate_smart_goal_should_reject_bad_goal() {
+        let mut deepseek = deepseek();
+
+        let result = deepseek
+            .negotiate_smart_goal(
+                "learning Rust",
+                "To become a better software engineer for my next project at work.",
+                &[],
+                "I want to get better.",
+            )
+            .await
+            .unwrap();
+
+        println!("{}", serde_json::to_string_pretty(&result).unwrap());
+
+        assert!(!result.passed);
+        assert!(!result.reason.is_empty());
+        assert!(result.guidance.is_some());
+        assert!(result.refined_goal.is_none());
+    }
+
+    #[tokio::test]
+    #[ignore = "skip test that requires third party service"]
+    async fn negotiate_smart_goal_should_approve_good_goal() {
+        let mut deepseek = deepseek();
+
+        let result = deepseek
+            .negotiate_smart_goal(
+                "learning Rust",
+                "To become a better software engineer for my next project at work.",
+                &[],
+                "Within one month, complete the official Rust book and build a small CLI tool to automate part of my workflow, measuring success by completing at least one practical project and passing all end-of-chapter exercises.",
+            )
+            .await
+            .unwrap();
+
+        println!("{}", serde_json::to_string_pretty(&result).unwrap());
+
+        assert!(result.passed);
+        assert!(!result.reason.is_empty());
+        assert!(result.refined_goal.is_some());
+
+        let detail = result.refined_goal.unwrap();
+        assert!(!detail.specific.is_empty());
+        assert!(!detail.measurable.is_empty());
+        assert!(!detail.achievable.is_empty());
+        assert!(!detail.relevant.is_empty());
+        assert!(!detail.time_bound.is_empty());
+    }
+
+    fn deepseek() -> OpenAiCompatible {
+        dotenv::dotenv().ok();
+        OpenAiCompatible::try_new(LlmConfig::preset(LlmProvider::DeepSeek)).unwrap()
+    }
+}
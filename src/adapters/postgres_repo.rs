@@ -1,11 +1,26 @@
-use anyhow::Result;
+use std::collections::HashMap;
+
 use async_trait::async_trait;
-use sqlx::PgPool;
+use chrono::NaiveDate;
+use sqlx::{PgConnection, PgPool, types::Json};
 
 use crate::{
-    adapters::models::{Record, Task},
-    domain::state::TaskStatus,
-    ports::repository::Repository,
+    adapters::models::{
+        Milestone, Record, RejectedAttempt, Review, SearchHit, SmartGoalTurn, Task, TaskArchive,
+        TaskRevision, TaskTransition,
+    },
+    domain::{
+        backup::Backup,
+        models::{
+            HIGHLIGHT_END, HIGHLIGHT_START, SearchFilter, SmartGoalDetail, TaskField, TurnRole,
+        },
+        state::TaskStatus,
+    },
+    ports::{
+        error::{GewuError, Result},
+        llm::MilestonePlan,
+        repository::Repository,
+    },
 };
 
 pub struct PostgresRepo {
@@ -18,6 +33,115 @@ impl PostgresRepo {
     }
 }
 
+/// Move the task from `from` to `to` and record the transition, within the caller's transaction.
+async fn transition(
+    conn: &mut PgConnection,
+    id: i64,
+    from: &TaskStatus,
+    to: &TaskStatus,
+    reason: Option<&str>,
+) -> Result<()> {
+    let result = sqlx::query(
+        r#"
+        UPDATE tasks SET status = $1, updated_at = $2 WHERE id = $3 AND status = $4
+        "#,
+    )
+    .bind(to)
+    .bind(chrono::Utc::now())
+    .bind(id)
+    .bind(from)
+    .execute(&mut *conn)
+    .await?;
+    if result.rows_affected() == 0 {
+        return Err(GewuError::status_conflict(format!(
+            "Task #{} is not in {} status",
+            id, from
+        )));
+    }
+
+    sqlx::query(
+        r#"
+        INSERT INTO task_transitions (task_id, from_status, to_status, reason)
+        VALUES ($1, $2, $3, $4)
+        "#,
+    )
+    .bind(id)
+    .bind(from)
+    .bind(to)
+    .bind(reason)
+    .execute(&mut *conn)
+    .await?;
+    Ok(())
+}
+
+/// Replace the review questions of a task with unanswered ones, within the caller's transaction.
+async fn replace_reviews(
+    conn: &mut PgConnection,
+    task_id: i64,
+    questions: &[String],
+) -> Result<Vec<crate::domain::models::Review>> {
+    sqlx::query(
+        r#"
+        DELETE FROM reviews WHERE task_id = $1
+        "#,
+    )
+    .bind(task_id)
+    .execute(&mut *conn)
+    .await?;
+
+    let mut reviews = Vec::with_capacity(questions.len());
+    for question in questions {
+        let review: Review = sqlx::query_as(
+            r#"
+            INSERT INTO reviews (task_id, question)
+            VALUES ($1, $2)
+            RETURNING id, task_id, question, user_answer, ai_feedback, is_passed, created_at, updated_at;
+            "#,
+        )
+        .bind(task_id)
+        .bind(question)
+        .fetch_one(&mut *conn)
+        .await?;
+        reviews.push(review.into());
+    }
+    Ok(reviews)
+}
+
+/// Insert or replace the archive of a task, within the caller's transaction.
+async fn archive(
+    conn: &mut PgConnection,
+    task_id: i64,
+    summary: &str,
+    tags: &[String],
+) -> Result<crate::domain::models::TaskArchive> {
+    sqlx::query(
+        r#"
+        INSERT INTO task_archives (task_id, summary, tags, completed_at)
+        VALUES ($1, $2, $3, $4)
+        ON CONFLICT (task_id) DO UPDATE
+        SET summary = excluded.summary, tags = excluded.tags, completed_at = excluded.completed_at
+        "#,
+    )
+    .bind(task_id)
+    .bind(summary)
+    .bind(Json(tags))
+    .bind(chrono::Utc::now())
+    .execute(&mut *conn)
+    .await?;
+
+    let archive: TaskArchive = sqlx::query_as(
+        r#"
+        SELECT a.task_id, t.topic, a.summary, a.tags, a.completed_at FROM task_archives a
+        INNER JOIN tasks t ON t.id = a.task_id
+        WHERE a.task_id = $1
+        "#,
+    )
+    .bind(task_id)
+    .fetch_one(&mut *conn)
+    .await?;
+    Ok(archive.into())
+}
+
 #[async_trait]
 impl Repository for PostgresRepo {
     async fn create_task(
@@ -29,7 +153,7 @@ impl Repository for PostgresRepo {
             r#"
             INSERT INTO tasks (topic, motivation, status)
             VALUES ($1, $2, $3)
-            RETURNING id, topic, motivation, smart_goal, status, created_at, updated_at;
+            RETURNING id, topic, motivation, smart_goal, deadline, paused_until, status, created_at, updated_at;
             "#,
         )
         .bind(topic)
@@ -41,15 +165,14 @@ impl Repository for PostgresRepo {
         Ok(task.into())
     }
 
-    async fn update_task_smart_goal(&self, id: i64, smart_goal: &str) -> Result<()> {
+    async fn update_task_topic(&self, id: i64, topic: &str) -> Result<()> {
         sqlx::query(
             r#"
-            UPDATE tasks SET smart_goal = $1, updated_at = $2, status = $3 WHERE id = $4
+            UPDATE tasks SET topic = $1, updated_at = $2 WHERE id = $3
             "#,
         )
-        .bind(smart_goal)
+        .bind(topic)
         .bind(chrono::Utc::now())
-        .bind(TaskStatus::Active)
         .bind(id)
         .execute(&self.pool)
         .await?;
@@ -57,10 +180,297 @@ impl Repository for PostgresRepo {
         Ok(())
     }
 
+    async fn update_task_motivation(&self, id: i64, motivation: &str) -> Result<()> {
+        sqlx::query(
+            r#"
+            UPDATE tasks SET motivation = $1, updated_at = $2 WHERE id = $3
+            "#,
+        )
+        .bind(motivation)
+        .bind(chrono::Utc::now())
+        .bind(id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn update_task_smart_goal(&self, id: i64, smart_goal: &SmartGoalDetail) -> Result<()> {
+        sqlx::query(
+            r#"
+            UPDATE tasks SET smart_goal = $1, updated_at = $2 WHERE id = $3
+            "#,
+        )
+        .bind(Json(smart_goal))
+        .bind(chrono::Utc::now())
+        .bind(id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn approve_smart_goal(
+        &self,
+        id: i64,
+        from: TaskStatus,
+        smart_goal: &SmartGoalDetail,
+        milestones: &[MilestonePlan],
+        deadline: Option<NaiveDate>,
+        reason: &str,
+    ) -> Result<()> {
+        let now = chrono::Utc::now();
+        let mut tx = self.pool.begin().await?;
+        transition(&mut tx, id, &from, &TaskStatus::Active, Some(reason)).await?;
+
+        sqlx::query(
+            r#"
+            UPDATE tasks SET smart_goal = $1, deadline = COALESCE($2, deadline), updated_at = $3
+            WHERE id = $4
+            "#,
+        )
+        .bind(Json(smart_goal))
+        .bind(deadline)
+        .bind(now)
+        .bind(id)
+        .execute(&mut *tx)
+        .await?;
+
+        for milestone in milestones {
+            sqlx::query(
+                r#"
+                INSERT INTO milestones (task_id, title, criterion, due_date, created_at)
+                VALUES ($1, $2, $3, $4, $5)
+                "#,
+            )
+            .bind(id)
+            .bind(&milestone.title)
+            .bind(&milestone.criterion)
+            .bind(milestone.due_date)
+            .bind(now)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn update_task_deadline(&self, id: i64, deadline: Option<NaiveDate>) -> Result<()> {
+        sqlx::query(
+            r#"
+            UPDATE tasks SET deadline = $1, updated_at = $2 WHERE id = $3
+            "#,
+        )
+        .bind(deadline)
+        .bind(chrono::Utc::now())
+        .bind(id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn update_task_pause(
+        &self,
+        id: i64,
+        from: TaskStatus,
+        to: TaskStatus,
+        paused_until: Option<NaiveDate>,
+        reason: &str,
+    ) -> Result<()> {
+        let mut tx = self.pool.begin().await?;
+        transition(&mut tx, id, &from, &to, Some(reason)).await?;
+
+        sqlx::query(
+            r#"
+            UPDATE tasks SET paused_until = $1 WHERE id = $2
+            "#,
+        )
+        .bind(paused_until)
+        .bind(id)
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn create_task_revision(
+        &self,
+        task_id: i64,
+        field: TaskField,
+        previous: Option<&str>,
+    ) -> Result<crate::domain::models::TaskRevision> {
+        let revision: TaskRevision = sqlx::query_as(
+            r#"
+            INSERT INTO task_revisions (task_id, field, previous, created_at)
+            VALUES ($1, $2, $3, $4)
+            RETURNING id, task_id, field, previous, created_at;
+            "#,
+        )
+        .bind(task_id)
+        .bind(field)
+        .bind(previous)
+        .bind(chrono::Utc::now())
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(revision.into())
+    }
+
+    async fn get_task_revisions(
+        &self,
+        task_id: i64,
+    ) -> Result<Vec<crate::domain::models::TaskRevision>> {
+        let revisions: Vec<TaskRevision> = sqlx::query_as(
+            r#"
+            SELECT id, task_id, field, previous, created_at FROM task_revisions
+            WHERE task_id = $1
+            ORDER BY id
+            "#,
+        )
+        .bind(task_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(revisions.into_iter().map(TaskRevision::into).collect())
+    }
+
+    async fn create_smart_goal_turn(
+        &self,
+        task_id: i64,
+        role: TurnRole,
+        content: &str,
+    ) -> Result<crate::domain::models::SmartGoalTurn> {
+        let turn: SmartGoalTurn = sqlx::query_as(
+            r#"
+            INSERT INTO smart_goal_turns (task_id, role, content, created_at)
+            VALUES ($1, $2, $3, $4)
+            RETURNING id, task_id, role, content, created_at;
+            "#,
+        )
+        .bind(task_id)
+        .bind(role)
+        .bind(content)
+        .bind(chrono::Utc::now())
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(turn.into())
+    }
+
+    async fn get_smart_goal_turns(
+        &self,
+        task_id: i64,
+    ) -> Result<Vec<crate::domain::models::SmartGoalTurn>> {
+        let turns: Vec<SmartGoalTurn> = sqlx::query_as(
+            r#"
+            SELECT id, task_id, role, content, created_at FROM smart_goal_turns
+            WHERE task_id = $1
+            ORDER BY id
+            "#,
+        )
+        .bind(task_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(turns.into_iter().map(SmartGoalTurn::into).collect())
+    }
+
+    async fn get_milestones(&self, task_id: i64) -> Result<Vec<crate::domain::models::Milestone>> {
+        let milestones: Vec<Milestone> = sqlx::query_as(
+            r#"
+            SELECT id, task_id, title, criterion, due_date, completed_at, created_at FROM milestones
+            WHERE task_id = $1
+            ORDER BY due_date, id
+            "#,
+        )
+        .bind(task_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(milestones.into_iter().map(Milestone::into).collect())
+    }
+
+    async fn update_milestone_completed(&self, id: i64, completed: bool) -> Result<()> {
+        let completed_at = completed.then(chrono::Utc::now);
+        sqlx::query(
+            r#"
+            UPDATE milestones SET completed_at = $1 WHERE id = $2
+            "#,
+        )
+        .bind(completed_at)
+        .bind(id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn get_task_primer(&self, id: i64) -> Result<Option<String>> {
+        let primer: Option<Option<String>> = sqlx::query_scalar(
+            r#"
+            SELECT primer_content FROM tasks WHERE id = $1
+            "#,
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(primer.flatten())
+    }
+
+    async fn update_task_primer(&self, id: i64, primer: &str) -> Result<()> {
+        sqlx::query(
+            r#"
+            UPDATE tasks SET primer_content = $1, updated_at = $2 WHERE id = $3
+            "#,
+        )
+        .bind(primer)
+        .bind(chrono::Utc::now())
+        .bind(id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn update_task_status(
+        &self,
+        id: i64,
+        from: TaskStatus,
+        to: TaskStatus,
+        reason: Option<&str>,
+    ) -> Result<()> {
+        let mut tx = self.pool.begin().await?;
+        transition(&mut tx, id, &from, &to, reason).await?;
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn get_task_transitions(
+        &self,
+        task_id: i64,
+    ) -> Result<Vec<crate::domain::models::TaskTransition>> {
+        let transitions: Vec<TaskTransition> = sqlx::query_as(
+            r#"
+            SELECT id, task_id, from_status, to_status, reason, created_at FROM task_transitions
+            WHERE task_id = $1
+            ORDER BY id
+            "#,
+        )
+        .bind(task_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(transitions.into_iter().map(TaskTransition::into).collect())
+    }
+
     async fn get_task(&self, id: i64) -> Result<Option<crate::domain::models::Task>> {
         let task: Option<Task> = sqlx::query_as(
             r#"
-            SELECT id, topic, motivation, smart_goal, status, created_at, updated_at FROM tasks WHERE id = $1
+            SELECT id, topic, motivation, smart_goal, deadline, paused_until, status, created_at, updated_at FROM tasks WHERE id = $1
             "#,
         )
         .bind(id)
@@ -76,7 +486,7 @@ impl Repository for PostgresRepo {
     ) -> Result<Vec<crate::domain::models::Task>> {
         let tasks: Vec<Task> = sqlx::query_as(
             r#"
-            SELECT id, topic, motivation, smart_goal, status, created_at, updated_at FROM tasks
+            SELECT id, topic, motivation, smart_goal, deadline, paused_until, status, created_at, updated_at FROM tasks
             WHERE status = ANY($1)
             ORDER BY updated_at DESC
             "#,
@@ -88,6 +498,62 @@ impl Repository for PostgresRepo {
         Ok(tasks.into_iter().map(Task::into).collect())
     }
 
+    async fn search(
+        &self,
+        query: &str,
+        filter: &SearchFilter,
+        limit: i64,
+    ) -> Result<Vec<crate::domain::models::SearchHit>> {
+        let hits: Vec<SearchHit> = sqlx::query_as(
+            r#"
+            WITH query AS (SELECT websearch_to_tsquery('simple', $1) AS q)
+            SELECT kind, id, title, snippet, status, rank, created_at FROM (
+                SELECT 'Task' AS kind, t.id, t.topic AS title,
+                    ts_headline(
+                        'simple',
+                        concat_ws(' ', t.topic, t.motivation,
+                            (SELECT string_agg(value, ' ') FROM jsonb_each_text(t.smart_goal))),
+                        query.q,
+                        $6
+                    ) AS snippet,
+                    t.status, ts_rank(t.search_vector, query.q) AS rank, t.created_at
+                FROM tasks t, query
+                WHERE t.search_vector @@ query.q
+                    AND (cardinality($2::text[]) = 0 OR t.status = ANY($2))
+                UNION ALL
+                SELECT 'Record', r.id,
+                    (SELECT string_agg(t.topic, ', ' ORDER BY t.id) FROM task_records tr
+                        JOIN tasks t ON t.id = tr.task_id WHERE tr.record_id = r.id),
+                    ts_headline('simple', r.content, query.q, $6),
+                    NULL, ts_rank(r.search_vector, query.q), r.created_at
+                FROM records r, query
+                WHERE r.search_vector @@ query.q
+                    AND (cardinality($2::text[]) = 0 OR EXISTS (
+                        SELECT 1 FROM task_records tr JOIN tasks t ON t.id = tr.task_id
+                        WHERE tr.record_id = r.id AND t.status = ANY($2)
+                    ))
+            ) hits
+            WHERE ($3::date IS NULL OR (created_at AT TIME ZONE 'UTC')::date >= $3)
+                AND ($4::date IS NULL OR (created_at AT TIME ZONE 'UTC')::date <= $4)
+            ORDER BY rank DESC, created_at DESC
+            LIMIT $5
+            "#,
+        )
+        .bind(query)
+        .bind(&filter.statuses)
+        .bind(filter.from)
+        .bind(filter.to)
+        .bind(limit)
+        .bind(format!(
+            r#"StartSel={}, StopSel={}, MaxFragments=2, MaxWords=24, MinWords=8, FragmentDelimiter=" … ""#,
+            HIGHLIGHT_START, HIGHLIGHT_END
+        ))
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(hits.into_iter().map(SearchHit::into).collect())
+    }
+
     async fn delete_task(&self, id: i64) -> Result<()> {
         sqlx::query(
             r#"
@@ -116,6 +582,19 @@ impl Repository for PostgresRepo {
         Ok(record.into())
     }
 
+    async fn get_records(&self) -> Result<Vec<crate::domain::models::Record>> {
+        let records: Vec<Record> = sqlx::query_as(
+            r#"
+            SELECT id, content, created_at FROM records
+            ORDER BY created_at, id
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(records.into_iter().map(Record::into).collect())
+    }
+
     async fn create_task_record(&self, task_id: i64, record_id: i64) -> Result<()> {
         sqlx::query(
             r#"
@@ -145,6 +624,327 @@ impl Repository for PostgresRepo {
 
         Ok(records.into_iter().map(Record::into).collect())
     }
+
+    async fn get_task_reviews(&self, task_id: i64) -> Result<Vec<crate::domain::models::Review>> {
+        let reviews: Vec<Review> = sqlx::query_as(
+            r#"
+            SELECT id, task_id, question, user_answer, ai_feedback, is_passed, created_at, updated_at
+            FROM reviews WHERE task_id = $1
+            ORDER BY id
+            "#,
+        )
+        .bind(task_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(reviews.into_iter().map(Review::into).collect())
+    }
+
+    async fn update_review_answer(
+        &self,
+        id: i64,
+        answer: &str,
+        feedback: &str,
+        passed: bool,
+    ) -> Result<()> {
+        sqlx::query(
+            r#"
+            UPDATE reviews SET user_answer = $1, ai_feedback = $2, is_passed = $3, updated_at = $4
+            WHERE id = $5
+            "#,
+        )
+        .bind(answer)
+        .bind(feedback)
+        .bind(passed)
+        .bind(chrono::Utc::now())
+        .bind(id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn submit_summary(
+        &self,
+        task_id: i64,
+        from: TaskStatus,
+        summary: &str,
+        questions: &[String],
+        reason: &str,
+    ) -> Result<Vec<crate::domain::models::Review>> {
+        let mut tx = self.pool.begin().await?;
+        transition(
+            &mut tx,
+            task_id,
+            &from,
+            &TaskStatus::Reviewing,
+            Some(reason),
+        )
+        .await?;
+        sqlx::query(
+            r#"
+            WITH record AS (
+                INSERT INTO records (content) VALUES ($2) RETURNING id
+            )
+            INSERT INTO task_records (task_id, record_id)
+            SELECT $1, id FROM record
+            "#,
+        )
+        .bind(task_id)
+        .bind(summary)
+        .execute(&mut *tx)
+        .await?;
+        let reviews = replace_reviews(&mut tx, task_id, questions).await?;
+        tx.commit().await?;
+        Ok(reviews)
+    }
+
+    async fn complete_task(
+        &self,
+        task_id: i64,
+        from: TaskStatus,
+        summary: &str,
+        tags: &[String],
+        reason: &str,
+    ) -> Result<crate::domain::models::TaskArchive> {
+        let mut tx = self.pool.begin().await?;
+        transition(
+            &mut tx,
+            task_id,
+            &from,
+            &TaskStatus::Completed,
+            Some(reason),
+        )
+        .await?;
+        let archive = archive(&mut tx, task_id, summary, tags).await?;
+        tx.commit().await?;
+        Ok(archive)
+    }
+
+    async fn get_task_archives(&self) -> Result<Vec<crate::domain::models::TaskArchive>> {
+        let archives: Vec<TaskArchive> = sqlx::query_as(
+            r#"
+            SELECT a.task_id, t.topic, a.summary, a.tags, a.completed_at FROM task_archives a
+            INNER JOIN tasks t ON t.id = a.task_id
+            ORDER BY a.completed_at DESC, a.task_id DESC
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(archives.into_iter().map(TaskArchive::into).collect())
+    }
+
+    async fn create_rejected_attempt(
+        &self,
+        topic: &str,
+        motivation: &str,
+        reason: &str,
+    ) -> Result<crate::domain::models::RejectedAttempt> {
+        let attempt: RejectedAttempt = sqlx::query_as(
+            r#"
+            INSERT INTO rejected_attempts (topic, motivation, reason, created_at)
+            VALUES ($1, $2, $3, $4)
+            RETURNING id, topic, motivation, reason, created_at;
+            "#,
+        )
+        .bind(topic)
+        .bind(motivation)
+        .bind(reason)
+        .bind(chrono::Utc::now())
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(attempt.into())
+    }
+
+    async fn get_rejected_attempts(&self) -> Result<Vec<crate::domain::models::RejectedAttempt>> {
+        let attempts: Vec<RejectedAttempt> = sqlx::query_as(
+            r#"
+            SELECT id, topic, motivation, reason, created_at FROM rejected_attempts
+            ORDER BY created_at DESC, id DESC
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(attempts.into_iter().map(RejectedAttempt::into).collect())
+    }
+
+    async fn restore(&self, backup: &Backup, existing_records: &HashMap<i64, i64>) -> Result<()> {
+        let mut tx = self.pool.begin().await?;
+
+        let mut record_ids = existing_records.clone();
+        for record in &backup.records {
+            if record_ids.contains_key(&record.id) {
+                continue;
+            }
+            let (id,): (i64,) = sqlx::query_as(
+                r#"
+                INSERT INTO records (content, created_at) VALUES ($1, $2) RETURNING id
+                "#,
+            )
+            .bind(&record.content)
+            .bind(record.created_at)
+            .fetch_one(&mut *tx)
+            .await?;
+            record_ids.insert(record.id, id);
+        }
+
+        for entry in &backup.tasks {
+            let task = &entry.task;
+            let (task_id,): (i64,) = sqlx::query_as(
+                r#"
+                INSERT INTO tasks (topic, motivation, smart_goal, deadline, paused_until, status, primer_content, created_at, updated_at)
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+                RETURNING id
+                "#,
+            )
+            .bind(&task.topic)
+            .bind(&task.motivation)
+            .bind(task.smart_goal.as_ref().map(Json))
+            .bind(task.deadline)
+            .bind(task.paused_until)
+            .bind(&task.status)
+            .bind(&entry.primer)
+            .bind(task.created_at)
+            .bind(task.updated_at)
+            .fetch_one(&mut *tx)
+            .await?;
+
+            for record_id in &entry.record_ids {
+                let Some(id) = record_ids.get(record_id) else {
+                    return Err(GewuError::invalid(format!(
+                        "Record #{} of task #{} is not in the backup",
+                        record_id, task.id
+                    )));
+                };
+                sqlx::query(
+                    r#"
+                    INSERT INTO task_records (task_id, record_id) VALUES ($1, $2)
+                    "#,
+                )
+                .bind(task_id)
+                .bind(id)
+                .execute(&mut *tx)
+                .await?;
+            }
+
+            for transition in &entry.transitions {
+                sqlx::query(
+                    r#"
+                    INSERT INTO task_transitions (task_id, from_status, to_status, reason, created_at)
+                    VALUES ($1, $2, $3, $4, $5)
+                    "#,
+                )
+                .bind(task_id)
+                .bind(&transition.from_status)
+                .bind(&transition.to_status)
+                .bind(&transition.reason)
+                .bind(transition.created_at)
+                .execute(&mut *tx)
+                .await?;
+            }
+
+            for turn in &entry.smart_goal_turns {
+                sqlx::query(
+                    r#"
+                    INSERT INTO smart_goal_turns (task_id, role, content, created_at)
+                    VALUES ($1, $2, $3, $4)
+                    "#,
+                )
+                .bind(task_id)
+                .bind(turn.role)
+                .bind(&turn.content)
+                .bind(turn.created_at)
+                .execute(&mut *tx)
+                .await?;
+            }
+
+            for milestone in &entry.milestones {
+                sqlx::query(
+                    r#"
+                    INSERT INTO milestones (task_id, title, criterion, due_date, completed_at, created_at)
+                    VALUES ($1, $2, $3, $4, $5, $6)
+                    "#,
+                )
+                .bind(task_id)
+                .bind(&milestone.title)
+                .bind(&milestone.criterion)
+                .bind(milestone.due_date)
+                .bind(milestone.completed_at)
+                .bind(milestone.created_at)
+                .execute(&mut *tx)
+                .await?;
+            }
+
+            for review in &entry.reviews {
+                sqlx::query(
+                    r#"
+                    INSERT INTO reviews (task_id, question, user_answer, ai_feedback, is_passed, created_at, updated_at)
+                    VALUES ($1, $2, $3, $4, $5, $6, $7)
+                    "#,
+                )
+                .bind(task_id)
+                .bind(&review.question)
+                .bind(&review.user_answer)
+                .bind(&review.ai_feedback)
+                .bind(review.is_passed)
+                .bind(review.created_at)
+                .bind(review.updated_at)
+                .execute(&mut *tx)
+                .await?;
+            }
+
+            for revision in &entry.revisions {
+                sqlx::query(
+                    r#"
+                    INSERT INTO task_revisions (task_id, field, previous, created_at)
+                    VALUES ($1, $2, $3, $4)
+                    "#,
+                )
+                .bind(task_id)
+                .bind(revision.field)
+                .bind(&revision.previous)
+                .bind(revision.created_at)
+                .execute(&mut *tx)
+                .await?;
+            }
+
+            if let Some(archive) = &entry.archive {
+                sqlx::query(
+                    r#"
+                    INSERT INTO task_archives (task_id, summary, tags, completed_at)
+                    VALUES ($1, $2, $3, $4)
+                    "#,
+                )
+                .bind(task_id)
+                .bind(&archive.summary)
+                .bind(Json(&archive.tags))
+                .bind(archive.completed_at)
+                .execute(&mut *tx)
+                .await?;
+            }
+        }
+
+        for attempt in &backup.rejected_attempts {
+            sqlx::query(
+                r#"
+                INSERT INTO rejected_attempts (topic, motivation, reason, created_at)
+                VALUES ($1, $2, $3, $4)
+                "#,
+            )
+            .bind(&attempt.topic)
+            .bind(&attempt.motivation)
+            .bind(&attempt.reason)
+            .bind(attempt.created_at)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await?;
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -152,6 +952,9 @@ mod tests {
     use sqlx_db_tester::TestPg;
 
     use super::*;
+    use crate::adapters::conformance::repository_conformance_tests;
+
+    repository_conformance_tests!(test_repo);
 
     #[tokio::test]
     async fn test_create_task() {
@@ -175,11 +978,72 @@ mod tests {
         let (repo, _test_pg) = test_repo().await;
         let task = repo.create_task("test", "test").await.unwrap();
 
-        repo.update_task_smart_goal(task.id, "smart goal")
-            .await
-            .unwrap();
+        let goal = SmartGoalDetail {
+            specific: "specific".to_string(),
+            measurable: "measurable".to_string(),
+            achievable: "achievable".to_string(),
+            relevant: "relevant".to_string(),
+            time_bound: "time bound".to_string(),
+        };
+        repo.update_task_smart_goal(task.id, &goal).await.unwrap();
         let task = repo.get_task(task.id).await.unwrap().unwrap();
-        assert_eq!(task.smart_goal, Some("smart goal".to_string()));
+        assert_eq!(task.smart_goal, Some(goal));
+    }
+
+    #[tokio::test]
+    async fn legacy_goals_should_become_smart_goals() {
+        let (repo, _test_pg) = test_repo().await;
+        let complete = SmartGoalDetail {
+            specific: "Build a CLI".to_string(),
+            measurable: "measurable".to_string(),
+            achievable: "achievable".to_string(),
+            relevant: "relevant".to_string(),
+            time_bound: "time bound".to_string(),
+        };
+        let complete_json = serde_json::to_string(&complete).unwrap();
+        let partial_json = r#"{"specific": "Learn Rust"}"#;
+
+        // Replay the migration on a legacy copy of the column in its own schema
+        let mut conn = repo.pool.acquire().await.unwrap();
+        sqlx::raw_sql(
+            "CREATE SCHEMA legacy; SET search_path TO legacy; \
+             CREATE TABLE tasks (id BIGINT PRIMARY KEY, smart_goal TEXT)",
+        )
+        .execute(&mut *conn)
+        .await
+        .unwrap();
+        for (id, legacy) in [
+            (1, Some("Learn Rust in 4 weeks")),
+            (2, Some(partial_json)),
+            (3, Some(complete_json.as_str())),
+            (4, None),
+        ] {
+            sqlx::query("INSERT INTO tasks (id, smart_goal) VALUES ($1, $2)")
+                .bind(id)
+                .bind(legacy)
+                .execute(&mut *conn)
+                .await
+                .unwrap();
+        }
+        sqlx::raw_sql(include_str!(
+            "../../migrations/20260315090000_typed_smart_goal.sql"
+        ))
+        .execute(&mut *conn)
+        .await
+        .unwrap();
+
+        let goals: Vec<Option<Json<SmartGoalDetail>>> =
+            sqlx::query_scalar("SELECT smart_goal FROM tasks ORDER BY id")
+                .fetch_all(&mut *conn)
+                .await
+                .unwrap();
+        let goals: Vec<Option<SmartGoalDetail>> =
+            goals.into_iter().map(|g| g.map(|g| g.0)).collect();
+        assert_eq!(goals[0].as_ref().unwrap().specific, "Learn Rust in 4 weeks");
+        assert!(goals[0].as_ref().unwrap().measurable.is_empty());
+        assert_eq!(goals[1].as_ref().unwrap().specific, partial_json);
+        assert_eq!(goals[2], Some(complete));
+        assert_eq!(goals[3], None);
     }
 
     async fn test_repo() -> (PostgresRepo, TestPg) {
@@ -0,0 +1,1074 @@
+use std::{collections::HashMap, path::Path, str::FromStr};
+
+use async_trait::async_trait;
+use chrono::NaiveDate;
+use sqlx::{
+    QueryBuilder, Sqlite, SqliteConnection, SqlitePool,
+    sqlite::{SqliteConnectOptions, SqlitePoolOptions},
+    types::Json,
+};
+
+use crate::{
+    adapters::models::{
+        Milestone, Record, RejectedAttempt, Review, SearchHit, SmartGoalTurn, Task, TaskArchive,
+        TaskRevision, TaskTransition,
+    },
+    domain::{
+        backup::Backup,
+        models::{
+            HIGHLIGHT_END, HIGHLIGHT_START, SearchFilter, SmartGoalDetail, TaskField, TurnRole,
+        },
+        state::TaskStatus,
+    },
+    ports::{
+        error::{GewuError, Result},
+        llm::MilestonePlan,
+        repository::Repository,
+    },
+};
+
+/// Repository backed by a local SQLite file, so gewu can run without a database server.
+pub struct SqliteRepo {
+    pool: SqlitePool,
+}
+
+impl SqliteRepo {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    /// Open (or create) the database at the given `sqlite:` url and run the migrations.
+    pub async fn connect(url: &str) -> anyhow::Result<Self> {
+        let options = SqliteConnectOptions::from_str(url)?.create_if_missing(true);
+        if let Some(parent) = options.get_filename().parent()
+            && parent != Path::new("")
+        {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let pool = SqlitePoolOptions::new().connect_with(options).await?;
+        sqlx::migrate!("./migrations/sqlite").run(&pool).await?;
+        Ok(Self::new(pool))
+    }
+
+    /// A fresh in-memory database with the migrations applied.
+    #[cfg(test)]
+    pub async fn in_memory() -> anyhow::Result<Self> {
+        // Every connection to `sqlite::memory:` opens a new database, so keep a single one
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect_with(SqliteConnectOptions::from_str("sqlite::memory:")?)
+            .await?;
+        sqlx::migrate!("./migrations/sqlite").run(&pool).await?;
+        Ok(Self::new(pool))
+    }
+}
+
+/// Quote every word of the query so FTS5 operators and punctuation are matched literally.
+/// The words are implicitly AND-ed.
+fn fts_query(query: &str) -> String {
+    query
+        .split_whitespace()
+        .map(|word| format!("\"{}\"", word.replace('"', "\"\"")))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Move the task from `from` to `to` and record the transition, within the caller's transaction.
+async fn transition(
+    conn: &mut SqliteConnection,
+    id: i64,
+    from: &TaskStatus,
+    to: &TaskStatus,
+    reason: Option<&str>,
+) -> Result<()> {
+    let now = chrono::Utc::now();
+    let result = sqlx::query(
+        r#"
+        UPDATE tasks SET status = ?, updated_at = ? WHERE id = ? AND status = ?
+        "#,
+    )
+    .bind(to)
+    .bind(now)
+    .bind(id)
+    .bind(from)
+    .execute(&mut *conn)
+    .await?;
+    if result.rows_affected() == 0 {
+        return Err(GewuError::status_conflict(format!(
+            "Task #{} is not in {} status",
+            id, from
+        )));
+    }
+
+    sqlx::query(
+        r#"
+        INSERT INTO task_transitions (task_id, from_status, to_status, reason, created_at)
+        VALUES (?, ?, ?, ?, ?)
+        "#,
+    )
+    .bind(id)
+    .bind(from)
+    .bind(to)
+    .bind(reason)
+    .bind(now)
+    .execute(&mut *conn)
+    .await?;
+    Ok(())
+}
+
+/// Replace the review questions of a task with unanswered ones, within the caller's transaction.
+async fn replace_reviews(
+    conn: &mut SqliteConnection,
+    task_id: i64,
+    questions: &[String],
+) -> Result<Vec<crate::domain::models::Review>> {
+    let now = chrono::Utc::now();
+    sqlx::query(
+        r#"
+        DELETE FROM reviews WHERE task_id = ?
+        "#,
+    )
+    .bind(task_id)
+    .execute(&mut *conn)
+    .await?;
+
+    let mut reviews = Vec::with_capacity(questions.len());
+    for question in questions {
+        let review: Review = sqlx::query_as(
+            r#"
+            INSERT INTO reviews (task_id, question, created_at, updated_at)
+            VALUES (?, ?, ?, ?)
+            RETURNING id, task_id, question, user_answer, ai_feedback, is_passed, created_at, updated_at;
+            "#,
+        )
+        .bind(task_id)
+        .bind(question)
+        .bind(now)
+        .bind(now)
+        .fetch_one(&mut *conn)
+        .await?;
+        reviews.push(review.into());
+    }
+    Ok(reviews)
+}
+
+/// Insert or replace the archive of a task, within the caller's transaction.
+async fn archive(
+    conn: &mut SqliteConnection,
+    task_id: i64,
+    summary: &str,
+    tags: &[String],
+) -> Result<crate::domain::models::TaskArchive> {
+    sqlx::query(
+        r#"
+        INSERT INTO task_archives (task_id, summary, tags, completed_at)
+        VALUES (?, ?, ?, ?)
+        ON CONFLICT (task_id) DO UPDATE
+        SET summary = excluded.summary, tags = excluded.tags, completed_at = excluded.completed_at
+        "#,
+    )
+    .bind(task_id)
+    .bind(summary)
+    .bind(Json(tags))
+    .bind(chrono::Utc::now())
+    .execute(&mut *conn)
+    .await?;
+
+    let archive: TaskArchive = sqlx::query_as(
+        r#"
+        SELECT a.task_id, t.topic, a.summary, a.tags, a.completed_at FROM task_archives a
+        INNER JOIN tasks t ON t.id = a.task_id
+        WHERE a.task_id = ?
+        "#,
+    )
+    .bind(task_id)
+    .fetch_one(&mut *conn)
+    .await?;
+    Ok(archive.into())
+}
+
+#[async_trait]
+impl Repository for SqliteRepo {
+    async fn create_task(
+        &self,
+        topic: &str,
+        motivation: &str,
+    ) -> Result<crate::domain::models::Task> {
+        let now = chrono::Utc::now();
+        let task: Task = sqlx::query_as(
+            r#"
+            INSERT INTO tasks (topic, motivation, status, created_at, updated_at)
+            VALUES (?, ?, ?, ?, ?)
+            RETURNING id, topic, motivation, smart_goal, deadline, paused_until, status, created_at, updated_at;
+            "#,
+        )
+        .bind(topic)
+        .bind(motivation)
+        .bind(TaskStatus::Planning)
+        .bind(now)
+        .bind(now)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(task.into())
+    }
+
+    async fn update_task_topic(&self, id: i64, topic: &str) -> Result<()> {
+        sqlx::query(
+            r#"
+            UPDATE tasks SET topic = ?, updated_at = ? WHERE id = ?
+            "#,
+        )
+        .bind(topic)
+        .bind(chrono::Utc::now())
+        .bind(id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn update_task_motivation(&self, id: i64, motivation: &str) -> Result<()> {
+        sqlx::query(
+            r#"
+            UPDATE tasks SET motivation = ?, updated_at = ? WHERE id = ?
+            "#,
+        )
+        .bind(motivation)
+        .bind(chrono::Utc::now())
+        .bind(id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn update_task_smart_goal(&self, id: i64, smart_goal: &SmartGoalDetail) -> Result<()> {
+        sqlx::query(
+            r#"
+            UPDATE tasks SET smart_goal = ?, updated_at = ? WHERE id = ?
+            "#,
+        )
+        .bind(Json(smart_goal))
+        .bind(chrono::Utc::now())
+        .bind(id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn approve_smart_goal(
+        &self,
+        id: i64,
+        from: TaskStatus,
+        smart_goal: &SmartGoalDetail,
+        milestones: &[MilestonePlan],
+        deadline: Option<NaiveDate>,
+        reason: &str,
+    ) -> Result<()> {
+        let now = chrono::Utc::now();
+        let mut tx = self.pool.begin().await?;
+        transition(&mut tx, id, &from, &TaskStatus::Active, Some(reason)).await?;
+
+        sqlx::query(
+            r#"
+            UPDATE tasks SET smart_goal = ?, deadline = COALESCE(?, deadline), updated_at = ?
+            WHERE id = ?
+            "#,
+        )
+        .bind(Json(smart_goal))
+        .bind(deadline)
+        .bind(now)
+        .bind(id)
+        .execute(&mut *tx)
+        .await?;
+
+        for milestone in milestones {
+            sqlx::query(
+                r#"
+                INSERT INTO milestones (task_id, title, criterion, due_date, created_at)
+                VALUES (?, ?, ?, ?, ?)
+                "#,
+            )
+            .bind(id)
+            .bind(&milestone.title)
+            .bind(&milestone.criterion)
+            .bind(milestone.due_date)
+            .bind(now)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn update_task_deadline(&self, id: i64, deadline: Option<NaiveDate>) -> Result<()> {
+        sqlx::query(
+            r#"
+            UPDATE tasks SET deadline = ?, updated_at = ? WHERE id = ?
+            "#,
+        )
+        .bind(deadline)
+        .bind(chrono::Utc::now())
+        .bind(id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn update_task_pause(
+        &self,
+        id: i64,
+        from: TaskStatus,
+        to: TaskStatus,
+        paused_until: Option<NaiveDate>,
+        reason: &str,
+    ) -> Result<()> {
+        let mut tx = self.pool.begin().await?;
+        transition(&mut tx, id, &from, &to, Some(reason)).await?;
+
+        sqlx::query(
+            r#"
+            UPDATE tasks SET paused_until = ? WHERE id = ?
+            "#,
+        )
+        .bind(paused_until)
+        .bind(id)
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn create_task_revision(
+        &self,
+        task_id: i64,
+        field: TaskField,
+        previous: Option<&str>,
+    ) -> Result<crate::domain::models::TaskRevision> {
+        let revision: TaskRevision = sqlx::query_as(
+            r#"
+            INSERT INTO task_revisions (task_id, field, previous, created_at)
+            VALUES (?, ?, ?, ?)
+            RETURNING id, task_id, field, previous, created_at;
+            "#,
+        )
+        .bind(task_id)
+        .bind(field)
+        .bind(previous)
+        .bind(chrono::Utc::now())
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(revision.into())
+    }
+
+    async fn get_task_revisions(
+        &self,
+        task_id: i64,
+    ) -> Result<Vec<crate::domain::models::TaskRevision>> {
+        let revisions: Vec<TaskRevision> = sqlx::query_as(
+            r#"
+            SELECT id, task_id, field, previous, created_at FROM task_revisions
+            WHERE task_id = ?
+            ORDER BY id
+            "#,
+        )
+        .bind(task_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(revisions.into_iter().map(TaskRevision::into).collect())
+    }
+
+    async fn create_smart_goal_turn(
+        &self,
+        task_id: i64,
+        role: TurnRole,
+        content: &str,
+    ) -> Result<crate::domain::models::SmartGoalTurn> {
+        let turn: SmartGoalTurn = sqlx::query_as(
+            r#"
+            INSERT INTO smart_goal_turns (task_id, role, content, created_at)
+            VALUES (?, ?, ?, ?)
+            RETURNING id, task_id, role, content, created_at;
+            "#,
+        )
+        .bind(task_id)
+        .bind(role)
+        .bind(content)
+        .bind(chrono::Utc::now())
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(turn.into())
+    }
+
+    async fn get_smart_goal_turns(
+        &self,
+        task_id: i64,
+    ) -> Result<Vec<crate::domain::models::SmartGoalTurn>> {
+        let turns: Vec<SmartGoalTurn> = sqlx::query_as(
+            r#"
+            SELECT id, task_id, role, content, created_at FROM smart_goal_turns
+            WHERE task_id = ?
+            ORDER BY id
+            "#,
+        )
+        .bind(task_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(turns.into_iter().map(SmartGoalTurn::into).collect())
+    }
+
+    async fn get_milestones(&self, task_id: i64) -> Result<Vec<crate::domain::models::Milestone>> {
+        let milestones: Vec<Milestone> = sqlx::query_as(
+            r#"
+            SELECT id, task_id, title, criterion, due_date, completed_at, created_at FROM milestones
+            WHERE task_id = ?
+            ORDER BY due_date, id
+            "#,
+        )
+        .bind(task_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(milestones.into_iter().map(Milestone::into).collect())
+    }
+
+    async fn update_milestone_completed(&self, id: i64, completed: bool) -> Result<()> {
+        let completed_at = completed.then(chrono::Utc::now);
+        sqlx::query(
+            r#"
+            UPDATE milestones SET completed_at = ? WHERE id = ?
+            "#,
+        )
+        .bind(completed_at)
+        .bind(id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn get_task_primer(&self, id: i64) -> Result<Option<String>> {
+        let primer: Option<Option<String>> = sqlx::query_scalar(
+            r#"
+            SELECT primer_content FROM tasks WHERE id = ?
+            "#,
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(primer.flatten())
+    }
+
+    async fn update_task_primer(&self, id: i64, primer: &str) -> Result<()> {
+        sqlx::query(
+            r#"
+            UPDATE tasks SET primer_content = ?, updated_at = ? WHERE id = ?
+            "#,
+        )
+        .bind(primer)
+        .bind(chrono::Utc::now())
+        .bind(id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn update_task_status(
+        &self,
+        id: i64,
+        from: TaskStatus,
+        to: TaskStatus,
+        reason: Option<&str>,
+    ) -> Result<()> {
+        let mut tx = self.pool.begin().await?;
+        transition(&mut tx, id, &from, &to, reason).await?;
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn get_task_transitions(
+        &self,
+        task_id: i64,
+    ) -> Result<Vec<crate::domain::models::TaskTransition>> {
+        let transitions: Vec<TaskTransition> = sqlx::query_as(
+            r#"
+            SELECT id, task_id, from_status, to_status, reason, created_at FROM task_transitions
+            WHERE task_id = ?
+            ORDER BY id
+            "#,
+        )
+        .bind(task_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(transitions.into_iter().map(TaskTransition::into).collect())
+    }
+
+    async fn get_task(&self, id: i64) -> Result<Option<crate::domain::models::Task>> {
+        let task: Option<Task> = sqlx::query_as(
+            r#"
+            SELECT id, topic, motivation, smart_goal, deadline, paused_until, status, created_at, updated_at FROM tasks WHERE id = ?
+            "#,
+        )
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(task.map(Task::into))
+    }
+
+    async fn get_tasks_by_status(
+        &self,
+        status: &[TaskStatus],
+    ) -> Result<Vec<crate::domain::models::Task>> {
+        // SQLite has no array binding, so expand the statuses into an `IN (...)` list
+        let mut builder = QueryBuilder::<Sqlite>::new(
+            r#"
+            SELECT id, topic, motivation, smart_goal, deadline, paused_until, status, created_at, updated_at FROM tasks
+            WHERE status IN ("#,
+        );
+        let mut separated = builder.separated(", ");
+        for s in status {
+            separated.push_bind(s);
+        }
+        separated.push_unseparated(") ORDER BY updated_at DESC, id DESC");
+
+        let tasks: Vec<Task> = builder.build_query_as().fetch_all(&self.pool).await?;
+
+        Ok(tasks.into_iter().map(Task::into).collect())
+    }
+
+    async fn search(
+        &self,
+        query: &str,
+        filter: &SearchFilter,
+        limit: i64,
+    ) -> Result<Vec<crate::domain::models::SearchHit>> {
+        let query = fts_query(query);
+        if query.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let mut builder = QueryBuilder::<Sqlite>::new(
+            "SELECT kind, id, title, snippet, status, rank, created_at FROM (SELECT 'Task' AS kind, t.id, t.topic AS title, ",
+        );
+        builder
+            .push("snippet(tasks_fts, -1, ")
+            .push_bind(HIGHLIGHT_START)
+            .push(", ")
+            .push_bind(HIGHLIGHT_END)
+            .push(
+                ", ' … ', 24) AS snippet, t.status, -bm25(tasks_fts, 4.0, 2.0, 1.0) AS rank, t.created_at \
+                FROM tasks_fts JOIN tasks t ON t.id = tasks_fts.rowid WHERE tasks_fts MATCH ",
+            )
+            .push_bind(&query);
+        if !filter.statuses.is_empty() {
+            builder.push(" AND t.status IN (");
+            let mut separated = builder.separated(", ");
+            for status in &filter.statuses {
+                separated.push_bind(status);
+            }
+            separated.push_unseparated(")");
+        }
+
+        builder
+            .push(
+                " UNION ALL SELECT 'Record', r.id, \
+                (SELECT group_concat(topic, ', ') FROM (SELECT t.topic FROM task_records tr \
+                    JOIN tasks t ON t.id = tr.task_id WHERE tr.record_id = r.id ORDER BY t.id)), \
+                snippet(records_fts, 0, ",
+            )
+            .push_bind(HIGHLIGHT_START)
+            .push(", ")
+            .push_bind(HIGHLIGHT_END)
+            .push(
+                ", ' … ', 24), NULL, -bm25(records_fts), r.created_at \
+                FROM records_fts JOIN records r ON r.id = records_fts.rowid WHERE records_fts MATCH ",
+            )
+            .push_bind(&query);
+        if !filter.statuses.is_empty() {
+            builder.push(
+                " AND EXISTS (SELECT 1 FROM task_records tr JOIN tasks t ON t.id = tr.task_id \
+                WHERE tr.record_id = r.id AND t.status IN (",
+            );
+            let mut separated = builder.separated(", ");
+            for status in &filter.statuses {
+                separated.push_bind(status);
+            }
+            separated.push_unseparated("))");
+        }
+
+        builder.push(") hits WHERE 1 = 1");
+        if let Some(from) = filter.from {
+            builder.push(" AND date(created_at) >= ").push_bind(from);
+        }
+        if let Some(to) = filter.to {
+            builder.push(" AND date(created_at) <= ").push_bind(to);
+        }
+        builder
+            .push(" ORDER BY rank DESC, created_at DESC LIMIT ")
+            .push_bind(limit);
+
+        let hits: Vec<SearchHit> = builder.build_query_as().fetch_all(&self.pool).await?;
+
+        Ok(hits.into_iter().map(SearchHit::into).collect())
+    }
+
+    async fn delete_task(&self, id: i64) -> Result<()> {
+        sqlx::query(
+            r#"
+            DELETE FROM tasks WHERE id = ?
+            "#,
+        )
+        .bind(id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn create_record(&self, content: &str) -> Result<crate::domain::models::Record> {
+        let record: Record = sqlx::query_as(
+            r#"
+            INSERT INTO records (content, created_at)
+            VALUES (?, ?)
+            RETURNING id, content, created_at;
+            "#,
+        )
+        .bind(content)
+        .bind(chrono::Utc::now())
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(record.into())
+    }
+
+    async fn get_records(&self) -> Result<Vec<crate::domain::models::Record>> {
+        let records: Vec<Record> = sqlx::query_as(
+            r#"
+            SELECT id, content, created_at FROM records
+            ORDER BY created_at, id
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(records.into_iter().map(Record::into).collect())
+    }
+
+    async fn create_task_record(&self, task_id: i64, record_id: i64) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO task_records (task_id, record_id)
+            VALUES (?, ?)
+            "#,
+        )
+        .bind(task_id)
+        .bind(record_id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn get_task_records(&self, task_id: i64) -> Result<Vec<crate::domain::models::Record>> {
+        let records: Vec<Record> = sqlx::query_as(
+            r#"
+            SELECT r.id, r.content, r.created_at FROM records r
+            INNER JOIN task_records tr ON r.id = tr.record_id
+            WHERE tr.task_id = ?
+            "#,
+        )
+        .bind(task_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(records.into_iter().map(Record::into).collect())
+    }
+
+    async fn get_task_reviews(&self, task_id: i64) -> Result<Vec<crate::domain::models::Review>> {
+        let reviews: Vec<Review> = sqlx::query_as(
+            r#"
+            SELECT id, task_id, question, user_answer, ai_feedback, is_passed, created_at, updated_at
+            FROM reviews WHERE task_id = ?
+            ORDER BY id
+            "#,
+        )
+        .bind(task_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(reviews.into_iter().map(Review::into).collect())
+    }
+
+    async fn update_review_answer(
+        &self,
+        id: i64,
+        answer: &str,
+        feedback: &str,
+        passed: bool,
+    ) -> Result<()> {
+        sqlx::query(
+            r#"
+            UPDATE reviews SET user_answer = ?, ai_feedback = ?, is_passed = ?, updated_at = ?
+            WHERE id = ?
+            "#,
+        )
+        .bind(answer)
+        .bind(feedback)
+        .bind(passed)
+        .bind(chrono::Utc::now())
+        .bind(id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn submit_summary(
+        &self,
+        task_id: i64,
+        from: TaskStatus,
+        summary: &str,
+        questions: &[String],
+        reason: &str,
+    ) -> Result<Vec<crate::domain::models::Review>> {
+        let mut tx = self.pool.begin().await?;
+        transition(
+            &mut tx,
+            task_id,
+            &from,
+            &TaskStatus::Reviewing,
+            Some(reason),
+        )
+        .await?;
+        let record_id: i64 = sqlx::query_scalar(
+            r#"
+            INSERT INTO records (content, created_at)
+            VALUES (?, ?)
+            RETURNING id;
+            "#,
+        )
+        .bind(summary)
+        .bind(chrono::Utc::now())
+        .fetch_one(&mut *tx)
+        .await?;
+        sqlx::query(
+            r#"
+            INSERT INTO task_records (task_id, record_id)
+            VALUES (?, ?)
+            "#,
+        )
+        .bind(task_id)
+        .bind(record_id)
+        .execute(&mut *tx)
+        .await?;
+        let reviews = replace_reviews(&mut tx, task_id, questions).await?;
+        tx.commit().await?;
+        Ok(reviews)
+    }
+
+    async fn complete_task(
+        &self,
+        task_id: i64,
+        from: TaskStatus,
+        summary: &str,
+        tags: &[String],
+        reason: &str,
+    ) -> Result<crate::domain::models::TaskArchive> {
+        let mut tx = self.pool.begin().await?;
+        transition(
+            &mut tx,
+            task_id,
+            &from,
+            &TaskStatus::Completed,
+            Some(reason),
+        )
+        .await?;
+        let archive = archive(&mut tx, task_id, summary, tags).await?;
+        tx.commit().await?;
+        Ok(archive)
+    }
+
+    async fn get_task_archives(&self) -> Result<Vec<crate::domain::models::TaskArchive>> {
+        let archives: Vec<TaskArchive> = sqlx::query_as(
+            r#"
+            SELECT a.task_id, t.topic, a.summary, a.tags, a.completed_at FROM task_archives a
+            INNER JOIN tasks t ON t.id = a.task_id
+            ORDER BY a.completed_at DESC, a.task_id DESC
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(archives.into_iter().map(TaskArchive::into).collect())
+    }
+
+    async fn create_rejected_attempt(
+        &self,
+        topic: &str,
+        motivation: &str,
+        reason: &str,
+    ) -> Result<crate::domain::models::RejectedAttempt> {
+        let attempt: RejectedAttempt = sqlx::query_as(
+            r#"
+            INSERT INTO rejected_attempts (topic, motivation, reason, created_at)
+            VALUES (?, ?, ?, ?)
+            RETURNING id, topic, motivation, reason, created_at;
+            "#,
+        )
+        .bind(topic)
+        .bind(motivation)
+        .bind(reason)
+        .bind(chrono::Utc::now())
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(attempt.into())
+    }
+
+    async fn get_rejected_attempts(&self) -> Result<Vec<crate::domain::models::RejectedAttempt>> {
+        let attempts: Vec<RejectedAttempt> = sqlx::query_as(
+            r#"
+            SELECT id, topic, motivation, reason, created_at FROM rejected_attempts
+            ORDER BY created_at DESC, id DESC
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(attempts.into_iter().map(RejectedAttempt::into).collect())
+    }
+
+    async fn restore(&self, backup: &Backup, existing_records: &HashMap<i64, i64>) -> Result<()> {
+        let mut tx = self.pool.begin().await?;
+
+        let mut record_ids = existing_records.clone();
+        for record in &backup.records {
+            if record_ids.contains_key(&record.id) {
+                continue;
+            }
+            let (id,): (i64,) = sqlx::query_as(
+                r#"
+                INSERT INTO records (content, created_at) VALUES (?, ?) RETURNING id
+                "#,
+            )
+            .bind(&record.content)
+            .bind(record.created_at)
+            .fetch_one(&mut *tx)
+            .await?;
+            record_ids.insert(record.id, id);
+        }
+
+        for entry in &backup.tasks {
+            let task = &entry.task;
+            let (task_id,): (i64,) = sqlx::query_as(
+                r#"
+                INSERT INTO tasks (topic, motivation, smart_goal, deadline, paused_until, status, primer_content, created_at, updated_at)
+                VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+                RETURNING id
+                "#,
+            )
+            .bind(&task.topic)
+            .bind(&task.motivation)
+            .bind(task.smart_goal.as_ref().map(Json))
+            .bind(task.deadline)
+            .bind(task.paused_until)
+            .bind(&task.status)
+            .bind(&entry.primer)
+            .bind(task.created_at)
+            .bind(task.updated_at)
+            .fetch_one(&mut *tx)
+            .await?;
+
+            for record_id in &entry.record_ids {
+                let Some(id) = record_ids.get(record_id) else {
+                    return Err(GewuError::invalid(format!(
+                        "Record #{} of task #{} is not in the backup",
+                        record_id, task.id
+                    )));
+                };
+                sqlx::query(
+                    r#"
+                    INSERT INTO task_records (task_id, record_id) VALUES (?, ?)
+                    "#,
+                )
+                .bind(task_id)
+                .bind(id)
+                .execute(&mut *tx)
+                .await?;
+            }
+
+            for transition in &entry.transitions {
+                sqlx::query(
+                    r#"
+                    INSERT INTO task_transitions (task_id, from_status, to_status, reason, created_at)
+                    VALUES (?, ?, ?, ?, ?)
+                    "#,
+                )
+                .bind(task_id)
+                .bind(&transition.from_status)
+                .bind(&transition.to_status)
+                .bind(&transition.reason)
+                .bind(transition.created_at)
+                .execute(&mut *tx)
+                .await?;
+            }
+
+            for turn in &entry.smart_goal_turns {
+                sqlx::query(
+                    r#"
+                    INSERT INTO smart_goal_turns (task_id, role, content, created_at)
+                    VALUES (?, ?, ?, ?)
+                    "#,
+                )
+                .bind(task_id)
+                .bind(turn.role)
+                .bind(&turn.content)
+                .bind(turn.created_at)
+                .execute(&mut *tx)
+                .await?;
+            }
+
+            for milestone in &entry.milestones {
+                sqlx::query(
+                    r#"
+                    INSERT INTO milestones (task_id, title, criterion, due_date, completed_at, created_at)
+                    VALUES (?, ?, ?, ?, ?, ?)
+                    "#,
+                )
+                .bind(task_id)
+                .bind(&milestone.title)
+                .bind(&milestone.criterion)
+                .bind(milestone.due_date)
+                .bind(milestone.completed_at)
+                .bind(milestone.created_at)
+                .execute(&mut *tx)
+                .await?;
+            }
+
+            for review in &entry.reviews {
+                sqlx::query(
+                    r#"
+                    INSERT INTO reviews (task_id, question, user_answer, ai_feedback, is_passed, created_at, updated_at)
+                    VALUES (?, ?, ?, ?, ?, ?, ?)
+                    "#,
+                )
+                .bind(task_id)
+                .bind(&review.question)
+                .bind(&review.user_answer)
+                .bind(&review.ai_feedback)
+                .bind(review.is_passed)
+                .bind(review.created_at)
+                .bind(review.updated_at)
+                .execute(&mut *tx)
+                .await?;
+            }
+
+            for revision in &entry.revisions {
+                sqlx::query(
+                    r#"
+                    INSERT INTO task_revisions (task_id, field, previous, created_at)
+                    VALUES (?, ?, ?, ?)
+                    "#,
+                )
+                .bind(task_id)
+                .bind(revision.field)
+                .bind(&revision.previous)
+                .bind(revision.created_at)
+                .execute(&mut *tx)
+                .await?;
+            }
+
+            if let Some(archive) = &entry.archive {
+                sqlx::query(
+                    r#"
+                    INSERT INTO task_archives (task_id, summary, tags, completed_at)
+                    VALUES (?, ?, ?, ?)
+                    "#,
+                )
+                .bind(task_id)
+                .bind(&archive.summary)
+                .bind(Json(&archive.tags))
+                .bind(archive.completed_at)
+                .execute(&mut *tx)
+                .await?;
+            }
+        }
+
+        for attempt in &backup.rejected_attempts {
+            sqlx::query(
+                r#"
+                INSERT INTO rejected_attempts (topic, motivation, reason, created_at)
+                VALUES (?, ?, ?, ?)
+                "#,
+            )
+            .bind(&attempt.topic)
+            .bind(&attempt.motivation)
+            .bind(&attempt.reason)
+            .bind(attempt.created_at)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::conformance::repository_conformance_tests;
+
+    repository_conformance_tests!(test_repo);
+
+    async fn test_repo() -> (SqliteRepo, ()) {
+        (SqliteRepo::in_memory().await.unwrap(), ())
+    }
+
+    #[tokio::test]
+    async fn legacy_free_text_goal_should_become_specific() {
+        let repo = SqliteRepo::in_memory().await.unwrap();
+        let task = repo.create_task("topic", "motivation").await.unwrap();
+        let partial = repo.create_task("topic", "motivation").await.unwrap();
+        for (id, legacy) in [
+            (task.id, "Learn Rust in 4 weeks"),
+            (partial.id, r#"{"specific": "Learn Rust"}"#),
+        ] {
+            sqlx::query("UPDATE tasks SET smart_goal = ? WHERE id = ?")
+                .bind(legacy)
+                .bind(id)
+                .execute(&repo.pool)
+                .await
+                .unwrap();
+        }
+
+        sqlx::raw_sql(include_str!(
+            "../../migrations/sqlite/20260315090000_typed_smart_goal.sql"
+        ))
+        .execute(&repo.pool)
+        .await
+        .unwrap();
+
+        let goal = repo.get_task(task.id).await.unwrap().unwrap().smart_goal;
+        let goal = goal.unwrap();
+        assert_eq!(goal.specific, "Learn Rust in 4 weeks");
+        assert!(goal.measurable.is_empty());
+        // an incomplete object is kept whole as the specific part, so it still decodes
+        let goal = repo.get_task(partial.id).await.unwrap().unwrap().smart_goal;
+        assert_eq!(goal.unwrap().specific, r#"{"specific": "Learn Rust"}"#);
+    }
+}
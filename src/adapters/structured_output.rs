@@ -0,0 +1,191 @@
+use serde::de::DeserializeOwned;
+
+use crate::ports::{
+    error::GewuError,
+    llm::{
+        AnswerVerdict, GatekeeperVerdict, GenerateQuestionsResult, MatchTasksResult,
+        SmartGoalVerdict, TaskSummary,
+    },
+};
+
+/// How often the model is asked for a JSON reply before giving up.
+pub const MAX_ATTEMPTS: usize = 3;
+
+/// Why a model reply could not be turned into the expected structure.
+#[derive(Debug, thiserror::Error)]
+pub enum StructuredOutputError {
+    #[error("The model returned an empty response")]
+    Empty,
+    #[error("No JSON object found in the response")]
+    NoJson,
+    #[error("The JSON does not match the expected shape: {0}")]
+    Malformed(#[from] serde_json::Error),
+    #[error("The JSON is incomplete: {0}")]
+    Invalid(String),
+    #[error("No valid JSON after {attempts} attempts, last error: {last}")]
+    RetriesExhausted {
+        attempts: usize,
+        last: Box<StructuredOutputError>,
+    },
+}
+
+impl From<StructuredOutputError> for GewuError {
+    fn from(error: StructuredOutputError) -> Self {
+        Self::MalformedLlmResponse(error.to_string())
+    }
+}
+
+/// A structure the model is asked to answer with.
+pub trait StructuredOutput: DeserializeOwned {
+    /// Checks the parts of the shape serde can't express.
+    fn validate(&self) -> Result<(), String> {
+        Ok(())
+    }
+}
+
+impl StructuredOutput for GatekeeperVerdict {}
+
+impl StructuredOutput for SmartGoalVerdict {
+    fn validate(&self) -> Result<(), String> {
+        if self.passed && self.refined_goal.is_none() {
+            return Err("`refined_goal` is required when `passed` is true".to_string());
+        }
+        if self.milestones.iter().any(|m| m.title.trim().is_empty()) {
+            return Err("every milestone needs a `title`".to_string());
+        }
+        Ok(())
+    }
+}
+
+impl StructuredOutput for MatchTasksResult {}
+
+impl StructuredOutput for GenerateQuestionsResult {
+    fn validate(&self) -> Result<(), String> {
+        if self.questions.is_empty() {
+            return Err("`questions` must not be empty".to_string());
+        }
+        Ok(())
+    }
+}
+
+impl StructuredOutput for AnswerVerdict {}
+
+impl StructuredOutput for TaskSummary {
+    fn validate(&self) -> Result<(), String> {
+        if self.summary.trim().is_empty() {
+            return Err("`summary` must not be empty".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// Parse a model reply that should contain a single JSON object,
+/// tolerating markdown fences and text around it.
+pub fn parse<T: StructuredOutput>(content: &str) -> Result<T, StructuredOutputError> {
+    if content.trim().is_empty() {
+        return Err(StructuredOutputError::Empty);
+    }
+
+    let json = extract_json(content).ok_or(StructuredOutputError::NoJson)?;
+    let value: T = serde_json::from_str(json)?;
+    value.validate().map_err(StructuredOutputError::Invalid)?;
+    Ok(value)
+}
+
+/// Locate the first balanced `{ ... }` object in the text.
+fn extract_json(content: &str) -> Option<&str> {
+    let start = content.find('{')?;
+    let mut depth = 0usize;
+    let mut in_string = false;
+    let mut escaped = false;
+
+    for (i, c) in content[start..].char_indices() {
+        if in_string {
+            match c {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                '"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+
+        match c {
+            '"' => in_string = true,
+            '{' => depth += 1,
+            '}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(&content[start..start + i + 1]);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_should_accept_plain_json() {
+        let verdict: AnswerVerdict = parse(r#"{"passed": true, "feedback": "ok"}"#).unwrap();
+        assert!(verdict.passed);
+        assert_eq!(verdict.feedback, "ok");
+    }
+
+    #[test]
+    fn parse_should_strip_code_fence_and_preamble() {
+        let content = "Here is my verdict:\n```json\n{\"task_ids\": [1, 3]}\n```\nHope it helps!";
+        let result: MatchTasksResult = parse(content).unwrap();
+        assert_eq!(result.task_ids, vec![1, 3]);
+    }
+
+    #[test]
+    fn parse_should_ignore_braces_inside_strings() {
+        let content = r#"{"passed": false, "feedback": "use `}` and \"{\" carefully"} trailing"#;
+        let verdict: AnswerVerdict = parse(content).unwrap();
+        assert_eq!(verdict.feedback, "use `}` and \"{\" carefully");
+    }
+
+    #[test]
+    fn parse_should_reject_missing_or_wrong_shape() {
+        assert!(matches!(
+            parse::<AnswerVerdict>("  "),
+            Err(StructuredOutputError::Empty)
+        ));
+        assert!(matches!(
+            parse::<AnswerVerdict>("I can't answer that"),
+            Err(StructuredOutputError::NoJson)
+        ));
+        assert!(matches!(
+            parse::<AnswerVerdict>(r#"{"passed": "yes"}"#),
+            Err(StructuredOutputError::Malformed(_))
+        ));
+        assert!(matches!(
+            parse::<AnswerVerdict>(r#"{"passed": true, "feedback": "#),
+            Err(StructuredOutputError::NoJson)
+        ));
+    }
+
+    #[test]
+    fn parse_should_validate_beyond_serde() {
+        assert!(matches!(
+            parse::<SmartGoalVerdict>(r#"{"passed": true, "reason": "good"}"#),
+            Err(StructuredOutputError::Invalid(_))
+        ));
+        assert!(matches!(
+            parse::<GenerateQuestionsResult>(r#"{"questions": []}"#),
+            Err(StructuredOutputError::Invalid(_))
+        ));
+        assert!(matches!(
+            parse::<SmartGoalVerdict>(
+                r#"{"passed": false, "reason": "r", "milestones": [{"title": "x", "due_date": "next week", "criterion": "c"}]}"#
+            ),
+            Err(StructuredOutputError::Malformed(_))
+        ));
+    }
+}
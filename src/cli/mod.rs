@@ -1,37 +1,103 @@
 pub mod ui;
 
-use clap::{Args, Parser, Subcommand};
+use std::{path::PathBuf, str::FromStr};
+
+use chrono::NaiveDate;
+use clap::{Args, Parser, Subcommand, ValueEnum};
+
+use crate::domain::state::TaskStatus;
 
 #[derive(Parser)]
 #[command(
     author = "hedon",
     about = "Cognitive Gatekeeper",
     long_about = "Gewu is a cognitive gatekeeper that helps you clarify your learning motivation and turn it into a specific SMART goal.",
-    version = "0.1.0"
+    version = "0.1.0",
+    after_help = "Exit status: 0 on success, 1 on other errors, 2 on invalid arguments, 3 when the gatekeeper or the strategist rejects the input, 4 when a task is not found, 5 when the task status or a WIP limit does not allow it, 6 when the LLM is unavailable or answers nonsense, 7 on storage errors."
 )]
 pub struct Gewu {
+    /// How results are printed. `json` and `plain` are meant for scripts and never prompt
+    #[arg(long, global = true, value_enum, default_value_t = OutputFormat::Table)]
+    pub output: OutputFormat,
+    /// Answer yes to every confirmation
+    #[arg(short, long, global = true)]
+    pub yes: bool,
+    /// Never prompt, fail instead when an answer is missing from the options
+    #[arg(long, global = true)]
+    pub non_interactive: bool,
     #[command(subcommand)]
     pub operation: Operation,
 }
 
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    /// Colored cards and tables for the terminal
+    Table,
+    /// One tab-separated line per item, without colors
+    Plain,
+    /// One JSON document on stdout, errors as `{"error": ...}`
+    Json,
+}
+
 #[derive(Subcommand)]
 pub enum Operation {
     /// Add a new learning task
     Add(AddArgs),
     /// Describe a learning task
     Describe(DescribeArgs),
-    /// List all learning tasks
-    List,
-    /// Delete a learning task
+    /// Edit the topic, motivation or SMART goal of a task in your `$EDITOR`.
+    /// A new motivation or goal is audited again, the previous values are shown by `describe --history`.
+    Edit(EditArgs),
+    /// List all learning tasks, with the days left until their deadline
+    List(ListArgs),
+    /// Permanently delete a learning task with its history, requires `--purge`.
+    /// Use `discard` to abandon a task while keeping it in the graveyard.
     Delete(DeleteArgs),
+    /// Abandon a learning task with a reason, its records are kept in the graveyard.
+    Discard(DiscardArgs),
+    /// List the discarded tasks with why they were abandoned and how far they got.
+    Graveyard,
     /// Set a SMART goal for an existing learning task that was previously created without one.
     /// This follows the same process as the `Add` operation,
     /// but is intended for cases where adding a task was interrupted before setting a SMART goal.
     Plan(PlanArgs),
+    /// Pause a planning or active task so it no longer takes one of the limited slots.
+    /// With `--until`, `gewu list` and `gewu add` offer to resume it once the date has come.
+    Pause(PauseArgs),
+    /// Resume a paused task, back to active once it has a SMART goal, to planning otherwise.
+    Resume(ResumeArgs),
+    /// Move the deadline of a learning task, or clear it with `--clear`.
+    Deadline(DeadlineArgs),
+    /// Show the milestones of a task with its progress and check off the reached ones.
+    Milestones(MilestonesArgs),
     /// Record learning progress. The LLM will automatically verify if your submission aligns with active tasks.
     Record(RecordArgs),
+    /// Read a first-principles primer of an active task before diving in.
+    /// The primer is generated once and stored, use `--refresh` to generate a new one.
+    Learn(LearnArgs),
     /// Ask the llm to guide you on how to learn the given task.
     Guide(GuideArgs),
+    /// Submit a summary of what you learned for an active task.
+    /// The llm will generate scenario questions to examine your understanding.
+    Commit(CommitArgs),
+    /// Answer the review questions of a task. The task is completed once every question is passed,
+    /// failed questions can be retried by running this command again.
+    Review(ReviewArgs),
+    /// Show the skill tree of completed tasks, grouped by skill area.
+    Skills,
+    /// Search the topics, motivations and SMART goals of the tasks and the learning records.
+    /// Hits are ranked by relevance with the matched words highlighted.
+    Search(SearchArgs),
+    /// Export every task with its goal, milestones, records, reviews and history.
+    /// The JSON backup can be restored by `import`, the Markdown one is meant to be read.
+    Export(ExportArgs),
+    /// Restore a JSON backup written by `export`, the tasks and records get new ids.
+    /// Tasks that already exist are reported, use `--skip-existing` to import the rest.
+    Import(ImportArgs),
+    /// Write a Markdown note per task into a vault, e.g. an Obsidian one, and turn the list items
+    /// added under their `## Log` heading into learning records. Items in `Inbox.md` are matched
+    /// with the active tasks like `record` does.
+    Sync(SyncArgs),
 }
 
 #[derive(Args)]
@@ -43,30 +109,120 @@ pub struct AddArgs {
     /// The motivation of the learning task
     #[arg(short, long)]
     pub motivation: Option<String>,
+
+    /// The SMART goal to propose to the strategist once the motivation is approved
+    #[arg(short, long)]
+    pub goal: Option<String>,
 }
 
 #[derive(Args)]
 pub struct DescribeArgs {
     /// The id of the learning task
     pub id: i64,
+    /// Also show the earlier topics, motivations and goals of the task
+    #[arg(long)]
+    pub history: bool,
+}
+
+#[derive(Args)]
+pub struct EditArgs {
+    /// The id of the learning task
+    pub id: i64,
+    /// What to edit, asked interactively when omitted
+    #[arg(short, long, value_enum)]
+    pub field: Option<EditField>,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum EditField {
+    Topic,
+    Motivation,
+    Goal,
+}
+
+#[derive(Args)]
+pub struct ListArgs {
+    /// Only show planning and active tasks past their deadline
+    #[arg(long)]
+    pub overdue: bool,
 }
 
 #[derive(Args)]
 pub struct DeleteArgs {
     /// The id of the learning task
     pub id: i64,
+    /// Confirm that the task, its records links, reviews and history are removed for good
+    #[arg(long)]
+    pub purge: bool,
+}
+
+#[derive(Args)]
+pub struct DiscardArgs {
+    /// The id of the learning task
+    pub id: i64,
+    /// Why the task is abandoned, asked interactively when omitted
+    #[arg(short, long)]
+    pub reason: Option<String>,
 }
 
 #[derive(Args)]
 pub struct PlanArgs {
     /// The id of the learning task
     pub id: i64,
+    /// The SMART goal to propose to the strategist, asked interactively when omitted
+    #[arg(short, long)]
+    pub goal: Option<String>,
+}
+
+#[derive(Args)]
+pub struct PauseArgs {
+    /// The id of the learning task
+    pub id: i64,
+    /// When to pick the task up again, a date after today as YYYY-MM-DD
+    #[arg(short, long)]
+    pub until: Option<NaiveDate>,
+}
+
+#[derive(Args)]
+pub struct ResumeArgs {
+    /// The id of the learning task
+    pub id: i64,
+}
+
+#[derive(Args)]
+pub struct DeadlineArgs {
+    /// The id of the learning task
+    pub id: i64,
+    /// The new deadline, as YYYY-MM-DD
+    #[arg(required_unless_present = "clear")]
+    pub date: Option<NaiveDate>,
+    /// Remove the deadline
+    #[arg(long, conflicts_with = "date")]
+    pub clear: bool,
+}
+
+#[derive(Args)]
+pub struct MilestonesArgs {
+    /// The id of the learning task
+    pub id: i64,
 }
 
 #[derive(Args)]
 pub struct RecordArgs {
     /// The content of the learning record
     pub content: String,
+    /// Record it for these active tasks, e.g. `--tasks 3,5`, instead of matching them with the LLM
+    #[arg(long, value_delimiter = ',')]
+    pub tasks: Vec<i64>,
+}
+
+#[derive(Args)]
+pub struct LearnArgs {
+    /// The id of the learning task
+    pub id: i64,
+    /// Generate a new primer instead of showing the stored one
+    #[arg(short, long)]
+    pub refresh: bool,
 }
 
 #[derive(Args)]
@@ -74,3 +230,72 @@ pub struct GuideArgs {
     /// The id of the learning task
     pub id: i64,
 }
+
+#[derive(Args)]
+pub struct CommitArgs {
+    /// The id of the learning task
+    pub id: i64,
+
+    /// The summary of what you learned
+    #[arg(short, long)]
+    pub summary: Option<String>,
+}
+
+#[derive(Args)]
+pub struct ReviewArgs {
+    /// The id of the learning task
+    pub id: i64,
+}
+
+#[derive(Args)]
+pub struct SearchArgs {
+    /// The words to look for, all of them have to match
+    #[arg(required = true)]
+    pub query: Vec<String>,
+    /// Only tasks in this status and the records linked to them, can be repeated
+    #[arg(short, long, value_parser = TaskStatus::from_str)]
+    pub status: Vec<TaskStatus>,
+    /// Only what was created on or after this day, as YYYY-MM-DD
+    #[arg(long)]
+    pub from: Option<NaiveDate>,
+    /// Only what was created on or before this day, as YYYY-MM-DD
+    #[arg(long)]
+    pub to: Option<NaiveDate>,
+    /// How many hits to show at most, at least 1
+    #[arg(short = 'n', long, default_value_t = 20, value_parser = clap::value_parser!(i64).range(1..))]
+    pub limit: i64,
+}
+
+#[derive(Args)]
+pub struct ExportArgs {
+    /// The format of the backup
+    #[arg(short, long, value_enum, default_value_t = ExportFormat::Json)]
+    pub format: ExportFormat,
+    /// The file to write, the standard output when omitted
+    pub path: Option<PathBuf>,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum ExportFormat {
+    Json,
+    Markdown,
+}
+
+#[derive(Args)]
+pub struct ImportArgs {
+    /// The JSON backup written by `export`
+    pub path: PathBuf,
+    /// Import the rest of the backup when some tasks already exist
+    #[arg(long)]
+    pub skip_existing: bool,
+}
+
+#[derive(Args)]
+pub struct SyncArgs {
+    /// The folder of the Markdown notes, created when missing
+    #[arg(long)]
+    pub vault: PathBuf,
+    /// Overwrite the notes whose part written by gewu was edited by hand
+    #[arg(long)]
+    pub force: bool,
+}
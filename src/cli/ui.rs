@@ -1,14 +1,31 @@
-use std::collections::HashMap;
+use std::{
+    collections::{BTreeMap, HashMap},
+    path::Path,
+};
 
+use chrono::NaiveDate;
 use console::{Emoji, Term, style};
+use dialoguer::Confirm;
+use serde::Serialize;
 use termimad::MadSkin;
 
 use crate::{
+    cli::OutputFormat,
     domain::{
-        models::{Record, Task},
+        backup::{Backup, ImportReport},
+        models::{
+            AttemptOutcome, GraveyardEntry, HIGHLIGHT_END, HIGHLIGHT_START, Milestone, PastAttempt,
+            Record, Review, SearchHit, SearchHitKind, SmartGoalDetail, SmartGoalTurn, Task,
+            TaskArchive, TaskField, TaskRevision, TaskTransition, TurnRole,
+        },
+        policy::WipLimitReached,
         state::TaskStatus,
     },
-    ports::llm::SmartGoalDetail,
+    ports::{
+        error::GewuError,
+        llm::{MilestonePlan, SmartGoalVerdict},
+    },
+    services::vault::SyncReport,
 };
 
 static CHECKMARK: Emoji<'_, '_> = Emoji("✅ ", "[OK] ");
@@ -20,17 +37,141 @@ static BOOK: Emoji<'_, '_> = Emoji("📝 ", "");
 static CHART: Emoji<'_, '_> = Emoji("📊 ", "");
 static THOUGHT: Emoji<'_, '_> = Emoji("💭 ", "");
 static COMPASS: Emoji<'_, '_> = Emoji("🧭 ", "=> ");
+static QUESTION: Emoji<'_, '_> = Emoji("❓ ", "[?] ");
+static TROPHY: Emoji<'_, '_> = Emoji("🏆 ", "[*] ");
+static TREE: Emoji<'_, '_> = Emoji("🌳 ", "");
+static GRAVE: Emoji<'_, '_> = Emoji("🪦 ", "");
+static FLAG: Emoji<'_, '_> = Emoji("🚩 ", "");
+static CALENDAR: Emoji<'_, '_> = Emoji("📅 ", "");
+static ALARM: Emoji<'_, '_> = Emoji("⏰ ", "[!] ");
+static PENCIL: Emoji<'_, '_> = Emoji("✏️ ", "");
+static MAGNIFIER: Emoji<'_, '_> = Emoji("🔍 ", "");
+
+/// `println!` for the human-readable output, silent with `--output json` so stdout stays one
+/// JSON document
+macro_rules! say {
+    ($ui:expr) => {
+        if !$ui.is_json() {
+            println!();
+        }
+    };
+    ($ui:expr, $($arg:tt)*) => {
+        if !$ui.is_json() {
+            println!($($arg)*);
+        }
+    };
+}
 
 pub struct UI {
     width: usize,
+    /// Local date that deadlines and due dates are compared against
+    today: NaiveDate,
+    output: OutputFormat,
+    /// Answer yes to every confirmation instead of prompting
+    assume_yes: bool,
+    /// Whether prompts can be shown, only on a terminal with table output
+    interactive: bool,
 }
 
 impl UI {
-    pub fn new() -> Self {
+    pub fn new(output: OutputFormat, assume_yes: bool) -> Self {
         let term = Term::stdout();
         let width = term.size().1 as usize;
+        if output != OutputFormat::Table {
+            console::set_colors_enabled(false);
+        }
         Self {
             width: width.min(80),
+            today: chrono::Local::now().date_naive(),
+            output,
+            assume_yes,
+            // Prompts are drawn on stderr
+            interactive: output == OutputFormat::Table && Term::stderr().is_term(),
+        }
+    }
+
+    /// Turn the prompts off, e.g. for `--non-interactive`
+    pub fn with_prompts(mut self, prompts: bool) -> Self {
+        self.interactive &= prompts;
+        self
+    }
+
+    pub fn is_json(&self) -> bool {
+        self.output == OutputFormat::Json
+    }
+
+    fn is_plain(&self) -> bool {
+        self.output == OutputFormat::Plain
+    }
+
+    /// Whether the user can be asked on the terminal, scripts reading json or plain output can't
+    pub fn is_interactive(&self) -> bool {
+        self.interactive
+    }
+
+    pub fn assumes_yes(&self) -> bool {
+        self.assume_yes
+    }
+
+    /// Ask a yes/no question. `--yes` answers it, without a terminal to ask on it fails fast.
+    pub fn confirm(&self, prompt: &str, default: bool) -> anyhow::Result<bool> {
+        if self.assume_yes {
+            return Ok(true);
+        }
+        if !self.is_interactive() {
+            return Err(GewuError::invalid(format!(
+                "\"{}\" needs an answer, pass --yes to confirm",
+                prompt
+            ))
+            .into());
+        }
+        Ok(Confirm::new()
+            .with_prompt(style(prompt).cyan().to_string())
+            .default(default)
+            .interact()?)
+    }
+
+    /// Print the result of a command as one JSON document, only with `--output json`
+    pub fn print_json<T: Serialize>(&self, value: &T) -> anyhow::Result<()> {
+        if self.is_json() {
+            println!("{}", serde_json::to_string_pretty(value)?);
+        }
+        Ok(())
+    }
+
+    /// Report a failure, as `{"error": ...}` on stdout with `--output json`
+    pub fn print_error(&self, error: impl std::fmt::Display) {
+        if self.is_json() {
+            println!("{}", serde_json::json!({ "error": error.to_string() }));
+        } else {
+            eprintln!("{} {}", style("Error:").red().bold(), error);
+        }
+    }
+
+    /// Print a failed command with a hint on what to do about it,
+    /// `{"error": ..., "kind": ...}` on stdout in json mode
+    pub fn print_failure(&self, kind: &str, error: impl std::fmt::Display, hint: Option<&str>) {
+        if self.is_json() {
+            println!(
+                "{}",
+                serde_json::json!({ "error": error.to_string(), "kind": kind })
+            );
+            return;
+        }
+        eprintln!("{} {}", style("Error:").red().bold(), error);
+        if let Some(hint) = hint {
+            eprintln!("{} {}", LIGHTBULB, style(hint).dim());
+        }
+    }
+
+    /// Print why the LLM rejected the input as `{"error": ..., "verdict": ...}`, only with
+    /// `--output json`. The other outputs show the verdict itself.
+    pub fn print_rejected(&self, error: impl std::fmt::Display, verdict: &impl Serialize) {
+        if self.is_json() {
+            println!(
+                "{}",
+                serde_json::json!({ "error": error.to_string(), "verdict": verdict })
+            );
         }
     }
 
@@ -45,8 +186,41 @@ impl UI {
 
     // ─── Motivation ─────────────────────────────────────────
 
+    pub fn print_past_attempts(&self, attempts: &[PastAttempt]) {
+        if attempts.is_empty() {
+            return;
+        }
+
+        say!(
+            self,
+            "\n{} {}",
+            GRAVE,
+            style(format!(
+                "You have tried similar topics {} time(s) before:",
+                attempts.len()
+            ))
+            .yellow()
+            .bold()
+        );
+        for attempt in attempts {
+            let outcome = match attempt.outcome {
+                AttemptOutcome::Rejected => style("rejected").red(),
+                AttemptOutcome::Discarded => style("discarded").red().dim(),
+            };
+            say!(
+                self,
+                "   {}  {} {}  {}",
+                style(attempt.at.format("%Y-%m-%d")).dim(),
+                outcome,
+                style(&attempt.topic).bold(),
+                style(attempt.reason.as_deref().unwrap_or("")).dim()
+            );
+        }
+    }
+
     pub fn print_checking_motivation(&self) {
-        println!(
+        say!(
+            self,
             "\n{} {}",
             HOURGLASS,
             style("Reviewing your motivation...").cyan()
@@ -54,47 +228,61 @@ impl UI {
     }
 
     pub fn print_motivation_approved(&self, topic: &str, reason: &str, recommendation: &str) {
-        println!("\n{}", style(self.separator("success")).green().dim());
-        println!(
+        say!(self, "\n{}", style(self.separator("success")).green().dim());
+        say!(
+            self,
             "{} {}",
             CHECKMARK,
             style("MOTIVATION APPROVED").green().bold()
         );
-        println!("{}", style(self.separator("success")).green().dim());
+        say!(self, "{}", style(self.separator("success")).green().dim());
 
-        println!("\n{} {}", BOOK, style(topic).bold());
-        println!(
+        say!(self, "\n{} {}", BOOK, style(topic).bold());
+        say!(
+            self,
             "{} {}",
             CHART,
             style("Status: Validating → Planning").yellow()
         );
 
-        println!("\n{} {}", THOUGHT, style("Analysis").cyan().bold());
+        say!(self, "\n{} {}", THOUGHT, style("Analysis").cyan().bold());
         self.print_wrapped_text(reason, 3);
 
-        println!("\n{} {}", TARGET, style("Next Step").magenta().bold());
+        say!(self, "\n{} {}", TARGET, style("Next Step").magenta().bold());
         self.print_wrapped_text(recommendation, 3);
 
-        println!("\n{}", style(self.separator("success")).green().dim());
+        say!(self, "\n{}", style(self.separator("success")).green().dim());
     }
 
     pub fn print_motivation_rejected(&self, reason: &str, recommendation: &str) {
-        println!("\n{}", style(self.separator("error")).red().dim());
-        println!("{} {}", CROSS, style("MOTIVATION REJECTED").red().bold());
-        println!("{}", style(self.separator("error")).red().dim());
+        say!(self, "\n{}", style(self.separator("error")).red().dim());
+        say!(
+            self,
+            "{} {}",
+            CROSS,
+            style("MOTIVATION REJECTED").red().bold()
+        );
+        say!(self, "{}", style(self.separator("error")).red().dim());
 
-        println!(
+        say!(
+            self,
             "\n{} {}",
             THOUGHT,
             style("Why it was rejected").yellow().bold()
         );
         self.print_wrapped_text(reason, 3);
 
-        println!("\n{} {}", LIGHTBULB, style("How to improve").cyan().bold());
+        say!(
+            self,
+            "\n{} {}",
+            LIGHTBULB,
+            style("How to improve").cyan().bold()
+        );
         self.print_wrapped_text(recommendation, 3);
 
-        println!("\n{}", style(self.separator("error")).red().dim());
-        println!(
+        say!(self, "\n{}", style(self.separator("error")).red().dim());
+        say!(
+            self,
             "\n{} Try again with a more specific motivation\n",
             style("💡").cyan()
         );
@@ -103,63 +291,88 @@ impl UI {
     // ─── SMART Goal ─────────────────────────────────────────
 
     pub fn print_checking_smart_goal(&self) {
-        println!(
+        say!(
+            self,
             "\n{} {}",
             HOURGLASS,
             style("Evaluating your SMART goal...").cyan()
         );
     }
 
-    pub fn print_smart_goal_approved(&self, reason: &str, detail: &SmartGoalDetail) {
-        println!("\n{}", style(self.separator("success")).green().dim());
-        println!(
+    pub fn print_smart_goal_approved(
+        &self,
+        reason: &str,
+        detail: &SmartGoalDetail,
+        milestones: &[MilestonePlan],
+    ) {
+        say!(self, "\n{}", style(self.separator("success")).green().dim());
+        say!(
+            self,
             "{} {}",
             CHECKMARK,
             style("SMART GOAL APPROVED").green().bold()
         );
-        println!("{}", style(self.separator("success")).green().dim());
+        say!(self, "{}", style(self.separator("success")).green().dim());
 
-        println!("\n{} {}", THOUGHT, style("Analysis").cyan().bold());
+        say!(self, "\n{} {}", THOUGHT, style("Analysis").cyan().bold());
         self.print_wrapped_text(reason, 3);
 
-        println!(
+        say!(
+            self,
             "\n{} {}",
             TARGET,
             style("Refined SMART Goal").magenta().bold()
         );
         self.print_smart_goal_table(detail);
 
-        println!("\n{}", style(self.separator("success")).green().dim());
+        if !milestones.is_empty() {
+            say!(self, "\n{} {}", FLAG, style("Milestones").magenta().bold());
+            for milestone in milestones {
+                say!(
+                    self,
+                    "   {}  {}",
+                    style(milestone.due_date).cyan(),
+                    style(&milestone.title).bold()
+                );
+                self.print_wrapped_text(&milestone.criterion, 15);
+            }
+        }
+
+        say!(self, "\n{}", style(self.separator("success")).green().dim());
     }
 
     pub fn print_smart_goal_rejected(&self, reason: &str, guidance: &str) {
-        println!("\n{}", style(self.separator("error")).red().dim());
-        println!(
+        say!(self, "\n{}", style(self.separator("error")).red().dim());
+        say!(
+            self,
             "{} {}",
             CROSS,
             style("SMART GOAL NEEDS IMPROVEMENT").red().bold()
         );
-        println!("{}", style(self.separator("error")).red().dim());
+        say!(self, "{}", style(self.separator("error")).red().dim());
 
-        println!(
+        say!(
+            self,
             "\n{} {}",
             THOUGHT,
             style("Why it needs improvement").yellow().bold()
         );
         self.print_wrapped_text(reason, 3);
 
-        println!(
+        say!(
+            self,
             "\n{} {}",
             LIGHTBULB,
             style("Think about this").cyan().bold()
         );
         self.print_wrapped_text(guidance, 3);
 
-        println!("\n{}", style(self.separator("error")).red().dim());
+        say!(self, "\n{}", style(self.separator("error")).red().dim());
     }
 
     pub fn print_smart_goal_saved(&self) {
-        println!(
+        say!(
+            self,
             "\n{} {}\n",
             CHECKMARK,
             style("SMART goal saved! Status → Active").green().bold()
@@ -167,29 +380,104 @@ impl UI {
     }
 
     pub fn print_smart_goal_not_saved(&self) {
-        println!(
+        say!(
+            self,
             "\n{} {}\n",
             LIGHTBULB,
             style("SMART goal not saved. You can update it later.").yellow()
         );
     }
 
+    // ─── Milestones ─────────────────────────────────────────
+
+    pub fn print_milestones(&self, milestones: &[Milestone]) {
+        if milestones.is_empty() {
+            return;
+        }
+
+        let done = milestones.iter().filter(|m| m.is_completed()).count();
+        let overdue = milestones
+            .iter()
+            .filter(|m| m.is_overdue(self.today))
+            .count();
+
+        say!(self, "\n{} {}", FLAG, style("Milestones").cyan().bold());
+        let status = if overdue == 0 {
+            style("on track".to_string()).green()
+        } else {
+            style(format!("{} overdue", overdue)).red().bold()
+        };
+        say!(
+            self,
+            "   {} {}  {}",
+            progress_bar(done, milestones.len(), 20),
+            style(format!("{}/{}", done, milestones.len())).bold(),
+            status
+        );
+
+        for milestone in milestones {
+            let (mark, due) = if milestone.is_completed() {
+                (
+                    style("[x]").green(),
+                    style(milestone.due_date.to_string()).dim(),
+                )
+            } else if milestone.is_overdue(self.today) {
+                (
+                    style("[!]").red().bold(),
+                    style(format!("{} overdue", milestone.due_date)).red(),
+                )
+            } else {
+                (
+                    style("[ ]").dim(),
+                    style(milestone.due_date.to_string()).cyan(),
+                )
+            };
+            say!(
+                self,
+                "\n   {} {}  {}",
+                mark,
+                style(&milestone.title).bold(),
+                due
+            );
+            self.print_wrapped_text(&milestone.criterion, 7);
+        }
+        say!(self, "\n{}", style(self.separator("default")).dim());
+    }
+
+    pub fn print_no_milestones(&self, task_id: i64) {
+        say!(
+            self,
+            "\n{} {}",
+            LIGHTBULB,
+            style(format!(
+                "Task #{} has no milestones, they are planned together with the SMART goal",
+                task_id
+            ))
+            .yellow()
+        );
+    }
+
     // ─── SMART Goal Table ───────────────────────────────────
 
     fn print_smart_goal_table(&self, detail: &SmartGoalDetail) {
-        let rows = [
+        // Goals saved as free text before they were structured only have the specific part
+        let rows: Vec<_> = [
             ("S", "Specific", &detail.specific),
             ("M", "Measurable", &detail.measurable),
             ("A", "Achievable", &detail.achievable),
             ("R", "Relevant", &detail.relevant),
             ("T", "Time-bound", &detail.time_bound),
-        ];
+        ]
+        .into_iter()
+        .filter(|(_, _, content)| !content.trim().is_empty())
+        .collect();
 
         let label_width = 12;
         let content_width = self.width.saturating_sub(label_width + 7); // 7 = borders + padding
 
         // top border
-        println!(
+        say!(
+            self,
             "\n   {}{}{}",
             style("┌").dim(),
             style("─".repeat(label_width + 2)).dim(),
@@ -208,7 +496,8 @@ impl UI {
             let wrapped = self.wrap_text(content, content_width);
 
             // First line
-            println!(
+            say!(
+                self,
                 "   {} {}{}{} {} {}",
                 style("│").dim(),
                 styled_label,
@@ -220,7 +509,8 @@ impl UI {
 
             // Continuation lines
             for line in wrapped.iter().skip(1) {
-                println!(
+                say!(
+                    self,
                     "   {} {}{} {} {}",
                     style("│").dim(),
                     " ".repeat(label_width),
@@ -232,7 +522,8 @@ impl UI {
 
             // row separator (not after last row)
             if i < rows.len() - 1 {
-                println!(
+                say!(
+                    self,
                     "   {}{}{}",
                     style("├").dim(),
                     style("─".repeat(label_width + 2)).dim(),
@@ -242,7 +533,8 @@ impl UI {
         }
 
         // bottom border
-        println!(
+        say!(
+            self,
             "   {}{}{}",
             style("└").dim(),
             style("─".repeat(label_width + 2)).dim(),
@@ -368,7 +660,7 @@ impl UI {
         for line in text.lines() {
             let trimmed = line.trim();
             if trimmed.is_empty() {
-                println!();
+                say!(self);
                 continue;
             }
 
@@ -382,27 +674,28 @@ impl UI {
                     current_line.push(' ');
                     current_line.push_str(word);
                 } else {
-                    println!("{}{}", indent_str, current_line);
+                    say!(self, "{}{}", indent_str, current_line);
                     current_line = word.to_string();
                 }
             }
 
             if !current_line.is_empty() {
-                println!("{}{}", indent_str, current_line);
+                say!(self, "{}{}", indent_str, current_line);
             }
         }
     }
 
     pub fn print_task_detail(&self, task: &Task) {
         // Header
-        println!("\n{}", style(self.separator("default")).dim());
-        println!(
+        say!(self, "\n{}", style(self.separator("default")).dim());
+        say!(
+            self,
             "{} {}  {}",
             BOOK,
             style(&task.topic).bold(),
             style(format!("(#{})", task.id)).dim()
         );
-        println!("{}", style(self.separator("default")).dim());
+        say!(self, "{}", style(self.separator("default")).dim());
 
         // Status
         let status_style = match task.status {
@@ -411,29 +704,47 @@ impl UI {
             crate::domain::state::TaskStatus::Reviewing => style(&task.status).cyan(),
             crate::domain::state::TaskStatus::Completed => style(&task.status).green().bold(),
             crate::domain::state::TaskStatus::Discarded => style(&task.status).red().dim(),
+            crate::domain::state::TaskStatus::Paused => style(&task.status).magenta(),
         };
-        println!("\n{} {}: {}", CHART, style("Status").dim(), status_style);
+        say!(
+            self,
+            "\n{} {}: {}",
+            CHART,
+            style("Status").dim(),
+            status_style
+        );
+
+        if let Some(deadline) = task.deadline {
+            say!(
+                self,
+                "{} {}: {}  {}",
+                CALENDAR,
+                style("Deadline").dim(),
+                deadline,
+                self.deadline_badge(task).unwrap_or_default()
+            );
+        }
 
         // Motivation
         if let Some(motivation) = &task.motivation {
-            println!("\n{} {}", THOUGHT, style("Motivation").cyan().bold());
+            say!(self, "\n{} {}", THOUGHT, style("Motivation").cyan().bold());
             self.print_wrapped_text(motivation, 3);
         }
 
         // SMART Goal
-        if let Some(smart_goal_str) = &task.smart_goal {
-            println!("\n{} {}", TARGET, style("SMART Goal").magenta().bold());
-            // Try to parse as structured SmartGoalDetail
-            if let Ok(detail) = serde_json::from_str::<SmartGoalDetail>(smart_goal_str) {
-                self.print_smart_goal_table(&detail);
-            } else {
-                // Fallback: plain text
-                self.print_wrapped_text(smart_goal_str, 3);
-            }
+        if let Some(detail) = &task.smart_goal {
+            say!(
+                self,
+                "\n{} {}",
+                TARGET,
+                style("SMART Goal").magenta().bold()
+            );
+            self.print_smart_goal_table(detail);
         }
 
         // Timestamps
-        println!(
+        say!(
+            self,
             "\n   {} {}    {} {}",
             style("Created:").dim(),
             style(task.created_at.format("%Y-%m-%d %H:%M")).dim(),
@@ -441,12 +752,98 @@ impl UI {
             style(task.updated_at.format("%Y-%m-%d %H:%M")).dim(),
         );
 
-        println!("\n{}", style(self.separator("default")).dim());
+        say!(self, "\n{}", style(self.separator("default")).dim());
+    }
+
+    pub fn print_smart_goal_transcript(&self, turns: &[SmartGoalTurn]) {
+        if turns.is_empty() {
+            return;
+        }
+
+        say!(
+            self,
+            "\n{} {}",
+            COMPASS,
+            style("Planning Conversation").cyan().bold()
+        );
+        for turn in turns {
+            match turn.role {
+                TurnRole::Learner => {
+                    say!(self, "\n   {}", style("You").green().bold());
+                    self.print_wrapped_text(&turn.content, 5);
+                }
+                TurnRole::Strategist => {
+                    say!(self, "\n   {}", style("Strategist").magenta().bold());
+                    // Strategist turns are stored as verdict JSON
+                    match serde_json::from_str::<SmartGoalVerdict>(&turn.content) {
+                        Ok(verdict) => {
+                            let mark = if verdict.passed { CHECKMARK } else { CROSS };
+                            say!(self, "     {}{}", mark, verdict.reason);
+                            if let Some(guidance) = &verdict.guidance {
+                                self.print_wrapped_text(guidance, 5);
+                            }
+                        }
+                        Err(_) => self.print_wrapped_text(&turn.content, 5),
+                    }
+                }
+            }
+        }
+    }
+
+    pub fn print_task_revisions(&self, revisions: &[TaskRevision]) {
+        if revisions.is_empty() {
+            return;
+        }
+
+        say!(self, "\n{} {}", PENCIL, style("Edits").cyan().bold());
+        for revision in revisions {
+            say!(
+                self,
+                "\n   {}  {}",
+                style(revision.created_at.format("%Y-%m-%d %H:%M")).dim(),
+                style(format!("{} was", revision.field)).bold(),
+            );
+            let previous = revision.previous.as_deref().unwrap_or("(none)");
+            // SMART goals are stored as JSON
+            match serde_json::from_str::<SmartGoalDetail>(previous) {
+                Ok(detail) if revision.field == TaskField::SmartGoal => {
+                    self.print_wrapped_text(&detail.to_string(), 5)
+                }
+                _ => self.print_wrapped_text(previous, 5),
+            }
+        }
+    }
+
+    pub fn print_transition_list(&self, transitions: &[TaskTransition]) {
+        if transitions.is_empty() {
+            return;
+        }
+
+        say!(self, "\n{} {}", CHART, style("History").cyan().bold());
+        for transition in transitions {
+            say!(
+                self,
+                "   {}  {} → {}  {}",
+                style(transition.created_at.format("%Y-%m-%d %H:%M")).dim(),
+                self.status_badge(&transition.from_status),
+                self.status_badge(&transition.to_status),
+                style(transition.reason.as_deref().unwrap_or("")).dim(),
+            );
+        }
+        say!(self, "\n{}", style(self.separator("default")).dim());
     }
 
     pub fn print_task_list(&self, tasks: Vec<Task>) {
+        if self.is_plain() {
+            for task in &tasks {
+                let deadline = task.deadline.map_or("-".to_string(), |d| d.to_string());
+                println!("{}\t{}\t{}\t{}", task.id, task.status, deadline, task.topic);
+            }
+            return;
+        }
         if tasks.is_empty() {
-            println!(
+            say!(
+                self,
                 "\n{} {}",
                 LIGHTBULB,
                 style("No tasks found. Use `gewu add` to create one!").dim()
@@ -456,11 +853,12 @@ impl UI {
 
         let grouped = group_tasks_by_status(tasks);
 
-        // Display order: Active → Planning → Reviewing → Completed → Discarded
+        // Display order: Active → Planning → Reviewing → Paused → Completed → Discarded
         let status_order = [
             (TaskStatus::Active, "Active", "green"),
             (TaskStatus::Planning, "Planning", "yellow"),
             (TaskStatus::Reviewing, "Reviewing", "cyan"),
+            (TaskStatus::Paused, "Paused", "magenta"),
             (TaskStatus::Completed, "Completed", "green_bold"),
             (TaskStatus::Discarded, "Discarded", "red"),
         ];
@@ -469,30 +867,38 @@ impl UI {
         for (status, label, _color) in &status_order {
             if let Some(tasks) = grouped.get(status) {
                 if !first_group {
-                    println!();
+                    say!(self);
                 }
                 first_group = false;
 
                 // Group header
                 let badge = self.status_badge(status);
-                println!("\n {} {} ({})", badge, style(*label).bold(), tasks.len());
-                println!(" {}", style("─".repeat(self.width - 1)).dim());
+                say!(
+                    self,
+                    "\n {} {} ({})",
+                    badge,
+                    style(*label).bold(),
+                    tasks.len()
+                );
+                say!(self, " {}", style("─".repeat(self.width - 1)).dim());
 
                 for task in tasks {
                     self.print_task_card(task);
                 }
             }
         }
-        println!();
+        say!(self);
     }
 
     pub fn print_task_card(&self, task: &Task) {
-        // Line 1: ID + Topic
-        println!(
-            "  {} {}  {}",
+        // Line 1: ID + Topic + deadline
+        say!(
+            self,
+            "  {} {}  {}  {}",
             style(format!("#{:<3}", task.id)).dim(),
             style(&task.topic).bold(),
             self.status_badge(&task.status),
+            self.deadline_badge(task).unwrap_or_default(),
         );
 
         // Line 2: Motivation (truncated) + SMART goal indicator
@@ -502,14 +908,16 @@ impl UI {
         } else {
             format!("{}", style("SMART ✗").yellow().dim())
         };
-        print!(
-            "        {}",
-            style(self.truncate_text(motivation_preview, self.width - 22)).dim()
+        say!(
+            self,
+            "        {}  {}",
+            style(self.truncate_text(motivation_preview, self.width - 22)).dim(),
+            smart_icon
         );
-        println!("  {}", smart_icon);
 
         // Line 3: Timestamps
-        println!(
+        say!(
+            self,
             "        {} {}  {} {}",
             style("created").dim(),
             style(task.created_at.format("%m-%d %H:%M")).dim(),
@@ -518,7 +926,49 @@ impl UI {
         );
 
         // Separator between cards
-        println!("  {}", style("· · ·").dim());
+        say!(self, "  {}", style("· · ·").dim());
+    }
+
+    /// Days remaining or overdue, for tasks that are still in progress
+    fn deadline_badge(&self, task: &Task) -> Option<String> {
+        if matches!(task.status, TaskStatus::Completed | TaskStatus::Discarded) {
+            return None;
+        }
+        if let Some(until) = task
+            .paused_until
+            .filter(|_| task.status == TaskStatus::Paused)
+        {
+            return Some(style(format!("until {}", until)).magenta().to_string());
+        }
+        let days = task.days_remaining(self.today)?;
+        let badge = match days {
+            _ if task.is_overdue(self.today) => style(format!("⚠ {}d overdue", -days)).red().bold(),
+            ..0 => style(format!("{}d past deadline", -days)).dim(),
+            0 => style("due today".to_string()).yellow().bold(),
+            1..=3 => style(format!("{}d left", days)).yellow(),
+            _ => style(format!("{}d left", days)).dim(),
+        };
+        Some(badge.to_string())
+    }
+
+    pub fn print_review_candidates(&self, tasks: &[Task]) {
+        // Only a nudge, plain output is the task rows alone
+        if self.is_plain() {
+            return;
+        }
+        for task in tasks {
+            let days = -task.days_remaining(self.today).unwrap_or_default();
+            say!(
+                self,
+                "{} {}",
+                ALARM,
+                style(format!(
+                    "Task #{} \"{}\" is {} day(s) past its deadline. Run `gewu commit {}` to start the review, or `gewu deadline {} <date>` to move it.",
+                    task.id, task.topic, days, task.id, task.id
+                ))
+                .yellow()
+            );
+        }
     }
 
     fn status_badge(&self, status: &TaskStatus) -> String {
@@ -528,6 +978,7 @@ impl UI {
             TaskStatus::Reviewing => format!("{}", style("◎ Reviewing").cyan()),
             TaskStatus::Completed => format!("{}", style("✔ Completed").green().bold()),
             TaskStatus::Discarded => format!("{}", style("✘ Discarded").red().dim()),
+            TaskStatus::Paused => format!("{}", style("⏸ Paused").magenta()),
         }
     }
 
@@ -559,7 +1010,8 @@ impl UI {
     // ─── Record ───────────────────────────────────────────
 
     pub fn print_matching_records(&self) {
-        println!(
+        say!(
+            self,
             "\n{} {}",
             HOURGLASS,
             style("Matching your record with tasks...").cyan()
@@ -567,8 +1019,9 @@ impl UI {
     }
 
     pub fn print_matched_tasks(&self, task_ids: &[i64]) {
-        println!("\n{}", style(self.separator("success")).green().dim());
-        println!(
+        say!(self, "\n{}", style(self.separator("success")).green().dim());
+        say!(
+            self,
             "{} {}  {}",
             CHECKMARK,
             style("RECORD MATCHED").green().bold(),
@@ -578,23 +1031,48 @@ impl UI {
                 .collect::<Vec<String>>()
                 .join("  ")
         );
-        println!("{}", style(self.separator("success")).green().dim());
+        say!(self, "{}", style(self.separator("success")).green().dim());
     }
 
     pub fn print_no_matching_tasks(&self) {
-        println!("\n{}", style(self.separator("error")).red().dim());
-        println!(
+        say!(self, "\n{}", style(self.separator("error")).red().dim());
+        say!(
+            self,
             "{} {}",
             CROSS,
             style("No matching tasks found for this record")
                 .red()
                 .bold()
         );
-        println!("{}", style(self.separator("error")).red().dim());
+        say!(self, "{}", style(self.separator("error")).red().dim());
+    }
+
+    pub fn print_dropped_matches(&self, task_ids: &[i64]) {
+        say!(
+            self,
+            "{} {}  {}",
+            LIGHTBULB,
+            style("Ignored matches that are not active tasks").yellow(),
+            task_ids
+                .iter()
+                .map(|id| format!("{}", style(format!("#{}", id)).dim()))
+                .collect::<Vec<String>>()
+                .join("  ")
+        );
+    }
+
+    pub fn print_record_skipped(&self) {
+        say!(
+            self,
+            "\n{} {}\n",
+            LIGHTBULB,
+            style("No task selected, the record was not saved.").dim()
+        );
     }
 
     pub fn print_record_success(&self) {
-        println!(
+        say!(
+            self,
             "\n{} {}\n",
             CHECKMARK,
             style("Learning progress recorded!").green().bold()
@@ -603,7 +1081,8 @@ impl UI {
 
     pub fn print_record_list(&self, records: &[Record]) {
         if records.is_empty() {
-            println!(
+            say!(
+                self,
                 "\n{} {}",
                 LIGHTBULB,
                 style("No records yet. Use `gewu record` to track your progress!").dim()
@@ -611,36 +1090,39 @@ impl UI {
             return;
         }
 
-        println!("\n{}", style(self.separator("default")).dim());
-        println!(
+        say!(self, "\n{}", style(self.separator("default")).dim());
+        say!(
+            self,
             " {} {} ({})",
             BOOK,
             style("Learning Records").cyan().bold(),
             records.len()
         );
-        println!(" {}", style("─".repeat(self.width - 1)).dim());
+        say!(self, " {}", style("─".repeat(self.width - 1)).dim());
 
         // Show records in reverse chronological order (newest first)
         for (i, record) in records.iter().rev().enumerate() {
             let timestamp = style(record.created_at.format("%m-%d %H:%M")).dim();
             let content = self.truncate_text(&record.content, self.width - 18);
-            println!(
+            say!(
+                self,
                 "  {}  {}  {}",
                 style(format!("{:>2}.", i + 1)).dim(),
                 content,
                 timestamp,
             );
             if i < records.len() - 1 {
-                println!("  {}", style("· · ·").dim());
+                say!(self, "  {}", style("· · ·").dim());
             }
         }
-        println!("{}", style(self.separator("default")).dim());
+        say!(self, "{}", style(self.separator("default")).dim());
     }
 
     // ─── Guide ───────────────────────────────────────────
 
     pub fn print_guide_header(&self) {
-        println!(
+        say!(
+            self,
             "\n{} {}",
             HOURGLASS,
             style("Generating next-step guide...").cyan()
@@ -648,37 +1130,696 @@ impl UI {
     }
 
     pub fn print_guide_footer(&self) {
-        println!("\n{}", style(self.separator("default")).dim());
-        println!(
+        say!(self, "\n{}", style(self.separator("default")).dim());
+        say!(
+            self,
             " {} {}",
             COMPASS,
             style("Keep going! Record your progress with `gewu record`").dim()
         );
-        println!("{}", style(self.separator("default")).dim());
+        say!(self, "{}", style(self.separator("default")).dim());
+    }
+
+    // ─── Learn ───────────────────────────────────────────
+
+    pub fn print_primer_header(&self) {
+        say!(
+            self,
+            "\n{} {}",
+            HOURGLASS,
+            style("The master is writing your primer...").cyan()
+        );
+    }
+
+    pub fn print_primer(&self, primer: &str) {
+        if self.is_json() {
+            return;
+        }
+        say!(self);
+        Self::guide_skin().print_text(primer);
+    }
+
+    pub fn print_primer_footer(&self, task_id: i64, stored: bool) {
+        say!(self, "\n{}", style(self.separator("default")).dim());
+        if stored {
+            say!(
+                self,
+                " {} {}",
+                LIGHTBULB,
+                style(format!(
+                    "Stored primer. Run `gewu learn {} --refresh` to generate a new one",
+                    task_id
+                ))
+                .dim()
+            );
+        }
+        say!(
+            self,
+            " {} {}",
+            COMPASS,
+            style("Start learning! Record your progress with `gewu record`").dim()
+        );
+        say!(self, "{}", style(self.separator("default")).dim());
+    }
+
+    // ─── Skills ──────────────────────────────────────────
+
+    pub fn print_skill_tree(&self, archives: &[TaskArchive]) {
+        if self.is_plain() {
+            for (tag, archives) in group_archives_by_tag(archives) {
+                for archive in archives {
+                    println!("{}\t{}\t{}", tag, archive.task_id, archive.topic);
+                }
+            }
+            return;
+        }
+        if archives.is_empty() {
+            say!(
+                self,
+                "\n{} {}",
+                LIGHTBULB,
+                style("Your skill tree is empty. Pass a review with `gewu review` to grow it!")
+                    .dim()
+            );
+            return;
+        }
+
+        let grouped = group_archives_by_tag(archives);
+
+        say!(self, "\n{}", style(self.separator("default")).dim());
+        say!(
+            self,
+            " {} {} ({} skills, {} tasks)",
+            TREE,
+            style("Skill Tree").cyan().bold(),
+            grouped.len(),
+            archives.len()
+        );
+        say!(self, "{}", style(self.separator("default")).dim());
+
+        let mut tags = grouped.iter().peekable();
+        while let Some((tag, tasks)) = tags.next() {
+            let last_tag = tags.peek().is_none();
+            say!(
+                self,
+                "{} {} {}",
+                style(if last_tag { "└─" } else { "├─" }).dim(),
+                style(tag).green().bold(),
+                style(format!("({})", tasks.len())).dim()
+            );
+
+            let branch = if last_tag { "   " } else { "│  " };
+            for (i, archive) in tasks.iter().enumerate() {
+                let leaf = if i + 1 == tasks.len() {
+                    "└─"
+                } else {
+                    "├─"
+                };
+                say!(
+                    self,
+                    "{}{} {} {}  {}",
+                    style(branch).dim(),
+                    style(leaf).dim(),
+                    style(format!("#{}", archive.task_id)).cyan(),
+                    archive.topic,
+                    style(archive.completed_at.format("%Y-%m-%d")).dim()
+                );
+            }
+        }
+        say!(self, "{}", style(self.separator("default")).dim());
+    }
+
+    // ─── Graveyard ───────────────────────────────────────
+
+    pub fn print_wip_limit_reached(&self, limit: &WipLimitReached) {
+        say!(self, "\n{}", style(self.separator("error")).red().dim());
+        say!(self, "{} {}", CROSS, style(limit).red().bold());
+        say!(self, "{}", style(self.separator("error")).red().dim());
+    }
+
+    pub fn print_task_edited(&self, task_id: i64, field: &TaskField) {
+        say!(
+            self,
+            "\n{} {}\n",
+            CHECKMARK,
+            style(format!(
+                "{} of task #{} updated, the previous one is in `gewu describe {} --history`",
+                field, task_id, task_id
+            ))
+            .green()
+            .bold()
+        );
+    }
+
+    pub fn print_edit_unchanged(&self, task_id: i64) {
+        say!(
+            self,
+            "\n{} {}\n",
+            LIGHTBULB,
+            style(format!(
+                "Nothing changed, task #{} is left as it was",
+                task_id
+            ))
+            .yellow()
+        );
+    }
+
+    pub fn print_task_paused(&self, task_id: i64) {
+        say!(
+            self,
+            "\n{} {}\n",
+            CHECKMARK,
+            style(format!(
+                "Task #{} paused, its slot is free. Run `gewu resume {}` to pick it up again",
+                task_id, task_id
+            ))
+            .green()
+            .bold()
+        );
+    }
+
+    pub fn print_task_due_to_resume(&self, task: &Task) {
+        say!(
+            self,
+            "\n{} {}",
+            ALARM,
+            style(format!(
+                "Task #{} \"{}\" was paused until {}",
+                task.id,
+                task.topic,
+                task.paused_until.map(|d| d.to_string()).unwrap_or_default()
+            ))
+            .yellow()
+            .bold()
+        );
+    }
+
+    pub fn print_task_resumed(&self, task_id: i64, status: &TaskStatus) {
+        say!(
+            self,
+            "\n{} {}\n",
+            CHECKMARK,
+            style(format!("Task #{} resumed. Status → {}", task_id, status))
+                .green()
+                .bold()
+        );
+    }
+
+    pub fn print_goal_missing(&self, task_id: i64) {
+        say!(
+            self,
+            "\n{} {}\n",
+            LIGHTBULB,
+            style(format!(
+                "Task #{} stays in planning. Pass --goal or run `gewu plan {}` to set its SMART goal",
+                task_id, task_id
+            ))
+            .yellow()
+        );
+    }
+
+    pub fn print_plan_later(&self, task_id: i64) {
+        say!(
+            self,
+            "\n{} {}\n",
+            LIGHTBULB,
+            style(format!(
+                "Task #{} stays in planning. Run `gewu plan {}` once an active slot is free",
+                task_id, task_id
+            ))
+            .yellow()
+        );
+    }
+
+    pub fn print_task_discarded(&self, task_id: i64) {
+        say!(
+            self,
+            "\n{} {}\n",
+            CHECKMARK,
+            style(format!(
+                "Task #{} discarded. Its records are kept, see `gewu graveyard`",
+                task_id
+            ))
+            .green()
+            .bold()
+        );
+    }
+
+    pub fn print_task_deleted(&self, task_id: i64) {
+        say!(
+            self,
+            "{}",
+            style(format!("Task #{} deleted", task_id)).green().bold()
+        );
+    }
+
+    pub fn print_purge_required(&self, task_id: i64) {
+        if self.is_json() {
+            self.print_error(format!("Task #{} needs --purge to be deleted", task_id));
+            return;
+        }
+        say!(
+            self,
+            "\n{} {}",
+            LIGHTBULB,
+            style("Deleting removes the task, its record links, reviews and history for good.")
+                .yellow()
+        );
+        say!(
+            self,
+            "   {}\n",
+            style(format!(
+                "Use `gewu discard {}` to keep it in the graveyard, or `gewu delete {} --purge` to delete it.",
+                task_id, task_id
+            ))
+            .dim()
+        );
+    }
+
+    pub fn print_graveyard(&self, entries: &[GraveyardEntry]) {
+        if self.is_plain() {
+            for entry in entries {
+                println!(
+                    "{}\t{}\t{}\t{}",
+                    entry.task.id,
+                    entry.discarded_at.format("%Y-%m-%d"),
+                    entry.task.topic,
+                    entry.reason.as_deref().unwrap_or("-")
+                );
+            }
+            return;
+        }
+        if entries.is_empty() {
+            say!(
+                self,
+                "\n{} {}",
+                LIGHTBULB,
+                style("The graveyard is empty. Every task you started is still alive!").dim()
+            );
+            return;
+        }
+
+        say!(self, "\n{}", style(self.separator("default")).dim());
+        say!(
+            self,
+            " {} {} ({})",
+            GRAVE,
+            style("Graveyard").red().bold(),
+            entries.len()
+        );
+        say!(self, "{}", style(self.separator("default")).dim());
+
+        for entry in entries {
+            say!(
+                self,
+                "\n {} {}  {}",
+                style(format!("#{}", entry.task.id)).cyan().bold(),
+                style(&entry.task.topic).bold(),
+                style(entry.discarded_at.format("%Y-%m-%d")).dim()
+            );
+            say!(
+                self,
+                "   {} {}  {}",
+                style("Got to").dim(),
+                self.status_badge(&entry.discarded_from),
+                style(format!("{} records", entry.records)).dim()
+            );
+            if let Some(reason) = &entry.reason {
+                say!(self, "   {}", style("Why it was abandoned").yellow());
+                self.print_wrapped_text(reason, 5);
+            }
+        }
+        say!(self, "\n{}", style(self.separator("default")).dim());
+    }
+
+    // ─── Search ──────────────────────────────────────────
+
+    pub fn print_search_results(&self, query: &str, hits: &[SearchHit]) {
+        if self.is_plain() {
+            for hit in hits {
+                let kind = match hit.kind {
+                    SearchHitKind::Task => "task",
+                    SearchHitKind::Record => "record",
+                };
+                let snippet = hit
+                    .snippet
+                    .replace(HIGHLIGHT_START, "")
+                    .replace(HIGHLIGHT_END, "")
+                    .split_whitespace()
+                    .collect::<Vec<_>>()
+                    .join(" ");
+                println!(
+                    "{}\t{}\t{}\t{}\t{}",
+                    kind,
+                    hit.id,
+                    hit.created_at.format("%Y-%m-%d"),
+                    hit.title.as_deref().unwrap_or("-"),
+                    snippet
+                );
+            }
+            return;
+        }
+        if hits.is_empty() {
+            say!(
+                self,
+                "\n{} {}",
+                LIGHTBULB,
+                style(format!(
+                    "Nothing matches \"{}\". Try fewer words or a wider date range",
+                    query
+                ))
+                .dim()
+            );
+            return;
+        }
+
+        say!(self, "\n{}", style(self.separator("default")).dim());
+        say!(
+            self,
+            " {} {} ({})",
+            MAGNIFIER,
+            style(format!("Results for \"{}\"", query)).cyan().bold(),
+            hits.len()
+        );
+        say!(self, "{}", style(self.separator("default")).dim());
+
+        for hit in hits {
+            let label = match hit.kind {
+                SearchHitKind::Task => format!("Task #{}", hit.id),
+                SearchHitKind::Record => format!("Record #{}", hit.id),
+            };
+            let title = match &hit.title {
+                Some(title) => style(title.clone()).bold(),
+                None => style("not linked to a task".to_string()).dim(),
+            };
+            let status = hit
+                .status
+                .as_ref()
+                .map(|status| self.status_badge(status))
+                .unwrap_or_default();
+            say!(
+                self,
+                "\n {} {}  {}  {}",
+                style(label).cyan().bold(),
+                title,
+                status,
+                style(hit.created_at.format("%Y-%m-%d")).dim()
+            );
+            self.print_wrapped_text(&highlight_matches(&hit.snippet), 3);
+        }
+        say!(self, "\n{}", style(self.separator("default")).dim());
+    }
+
+    // ─── Backup ──────────────────────────────────────────
+
+    pub fn print_exported(&self, path: &Path, backup: &Backup) {
+        say!(
+            self,
+            "\n{} {}\n",
+            CHECKMARK,
+            style(format!(
+                "Exported {} task(s) and {} record(s) to {}",
+                backup.tasks.len(),
+                backup.records.len(),
+                path.display()
+            ))
+            .green()
+            .bold()
+        );
+    }
+
+    pub fn print_import_report(&self, report: &ImportReport) {
+        say!(
+            self,
+            "\n{} {}",
+            CHECKMARK,
+            style(format!(
+                "Imported {} task(s), {} record(s) and {} rejected attempt(s)",
+                report.tasks, report.records, report.rejected_attempts
+            ))
+            .green()
+            .bold()
+        );
+        if !report.skipped_tasks.is_empty() {
+            say!(
+                self,
+                "{} {}",
+                LIGHTBULB,
+                style(format!(
+                    "Skipped {} task(s) that already exist",
+                    report.skipped_tasks.len()
+                ))
+                .yellow()
+            );
+        }
+        if !report.existing_records.is_empty() {
+            say!(
+                self,
+                "{} {}",
+                LIGHTBULB,
+                style(format!(
+                    "Linked {} record(s) that already exist instead of copying them",
+                    report.existing_records.len()
+                ))
+                .dim()
+            );
+        }
+        say!(self);
+    }
+
+    pub fn print_sync_report(&self, report: &SyncReport) {
+        say!(
+            self,
+            "\n{} {}",
+            CHECKMARK,
+            style(format!(
+                "{} note(s) written, {} unchanged, {} record(s) imported",
+                report.written, report.unchanged, report.records
+            ))
+            .green()
+            .bold()
+        );
+        if report.pending > 0 {
+            say!(
+                self,
+                "{} {}",
+                LIGHTBULB,
+                style(format!(
+                    "{} log entr(ies) kept for the next sync, no active task took them",
+                    report.pending
+                ))
+                .yellow()
+            );
+        }
+        for path in &report.conflicts {
+            say!(
+                self,
+                "{} {}",
+                CROSS,
+                style(format!(
+                    "{} was edited above its log or shares its gewu_id with another note",
+                    path.display()
+                ))
+                .red()
+            );
+        }
+        if !report.conflicts.is_empty() {
+            say!(
+                self,
+                "{} {}",
+                LIGHTBULB,
+                style("Run `gewu sync --force` to overwrite the edited notes, delete the copies of a note")
+                    .dim()
+            );
+        }
+        for path in &report.orphans {
+            say!(
+                self,
+                "{} {}",
+                LIGHTBULB,
+                style(format!("{} belongs to a deleted task", path.display())).dim()
+            );
+        }
+        say!(self);
+    }
+
+    // ─── Review ──────────────────────────────────────────
+
+    pub fn print_generating_questions(&self) {
+        say!(
+            self,
+            "\n{} {}",
+            HOURGLASS,
+            style("The examiner is preparing your questions...").cyan()
+        );
+    }
+
+    pub fn print_review_questions(&self, reviews: &[Review]) {
+        say!(self, "\n{}", style(self.separator("default")).dim());
+        say!(
+            self,
+            " {} {} ({})",
+            QUESTION,
+            style("Review Questions").cyan().bold(),
+            reviews.len()
+        );
+        say!(self, " {}", style("─".repeat(self.width - 1)).dim());
+
+        for (i, review) in reviews.iter().enumerate() {
+            say!(self, "\n  {}", style(format!("Q{}.", i + 1)).cyan().bold());
+            self.print_wrapped_text(&review.question, 3);
+        }
+
+        say!(self, "\n{}", style(self.separator("default")).dim());
+        if let Some(review) = reviews.first() {
+            say!(
+                self,
+                " {} {}",
+                COMPASS,
+                style(format!(
+                    "Answer them with `gewu review {}` when you are ready",
+                    review.task_id
+                ))
+                .dim()
+            );
+        }
+    }
+
+    pub fn print_question(&self, index: usize, total: usize, question: &str) {
+        say!(self, "\n{}", style(self.separator("default")).dim());
+        say!(
+            self,
+            "{} {}",
+            QUESTION,
+            style(format!("Question {}/{}", index, total)).cyan().bold()
+        );
+        say!(self, "{}", style(self.separator("default")).dim());
+        self.print_wrapped_text(question, 3);
+        say!(self);
+    }
+
+    pub fn print_scoring_answer(&self) {
+        say!(
+            self,
+            "\n{} {}",
+            HOURGLASS,
+            style("The examiner is scoring your answer...").cyan()
+        );
+    }
+
+    pub fn print_answer_passed(&self, feedback: &str) {
+        say!(self, "\n{} {}", CHECKMARK, style("PASSED").green().bold());
+        self.print_wrapped_text(feedback, 3);
+    }
+
+    pub fn print_answer_failed(&self, feedback: &str) {
+        say!(self, "\n{} {}", CROSS, style("NOT PASSED").red().bold());
+        self.print_wrapped_text(feedback, 3);
+    }
+
+    pub fn print_review_completed(&self, archive: &TaskArchive) {
+        say!(self, "\n{}", style(self.separator("success")).green().dim());
+        say!(
+            self,
+            "{} {}",
+            TROPHY,
+            style("ALL QUESTIONS PASSED! Status → Completed")
+                .green()
+                .bold()
+        );
+        say!(self, "{}", style(self.separator("success")).green().dim());
+
+        say!(self, "\n{} {}", BOOK, style("Summary").cyan().bold());
+        self.print_wrapped_text(&archive.summary, 3);
+        say!(
+            self,
+            "\n{} {}  {}",
+            TREE,
+            style("Added to your skill tree").cyan().bold(),
+            archive
+                .tags
+                .iter()
+                .map(|tag| format!("{}", style(tag).green()))
+                .collect::<Vec<String>>()
+                .join(&format!("{}", style(" · ").dim()))
+        );
+        say!(
+            self,
+            "\n{} {}",
+            COMPASS,
+            style("Run `gewu skills` to see the whole tree").dim()
+        );
+    }
+
+    pub fn print_review_incomplete(&self, task_id: i64, failed: usize) {
+        say!(self, "\n{}", style(self.separator("error")).red().dim());
+        say!(
+            self,
+            "{} {}",
+            CROSS,
+            style(format!("{} question(s) not passed yet", failed))
+                .red()
+                .bold()
+        );
+        say!(self, "{}", style(self.separator("error")).red().dim());
+        say!(
+            self,
+            "\n{} Study the feedback and retry with `gewu review {}`\n",
+            style("💡").cyan(),
+            task_id
+        );
+    }
+
+    pub fn print_back_to_active(&self, task_id: i64) {
+        say!(
+            self,
+            "\n{} {}\n",
+            COMPASS,
+            style(format!(
+                "Status → Active. Submit a new summary with `gewu commit {}` when you are ready",
+                task_id
+            ))
+            .yellow()
+        );
     }
 
     /// Render streaming guide output with markdown formatting.
     /// Buffers text by paragraph and renders each complete paragraph
     /// with termimad for proper markdown styling.
-    pub async fn print_guide_streaming(&self, rx: &mut tokio::sync::mpsc::Receiver<String>) {
+    /// Render the streamed markdown as it arrives, returns the whole text.
+    pub async fn print_guide_streaming(
+        &self,
+        rx: &mut tokio::sync::mpsc::Receiver<String>,
+    ) -> String {
         let skin = Self::guide_skin();
         let mut buffer = String::new();
+        let mut content = String::new();
         let mut block_count: usize = 0;
 
-        println!(); // breathing room before guide content
+        if self.is_json() {
+            while let Some(chunk) = rx.recv().await {
+                content.push_str(&chunk);
+            }
+            return content;
+        }
+
+        say!(self); // breathing room before guide content
 
         while let Some(chunk) = rx.recv().await {
             buffer.push_str(&chunk);
+            content.push_str(&chunk);
             Self::flush_markdown_blocks(&mut buffer, &skin, &mut block_count);
         }
 
         // Render any remaining content
         if !buffer.trim().is_empty() {
             if block_count > 0 {
-                println!();
+                say!(self);
             }
             skin.print_text(&buffer);
         }
+
+        content
     }
 
     /// Flush complete markdown blocks (paragraphs) from the buffer,
@@ -730,6 +1871,41 @@ impl UI {
     }
 }
 
+/// Group the archived tasks by tag, a task with several tags shows up under each of them.
+fn group_archives_by_tag(archives: &[TaskArchive]) -> BTreeMap<&str, Vec<&TaskArchive>> {
+    let mut map = BTreeMap::new();
+    for archive in archives {
+        for tag in &archive.tags {
+            map.entry(tag.as_str())
+                .or_insert_with(Vec::new)
+                .push(archive);
+        }
+    }
+    map
+}
+
+/// A bar of `width` cells filled in proportion to `done / total`.
+fn progress_bar(done: usize, total: usize, width: usize) -> String {
+    let filled = (done * width).checked_div(total).unwrap_or(0);
+    format!(
+        "{}{}",
+        style("█".repeat(filled)).green(),
+        style("░".repeat(width - filled)).dim()
+    )
+}
+
+/// Replace the highlight markers of a search snippet with bold yellow text
+fn highlight_matches(snippet: &str) -> String {
+    let mut parts = snippet.split(HIGHLIGHT_START);
+    let mut highlighted = parts.next().unwrap_or_default().to_string();
+    for part in parts {
+        let (matched, rest) = part.split_once(HIGHLIGHT_END).unwrap_or((part, ""));
+        highlighted.push_str(&style(matched).yellow().bold().to_string());
+        highlighted.push_str(rest);
+    }
+    highlighted
+}
+
 fn group_tasks_by_status(tasks: Vec<Task>) -> HashMap<TaskStatus, Vec<Task>> {
     let mut map = HashMap::new();
     for task in tasks {
@@ -742,6 +1918,6 @@ fn group_tasks_by_status(tasks: Vec<Task>) -> HashMap<TaskStatus, Vec<Task>> {
 
 impl Default for UI {
     fn default() -> Self {
-        Self::new()
+        Self::new(OutputFormat::Table, false)
     }
 }
@@ -0,0 +1,357 @@
+use std::{path::PathBuf, str::FromStr};
+
+use crate::domain::policy::WipPolicy;
+
+/// Where gewu stores its data, resolved from `DATABASE_URL`.
+#[derive(Debug, PartialEq)]
+pub enum DatabaseConfig {
+    Postgres(String),
+    Sqlite(String),
+    /// `DATABASE_URL=memory:`, nothing is persisted once the command exits
+    Memory,
+}
+
+impl DatabaseConfig {
+    /// Use `DATABASE_URL` when it is set, a `sqlite:` url selects the SQLite backend.
+    /// Without it, gewu falls back to a per-user SQLite file at `~/.gewu/data.db`.
+    pub fn from_env() -> Self {
+        Self::from_url(std::env::var("DATABASE_URL").ok())
+    }
+
+    fn from_url(url: Option<String>) -> Self {
+        match url {
+            Some(url) if url == "memory:" => Self::Memory,
+            Some(url) if url.starts_with("sqlite:") => Self::Sqlite(url),
+            Some(url) if !url.trim().is_empty() => Self::Postgres(url),
+            _ => Self::Sqlite(format!("sqlite://{}", default_data_file().display())),
+        }
+    }
+}
+
+fn default_data_file() -> PathBuf {
+    let home = std::env::var("HOME")
+        .or_else(|_| std::env::var("USERPROFILE"))
+        .unwrap_or_else(|_| ".".to_string());
+    PathBuf::from(home).join(".gewu").join("data.db")
+}
+
+/// OpenAI-compatible providers with known defaults, selected by `LLM_PROVIDER`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LlmProvider {
+    DeepSeek,
+    OpenAI,
+    Moonshot,
+    Ollama,
+}
+
+impl LlmProvider {
+    pub fn base_url(&self) -> &'static str {
+        match self {
+            Self::DeepSeek => "https://api.deepseek.com",
+            Self::OpenAI => "https://api.openai.com/v1",
+            Self::Moonshot => "https://api.moonshot.cn/v1",
+            Self::Ollama => "http://localhost:11434/v1",
+        }
+    }
+
+    pub fn model(&self) -> &'static str {
+        match self {
+            Self::DeepSeek => "deepseek-chat",
+            Self::OpenAI => "gpt-4o-mini",
+            Self::Moonshot => "moonshot-v1-8k",
+            Self::Ollama => "llama3.1",
+        }
+    }
+
+    /// The env var holding the api key, local servers such as Ollama do not need one.
+    pub fn api_key_env(&self) -> Option<&'static str> {
+        match self {
+            Self::DeepSeek => Some("DEEPSEEK_API_KEY"),
+            Self::OpenAI => Some("OPENAI_API_KEY"),
+            Self::Moonshot => Some("MOONSHOT_API_KEY"),
+            Self::Ollama => None,
+        }
+    }
+}
+
+impl FromStr for LlmProvider {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "deepseek" => Ok(Self::DeepSeek),
+            "openai" => Ok(Self::OpenAI),
+            "moonshot" => Ok(Self::Moonshot),
+            "ollama" => Ok(Self::Ollama),
+            _ => anyhow::bail!(
+                "Unknown LLM provider: {}, expected one of deepseek, openai, moonshot, ollama",
+                s
+            ),
+        }
+    }
+}
+
+/// Settings of the OpenAI-compatible endpoint gewu talks to.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LlmConfig {
+    pub base_url: String,
+    pub model: String,
+    pub temperature: Option<f64>,
+    pub api_key: Option<String>,
+}
+
+impl LlmConfig {
+    /// The default settings of a provider, with the api key read from its env var.
+    pub fn preset(provider: LlmProvider) -> Self {
+        Self {
+            base_url: provider.base_url().to_string(),
+            model: provider.model().to_string(),
+            temperature: None,
+            api_key: provider
+                .api_key_env()
+                .and_then(|name| std::env::var(name).ok()),
+        }
+    }
+
+    /// Start from the `LLM_PROVIDER` preset (DeepSeek by default) and apply the overrides from
+    /// `LLM_BASE_URL`, `LLM_MODEL`, `LLM_TEMPERATURE` and `LLM_API_KEY_ENV`.
+    pub fn from_env() -> anyhow::Result<Self> {
+        Self::from_vars(env_var)
+    }
+
+    fn from_vars(var: impl Fn(&str) -> Option<String>) -> anyhow::Result<Self> {
+        let provider = match var("LLM_PROVIDER") {
+            Some(p) => p.parse()?,
+            None => LlmProvider::DeepSeek,
+        };
+
+        let mut config = Self::preset(provider);
+        if let Some(base_url) = var("LLM_BASE_URL") {
+            config.base_url = base_url;
+        }
+        if let Some(model) = var("LLM_MODEL") {
+            config.model = model;
+        }
+        if let Some(temperature) = var("LLM_TEMPERATURE") {
+            config.temperature =
+                Some(temperature.parse().map_err(|e| {
+                    anyhow::anyhow!("Invalid LLM_TEMPERATURE {}: {}", temperature, e)
+                })?);
+        }
+
+        let api_key_env = var("LLM_API_KEY_ENV").or(provider.api_key_env().map(String::from));
+        if let Some(name) = api_key_env {
+            match var(&name) {
+                Some(api_key) => config.api_key = Some(api_key),
+                None => anyhow::bail!("{} is not set", name),
+            }
+        }
+
+        Ok(config)
+    }
+}
+
+/// Read an env var, treating an empty value as unset.
+fn env_var(name: &str) -> Option<String> {
+    std::env::var(name).ok().filter(|v| !v.trim().is_empty())
+}
+
+/// Whether LLM responses come from the configured endpoint or from fixture files.
+#[derive(Debug, PartialEq)]
+pub enum LlmMode {
+    Live,
+    /// Call the endpoint and append every response to the file, set by `LLM_RECORD`
+    Record(PathBuf),
+    /// Replay the responses saved in the file without network, set by `LLM_REPLAY`.
+    /// Each command continues where the previous one stopped.
+    Replay(PathBuf),
+}
+
+impl LlmMode {
+    pub fn from_env() -> Self {
+        if let Some(path) = env_var("LLM_REPLAY") {
+            Self::Replay(path.into())
+        } else if let Some(path) = env_var("LLM_RECORD") {
+            Self::Record(path.into())
+        } else {
+            Self::Live
+        }
+    }
+}
+
+impl WipPolicy {
+    /// Override the default limits with `WIP_MAX_PLANNING` and `WIP_MAX_ACTIVE`.
+    pub fn from_env() -> anyhow::Result<Self> {
+        Self::from_vars(env_var)
+    }
+
+    fn from_vars(var: impl Fn(&str) -> Option<String>) -> anyhow::Result<Self> {
+        let mut policy = Self::default();
+        if let Some(max) = var("WIP_MAX_PLANNING") {
+            policy.max_planning = max
+                .parse()
+                .map_err(|e| anyhow::anyhow!("Invalid WIP_MAX_PLANNING {}: {}", max, e))?;
+        }
+        if let Some(max) = var("WIP_MAX_ACTIVE") {
+            policy.max_active = max
+                .parse()
+                .map_err(|e| anyhow::anyhow!("Invalid WIP_MAX_ACTIVE {}: {}", max, e))?;
+        }
+        Ok(policy)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    /// Look the variables up in a fixed map instead of the process env
+    fn vars(pairs: &[(&str, &str)]) -> impl Fn(&str) -> Option<String> {
+        let map: HashMap<String, String> = pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        move |name| map.get(name).cloned()
+    }
+
+    #[test]
+    fn database_url_should_select_the_backend() {
+        assert_eq!(
+            DatabaseConfig::from_url(Some("memory:".to_string())),
+            DatabaseConfig::Memory
+        );
+        assert_eq!(
+            DatabaseConfig::from_url(Some("sqlite:///tmp/gewu.db".to_string())),
+            DatabaseConfig::Sqlite("sqlite:///tmp/gewu.db".to_string())
+        );
+        assert_eq!(
+            DatabaseConfig::from_url(Some("postgres://localhost/gewu".to_string())),
+            DatabaseConfig::Postgres("postgres://localhost/gewu".to_string())
+        );
+    }
+
+    #[test]
+    fn missing_database_url_should_fall_back_to_the_sqlite_file() {
+        for url in [None, Some("  ".to_string())] {
+            match DatabaseConfig::from_url(url) {
+                DatabaseConfig::Sqlite(url) => assert!(url.ends_with("data.db")),
+                other => panic!("expected the default SQLite file, got {:?}", other),
+            }
+        }
+    }
+
+    #[test]
+    fn provider_names_should_parse_case_insensitively() {
+        assert_eq!(
+            "DeepSeek".parse::<LlmProvider>().unwrap(),
+            LlmProvider::DeepSeek
+        );
+        assert_eq!(
+            "openai".parse::<LlmProvider>().unwrap(),
+            LlmProvider::OpenAI
+        );
+        assert_eq!(
+            "MOONSHOT".parse::<LlmProvider>().unwrap(),
+            LlmProvider::Moonshot
+        );
+        assert_eq!(
+            "ollama".parse::<LlmProvider>().unwrap(),
+            LlmProvider::Ollama
+        );
+        assert!("claude".parse::<LlmProvider>().is_err());
+    }
+
+    #[test]
+    fn llm_config_should_start_from_the_provider_preset() {
+        let config = LlmConfig::from_vars(vars(&[
+            ("LLM_PROVIDER", "openai"),
+            ("OPENAI_API_KEY", "sk-test"),
+        ]))
+        .unwrap();
+
+        assert_eq!(
+            config,
+            LlmConfig {
+                base_url: "https://api.openai.com/v1".to_string(),
+                model: "gpt-4o-mini".to_string(),
+                temperature: None,
+                api_key: Some("sk-test".to_string()),
+            }
+        );
+    }
+
+    #[test]
+    fn llm_config_should_apply_the_overrides() {
+        let config = LlmConfig::from_vars(vars(&[
+            ("LLM_PROVIDER", "ollama"),
+            ("LLM_BASE_URL", "http://gpu:11434/v1"),
+            ("LLM_MODEL", "qwen2.5"),
+            ("LLM_TEMPERATURE", "0.2"),
+        ]))
+        .unwrap();
+
+        assert_eq!(config.base_url, "http://gpu:11434/v1");
+        assert_eq!(config.model, "qwen2.5");
+        assert_eq!(config.temperature, Some(0.2));
+        assert_eq!(config.api_key, None);
+
+        let config = LlmConfig::from_vars(vars(&[
+            ("LLM_PROVIDER", "ollama"),
+            ("LLM_API_KEY_ENV", "GATEWAY_KEY"),
+            ("GATEWAY_KEY", "secret"),
+        ]))
+        .unwrap();
+        assert_eq!(config.api_key, Some("secret".to_string()));
+    }
+
+    #[test]
+    fn llm_config_should_reject_a_missing_key_or_bad_values() {
+        let missing_key = LlmConfig::from_vars(vars(&[])).unwrap_err();
+        assert_eq!(missing_key.to_string(), "DEEPSEEK_API_KEY is not set");
+
+        assert!(LlmConfig::from_vars(vars(&[("LLM_PROVIDER", "claude")])).is_err());
+        assert!(
+            LlmConfig::from_vars(vars(&[
+                ("LLM_PROVIDER", "ollama"),
+                ("LLM_TEMPERATURE", "warm"),
+            ]))
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn wip_policy_should_read_the_limits() {
+        assert_eq!(
+            WipPolicy::from_vars(vars(&[])).unwrap(),
+            WipPolicy::default()
+        );
+        assert_eq!(
+            WipPolicy::from_vars(vars(&[("WIP_MAX_PLANNING", "5"), ("WIP_MAX_ACTIVE", "1")]))
+                .unwrap(),
+            WipPolicy {
+                max_planning: 5,
+                max_active: 1,
+            }
+        );
+    }
+
+    #[test]
+    fn wip_policy_should_reject_invalid_limits() {
+        for (name, value) in [
+            ("WIP_MAX_PLANNING", "many"),
+            ("WIP_MAX_ACTIVE", "-1"),
+            ("WIP_MAX_ACTIVE", "2.5"),
+        ] {
+            let error = WipPolicy::from_vars(vars(&[(name, value)])).unwrap_err();
+            assert!(
+                error
+                    .to_string()
+                    .starts_with(&format!("Invalid {} {}", name, value)),
+                "{}",
+                error
+            );
+        }
+    }
+}
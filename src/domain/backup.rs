@@ -0,0 +1,246 @@
+use std::{
+    collections::HashMap,
+    fmt::{self, Write},
+};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::domain::models::{
+    Milestone, Record, RejectedAttempt, Review, SmartGoalTurn, Task, TaskArchive, TaskRevision,
+    TaskTransition,
+};
+
+/// Identifies a gewu backup document
+pub const BACKUP_FORMAT: &str = "gewu-backup";
+/// The layout version of the backup document, bumped on breaking changes
+pub const BACKUP_VERSION: u32 = 1;
+
+/// A self-describing snapshot of the whole learning database.
+/// Ids are the ones of the exported repository, they are remapped on import.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Backup {
+    pub format: String,
+    pub version: u32,
+    /// The gewu version that wrote the backup
+    pub generator: String,
+    pub exported_at: DateTime<Utc>,
+    pub tasks: Vec<TaskBackup>,
+    pub records: Vec<Record>,
+    pub rejected_attempts: Vec<RejectedAttempt>,
+}
+
+/// A task with everything that belongs to it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskBackup {
+    pub task: Task,
+    pub primer: Option<String>,
+    /// The ids of the linked records in [`Backup::records`]
+    pub record_ids: Vec<i64>,
+    pub transitions: Vec<TaskTransition>,
+    pub smart_goal_turns: Vec<SmartGoalTurn>,
+    pub milestones: Vec<Milestone>,
+    pub reviews: Vec<Review>,
+    pub revisions: Vec<TaskRevision>,
+    pub archive: Option<TaskArchive>,
+}
+
+impl Backup {
+    pub fn new(
+        tasks: Vec<TaskBackup>,
+        records: Vec<Record>,
+        rejected_attempts: Vec<RejectedAttempt>,
+    ) -> Self {
+        Self {
+            format: BACKUP_FORMAT.to_string(),
+            version: BACKUP_VERSION,
+            generator: format!("gewu {}", env!("CARGO_PKG_VERSION")),
+            exported_at: Utc::now(),
+            tasks,
+            records,
+            rejected_attempts,
+        }
+    }
+
+    /// Render the backup as a Markdown document meant to be read, not imported back
+    pub fn to_markdown(&self) -> String {
+        let mut md = String::new();
+        self.write_markdown(&mut md)
+            .expect("writing into a String never fails");
+        md
+    }
+
+    fn write_markdown(&self, md: &mut String) -> fmt::Result {
+        const TIME: &str = "%Y-%m-%d %H:%M";
+
+        writeln!(md, "# Learning backup\n")?;
+        writeln!(
+            md,
+            "> {} version {}, written by {} on {}\n",
+            self.format,
+            self.version,
+            self.generator,
+            self.exported_at.format(TIME)
+        )?;
+
+        for entry in &self.tasks {
+            let task = &entry.task;
+            writeln!(md, "## #{} {} ({})\n", task.id, task.topic, task.status)?;
+            if let Some(motivation) = &task.motivation {
+                writeln!(md, "**Motivation:** {}\n", motivation)?;
+            }
+            writeln!(md, "**Created:** {}\n", task.created_at.format(TIME))?;
+            if let Some(deadline) = task.deadline {
+                writeln!(md, "**Deadline:** {}\n", deadline)?;
+            }
+            if let Some(until) = task.paused_until {
+                writeln!(md, "**Paused until:** {}\n", until)?;
+            }
+
+            if let Some(goal) = &task.smart_goal {
+                writeln!(md, "### SMART goal\n")?;
+                for line in goal.to_string().lines() {
+                    writeln!(md, "- {}", line)?;
+                }
+                writeln!(md)?;
+            }
+
+            if !entry.milestones.is_empty() {
+                writeln!(md, "### Milestones\n")?;
+                for milestone in &entry.milestones {
+                    writeln!(
+                        md,
+                        "- [{}] {} by {}: {}",
+                        if milestone.is_completed() { "x" } else { " " },
+                        milestone.title,
+                        milestone.due_date,
+                        milestone.criterion
+                    )?;
+                }
+                writeln!(md)?;
+            }
+
+            let records: Vec<&Record> = entry
+                .record_ids
+                .iter()
+                .filter_map(|id| self.records.iter().find(|r| r.id == *id))
+                .collect();
+            if !records.is_empty() {
+                writeln!(md, "### Records\n")?;
+                for record in records {
+                    writeln!(
+                        md,
+                        "- {}: {}",
+                        record.created_at.format(TIME),
+                        record.content
+                    )?;
+                }
+                writeln!(md)?;
+            }
+
+            if !entry.reviews.is_empty() {
+                writeln!(md, "### Reviews\n")?;
+                for review in &entry.reviews {
+                    writeln!(
+                        md,
+                        "- [{}] {}",
+                        if review.is_passed { "x" } else { " " },
+                        review.question
+                    )?;
+                    if let Some(answer) = &review.user_answer {
+                        writeln!(md, "  - Answer: {}", answer)?;
+                    }
+                    if let Some(feedback) = &review.ai_feedback {
+                        writeln!(md, "  - Feedback: {}", feedback)?;
+                    }
+                }
+                writeln!(md)?;
+            }
+
+            if let Some(archive) = &entry.archive {
+                writeln!(md, "### Summary\n")?;
+                writeln!(md, "{}\n", archive.summary)?;
+                if !archive.tags.is_empty() {
+                    writeln!(md, "**Tags:** {}\n", archive.tags.join(", "))?;
+                }
+            }
+
+            if !entry.transitions.is_empty() {
+                writeln!(md, "### History\n")?;
+                for transition in &entry.transitions {
+                    write!(
+                        md,
+                        "- {}: {} → {}",
+                        transition.created_at.format(TIME),
+                        transition.from_status,
+                        transition.to_status
+                    )?;
+                    match &transition.reason {
+                        Some(reason) => writeln!(md, " ({})", reason)?,
+                        None => writeln!(md)?,
+                    }
+                }
+                writeln!(md)?;
+            }
+        }
+
+        if !self.rejected_attempts.is_empty() {
+            writeln!(md, "## Rejected attempts\n")?;
+            for attempt in &self.rejected_attempts {
+                writeln!(
+                    md,
+                    "- {} {}: {} (rejected: {})",
+                    attempt.created_at.format(TIME),
+                    attempt.topic,
+                    attempt.motivation,
+                    attempt.reason
+                )?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Fail unless the document is a backup this version of gewu can read
+    pub fn check_version(&self) -> Result<(), UnsupportedBackup> {
+        if self.format != BACKUP_FORMAT || self.version == 0 || self.version > BACKUP_VERSION {
+            return Err(UnsupportedBackup {
+                format: self.format.clone(),
+                version: self.version,
+            });
+        }
+        Ok(())
+    }
+}
+
+/// 无法读取的备份文件
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+#[error(
+    "Unsupported backup {format} version {version}, expected {BACKUP_FORMAT} up to version {BACKUP_VERSION}"
+)]
+pub struct UnsupportedBackup {
+    pub format: String,
+    pub version: u32,
+}
+
+/// 备份中的任务已经存在
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+#[error(
+    "{} task(s) of the backup already exist: {}. Use --skip-existing to import the rest",
+    .topics.len(),
+    .topics.join(", ")
+)]
+pub struct ImportConflict {
+    pub topics: Vec<String>,
+}
+
+/// What an import restored and what it left out.
+#[derive(Debug, Default, Serialize)]
+pub struct ImportReport {
+    pub tasks: usize,
+    pub records: usize,
+    pub rejected_attempts: usize,
+    /// Tasks already in the repository, by backup id, that were not imported again
+    pub skipped_tasks: Vec<i64>,
+    /// Records already in the repository, by backup id, linked instead of copied
+    pub existing_records: HashMap<i64, i64>,
+}
@@ -1,2 +1,4 @@
+pub mod backup;
 pub mod models;
+pub mod policy;
 pub mod state;
@@ -1,22 +1,249 @@
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
 
 use crate::domain::state::TaskStatus;
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Task {
     pub id: i64,
     pub topic: String,
     pub motivation: Option<String>,
-    pub smart_goal: Option<String>,
+    pub smart_goal: Option<SmartGoalDetail>,
+    pub deadline: Option<NaiveDate>,
+    /// When a paused task should be picked up again
+    pub paused_until: Option<NaiveDate>,
     pub status: TaskStatus,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+impl Task {
+    /// Days left until the deadline, negative once it has passed
+    pub fn days_remaining(&self, today: NaiveDate) -> Option<i64> {
+        self.deadline.map(|deadline| (deadline - today).num_days())
+    }
+
+    /// Still being planned or worked on after its deadline
+    pub fn is_overdue(&self, today: NaiveDate) -> bool {
+        matches!(self.status, TaskStatus::Planning | TaskStatus::Active)
+            && self.deadline.is_some_and(|deadline| deadline < today)
+    }
+
+    /// Paused and its pause date has come
+    pub fn is_due_to_resume(&self, today: NaiveDate) -> bool {
+        self.status == TaskStatus::Paused && self.paused_until.is_some_and(|until| until <= today)
+    }
+}
+
+/// SMART 目标的结构化分解
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SmartGoalDetail {
+    /// Specific - 具体的
+    pub specific: String,
+    /// Measurable - 可衡量的
+    pub measurable: String,
+    /// Achievable - 可实现的
+    pub achievable: String,
+    /// Relevant - 相关的
+    pub relevant: String,
+    /// Time-bound - 有时限的
+    pub time_bound: String,
+}
+
+impl std::fmt::Display for SmartGoalDetail {
+    /// One labelled line per dimension, the form the goal is edited in
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Specific: {}", self.specific)?;
+        writeln!(f, "Measurable: {}", self.measurable)?;
+        writeln!(f, "Achievable: {}", self.achievable)?;
+        writeln!(f, "Relevant: {}", self.relevant)?;
+        write!(f, "Time-bound: {}", self.time_bound)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Record {
     pub id: i64,
     pub content: String,
     pub created_at: DateTime<Utc>,
 }
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Review {
+    pub id: i64,
+    pub task_id: i64,
+    pub question: String,
+    pub user_answer: Option<String>,
+    pub ai_feedback: Option<String>,
+    pub is_passed: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskTransition {
+    pub id: i64,
+    pub task_id: i64,
+    pub from_status: TaskStatus,
+    pub to_status: TaskStatus,
+    pub reason: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// The summary a task is archived with once it is completed, a leaf of the skill tree.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskArchive {
+    pub task_id: i64,
+    pub topic: String,
+    pub summary: String,
+    pub tags: Vec<String>,
+    pub completed_at: DateTime<Utc>,
+}
+
+/// A discarded task and how far it got before it was abandoned.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GraveyardEntry {
+    pub task: Task,
+    pub reason: Option<String>,
+    /// The status the task was in when it was discarded
+    pub discarded_from: TaskStatus,
+    pub discarded_at: DateTime<Utc>,
+    pub records: usize,
+}
+
+/// A motivation the gatekeeper rejected, kept to spot repeated impulses.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RejectedAttempt {
+    pub id: i64,
+    pub topic: String,
+    pub motivation: String,
+    pub reason: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// An earlier try at a topic that never turned into learning, shown to the gatekeeper.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PastAttempt {
+    pub topic: String,
+    pub motivation: Option<String>,
+    pub outcome: AttemptOutcome,
+    pub reason: Option<String>,
+    pub at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum AttemptOutcome {
+    /// 动机被守门人拒绝
+    Rejected,
+    /// 任务创建后被放弃
+    Discarded,
+}
+
+/// One message of the SMART goal negotiation, kept as the task's planning transcript.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SmartGoalTurn {
+    pub id: i64,
+    pub task_id: i64,
+    pub role: TurnRole,
+    /// The learner's goal draft or question, or the Strategist's verdict as JSON
+    pub content: String,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, sqlx::Type)]
+#[sqlx(type_name = "TEXT", rename_all = "PascalCase")]
+pub enum TurnRole {
+    Learner,    // 学习者
+    Strategist, // 军师
+}
+
+/// A checkpoint on the way to the SMART goal, with the criterion that proves it is reached.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Milestone {
+    pub id: i64,
+    pub task_id: i64,
+    pub title: String,
+    pub criterion: String,
+    pub due_date: NaiveDate,
+    pub completed_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl Milestone {
+    pub fn is_completed(&self) -> bool {
+        self.completed_at.is_some()
+    }
+
+    /// Still open after its due date
+    pub fn is_overdue(&self, today: NaiveDate) -> bool {
+        !self.is_completed() && self.due_date < today
+    }
+}
+
+/// The value a task field had before it was edited.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskRevision {
+    pub id: i64,
+    pub task_id: i64,
+    pub field: TaskField,
+    /// The replaced value, the SMART goal is kept as JSON
+    pub previous: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, sqlx::Type)]
+#[sqlx(type_name = "TEXT", rename_all = "PascalCase")]
+pub enum TaskField {
+    Topic,      // 主题
+    Motivation, // 动机
+    SmartGoal,  // SMART 目标
+}
+
+impl std::fmt::Display for TaskField {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TaskField::Topic => write!(f, "Topic"),
+            TaskField::Motivation => write!(f, "Motivation"),
+            TaskField::SmartGoal => write!(f, "SMART goal"),
+        }
+    }
+}
+
+/// Marks the start of a matched word in a search snippet
+pub const HIGHLIGHT_START: &str = "«";
+/// Marks the end of a matched word in a search snippet
+pub const HIGHLIGHT_END: &str = "»";
+
+/// Narrows a full-text search down by task status and creation date.
+#[derive(Debug, Clone, Default)]
+pub struct SearchFilter {
+    /// Only tasks in these statuses and the records linked to them, any status when empty
+    pub statuses: Vec<TaskStatus>,
+    /// Created on or after this day (UTC)
+    pub from: Option<NaiveDate>,
+    /// Created on or before this day (UTC)
+    pub to: Option<NaiveDate>,
+}
+
+/// A task or learning record matching a full-text search.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SearchHit {
+    pub kind: SearchHitKind,
+    pub id: i64,
+    /// The topic of the task, or the topics of the tasks a record is linked to
+    pub title: Option<String>,
+    /// The matching text, every matched word is wrapped in [`HIGHLIGHT_START`] and [`HIGHLIGHT_END`]
+    pub snippet: String,
+    /// The status of the task, `None` for records
+    pub status: Option<TaskStatus>,
+    /// Higher is a better match, only comparable within one search
+    pub rank: f32,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, sqlx::Type)]
+#[sqlx(type_name = "TEXT", rename_all = "PascalCase")]
+pub enum SearchHitKind {
+    Task,   // 任务
+    Record, // 学习记录
+}
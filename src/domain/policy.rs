@@ -0,0 +1,89 @@
+use thiserror::Error;
+
+use crate::domain::state::TaskStatus;
+
+/// How many tasks may be in progress at once, so new ideas don't crowd out the current ones.
+/// Planning and Active are limited separately, the other statuses are not work in progress.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WipPolicy {
+    pub max_planning: usize,
+    pub max_active: usize,
+}
+
+/// 在制品数量已达上限
+#[derive(Debug, Clone, Error, PartialEq, Eq)]
+#[error("You can only have at most {limit} {status} tasks, pause or finish one to free a slot")]
+pub struct WipLimitReached {
+    pub status: TaskStatus,
+    pub limit: usize,
+}
+
+impl Default for WipPolicy {
+    fn default() -> Self {
+        Self {
+            max_planning: 3,
+            max_active: 3,
+        }
+    }
+}
+
+impl WipPolicy {
+    /// The limit of the status, `None` if it is not work in progress.
+    pub fn limit(&self, status: &TaskStatus) -> Option<usize> {
+        match status {
+            TaskStatus::Planning => Some(self.max_planning),
+            TaskStatus::Active => Some(self.max_active),
+            _ => None,
+        }
+    }
+
+    /// Check that one more task can enter the status, given how many are in it already.
+    pub fn check(&self, status: &TaskStatus, current: usize) -> Result<(), WipLimitReached> {
+        match self.limit(status) {
+            Some(limit) if current >= limit => Err(WipLimitReached {
+                status: status.clone(),
+                limit,
+            }),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_should_reject_once_the_limit_is_reached() {
+        let policy = WipPolicy {
+            max_planning: 1,
+            max_active: 2,
+        };
+
+        assert_eq!(policy.check(&TaskStatus::Planning, 0), Ok(()));
+        assert_eq!(
+            policy.check(&TaskStatus::Planning, 1),
+            Err(WipLimitReached {
+                status: TaskStatus::Planning,
+                limit: 1
+            })
+        );
+        assert_eq!(policy.check(&TaskStatus::Active, 1), Ok(()));
+        assert!(policy.check(&TaskStatus::Active, 2).is_err());
+    }
+
+    #[test]
+    fn statuses_outside_wip_should_have_no_limit() {
+        let policy = WipPolicy::default();
+
+        for status in [
+            TaskStatus::Reviewing,
+            TaskStatus::Completed,
+            TaskStatus::Discarded,
+            TaskStatus::Paused,
+        ] {
+            assert_eq!(policy.limit(&status), None);
+            assert_eq!(policy.check(&status, 100), Ok(()));
+        }
+    }
+}
@@ -1,7 +1,8 @@
-use std::fmt;
+use std::{fmt, str::FromStr};
 
 use serde::{Deserialize, Serialize};
 use sqlx::Type;
+use thiserror::Error;
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Type, Hash)]
 #[sqlx(type_name = "TEXT", rename_all = "PascalCase")]
@@ -11,6 +12,61 @@ pub enum TaskStatus {
     Reviewing, // 考核中
     Completed, // 完成
     Discarded, // 废弃
+    Paused,    // 暂停，不占用在制品名额
+}
+
+/// 非法的状态流转
+#[derive(Debug, Error, PartialEq, Eq)]
+#[error("Invalid state transition: Cannot move from {from} to {to}")]
+pub struct InvalidTransition {
+    pub from: TaskStatus,
+    pub to: TaskStatus,
+}
+
+/// 无法识别的任务状态
+#[derive(Debug, Error, PartialEq, Eq)]
+#[error("Unknown task status: {0}")]
+pub struct UnknownStatus(pub String);
+
+impl TaskStatus {
+    /// Every status, in lifecycle order
+    pub const ALL: [TaskStatus; 6] = [
+        TaskStatus::Planning,
+        TaskStatus::Active,
+        TaskStatus::Reviewing,
+        TaskStatus::Completed,
+        TaskStatus::Discarded,
+        TaskStatus::Paused,
+    ];
+
+    /// Whether a task in this status can move to the given status.
+    ///
+    /// Planning → Active → Reviewing → Completed, Reviewing → Active when the review fails,
+    /// Planning and Active tasks can be paused and resumed, and any status can be discarded.
+    pub fn can_transition_to(&self, to: &TaskStatus) -> bool {
+        use TaskStatus::*;
+
+        match (self, to) {
+            (Planning, Active) | (Active, Reviewing) | (Reviewing, Completed) => true,
+            (Reviewing, Active) => true,
+            (Planning | Active, Paused) | (Paused, Planning | Active) => true,
+            (Discarded, Discarded) => false,
+            (_, Discarded) => true,
+            _ => false,
+        }
+    }
+
+    /// Validate the transition to the given status, returns the new status if it is legal.
+    pub fn transition_to(&self, to: TaskStatus) -> Result<TaskStatus, InvalidTransition> {
+        if self.can_transition_to(&to) {
+            Ok(to)
+        } else {
+            Err(InvalidTransition {
+                from: self.clone(),
+                to,
+            })
+        }
+    }
 }
 
 impl fmt::Display for TaskStatus {
@@ -19,15 +75,112 @@ impl fmt::Display for TaskStatus {
     }
 }
 
-impl From<String> for TaskStatus {
-    fn from(value: String) -> Self {
+impl FromStr for TaskStatus {
+    type Err = UnknownStatus;
+
+    /// Parse a status name case-insensitively, e.g. `active` from the command line
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "planning" => Ok(Self::Planning),
+            "active" => Ok(Self::Active),
+            "reviewing" => Ok(Self::Reviewing),
+            "completed" => Ok(Self::Completed),
+            "discarded" => Ok(Self::Discarded),
+            "paused" => Ok(Self::Paused),
+            _ => Err(UnknownStatus(s.to_string())),
+        }
+    }
+}
+
+impl TryFrom<String> for TaskStatus {
+    type Error = UnknownStatus;
+
+    /// Convert a stored status name, as written by the repositories
+    fn try_from(value: String) -> Result<Self, Self::Error> {
         match value.as_str() {
-            "Planning" => Self::Planning,
-            "Active" => Self::Active,
-            "Reviewing" => Self::Reviewing,
-            "Completed" => Self::Completed,
-            "Discarded" => Self::Discarded,
-            _ => panic!("Invalid task status: {}", value),
+            "Planning" => Ok(Self::Planning),
+            "Active" => Ok(Self::Active),
+            "Reviewing" => Ok(Self::Reviewing),
+            "Completed" => Ok(Self::Completed),
+            "Discarded" => Ok(Self::Discarded),
+            "Paused" => Ok(Self::Paused),
+            _ => Err(UnknownStatus(value)),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn happy_path_transitions_should_be_legal() {
+        use TaskStatus::*;
+
+        assert_eq!(Planning.transition_to(Active), Ok(Active));
+        assert_eq!(Active.transition_to(Reviewing), Ok(Reviewing));
+        assert_eq!(Reviewing.transition_to(Completed), Ok(Completed));
+        assert_eq!(Reviewing.transition_to(Active), Ok(Active));
+    }
+
+    #[test]
+    fn planning_and_active_tasks_can_be_paused_and_resumed() {
+        use TaskStatus::*;
+
+        assert_eq!(Planning.transition_to(Paused), Ok(Paused));
+        assert_eq!(Active.transition_to(Paused), Ok(Paused));
+        assert_eq!(Paused.transition_to(Planning), Ok(Planning));
+        assert_eq!(Paused.transition_to(Active), Ok(Active));
+        assert!(!Reviewing.can_transition_to(&Paused));
+        assert!(!Paused.can_transition_to(&Reviewing));
+    }
+
+    #[test]
+    fn any_status_except_discarded_can_be_discarded() {
+        use TaskStatus::*;
+
+        for status in [Planning, Active, Reviewing, Completed, Paused] {
+            assert!(status.can_transition_to(&Discarded));
+        }
+        assert!(!Discarded.can_transition_to(&Discarded));
+    }
+
+    #[test]
+    fn illegal_transitions_should_be_rejected() {
+        use TaskStatus::*;
+
+        assert_eq!(
+            Active.transition_to(Active),
+            Err(InvalidTransition {
+                from: Active,
+                to: Active
+            })
+        );
+        assert!(!Planning.can_transition_to(&Reviewing));
+        assert!(!Active.can_transition_to(&Completed));
+        assert!(!Completed.can_transition_to(&Active));
+        assert!(!Discarded.can_transition_to(&Planning));
+    }
+
+    #[test]
+    fn status_names_should_parse_case_insensitively() {
+        assert_eq!("active".parse(), Ok(TaskStatus::Active));
+        assert_eq!("Paused".parse(), Ok(TaskStatus::Paused));
+        assert_eq!(
+            "done".parse::<TaskStatus>(),
+            Err(UnknownStatus("done".to_string()))
+        );
+    }
+
+    #[test]
+    fn unknown_stored_status_should_be_an_error() {
+        assert_eq!(
+            TaskStatus::try_from("Reviewing".to_string()),
+            Ok(TaskStatus::Reviewing)
+        );
+        assert_eq!(
+            TaskStatus::try_from("active".to_string()),
+            Err(UnknownStatus("active".to_string()))
+        );
+    }
+}
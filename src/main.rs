@@ -1,26 +1,62 @@
-use std::io::{self, Write};
+use std::{
+    io::{self, Write},
+    process::ExitCode,
+};
 
 use clap::Parser;
 use console::style;
-use dialoguer::Confirm;
+use dialoguer::{Editor, MultiSelect, Select};
+use serde_json::json;
 use sqlx::PgPool;
 
 use crate::{
-    adapters::{deepseek::DeepSeek, postgres_repo::PostgresRepo},
-    cli::{Gewu, Operation, ui::UI},
-    domain::state::TaskStatus,
-    services::manager::TaskManager,
+    adapters::{
+        memory_repo::MemoryRepo,
+        mock_llm::{MockLlm, Recorder},
+        openai_compatible::OpenAiCompatible,
+        postgres_repo::PostgresRepo,
+        sqlite_repo::SqliteRepo,
+    },
+    cli::{EditField, ExportFormat, Gewu, Operation, ui::UI},
+    config::{DatabaseConfig, LlmConfig, LlmMode},
+    domain::{
+        backup::Backup,
+        models::{SearchFilter, Task, TaskField},
+        policy::{WipLimitReached, WipPolicy},
+        state::TaskStatus,
+    },
+    ports::{
+        error::{GewuError, Verdict},
+        llm::LlmClient,
+        repository::Repository,
+    },
+    services::{
+        manager::{ReviewOutcome, TaskManager},
+        vault,
+    },
 };
 
 mod adapters;
 mod cli;
+mod config;
 mod domain;
 mod ports;
 mod services;
 
 /// Read a line from stdin with a green `> ` prompt.
-/// The text stays on screen exactly as the user typed it.
-fn read_input() -> anyhow::Result<String> {
+/// The text stays on screen exactly as the user typed it. Without a terminal it fails
+/// naming the option that gives the answer, if the command has one.
+fn read_input(ui: &UI, option: Option<&str>) -> anyhow::Result<String> {
+    if !ui.is_interactive() {
+        let message = match option {
+            Some(option) => format!(
+                "An answer is needed on the terminal, pass {} instead",
+                option
+            ),
+            None => "An answer is needed on the terminal, run the command in one".to_string(),
+        };
+        return Err(GewuError::invalid(message).into());
+    }
     print!("{} ", style(">").green().bold());
     io::stdout().flush()?;
 
@@ -28,295 +64,862 @@ fn read_input() -> anyhow::Result<String> {
     io::stdin().read_line(&mut input)?;
     let input = input.trim().to_string();
     if input.is_empty() {
-        anyhow::bail!("Input cannot be empty");
+        return Err(GewuError::invalid("Input cannot be empty").into());
     }
     Ok(input)
 }
 
+/// Ask a question with an optional hint below it and read the answer, which `option`
+/// gives without a terminal.
+fn ask(ui: &UI, question: &str, hint: Option<&str>, option: &str) -> anyhow::Result<String> {
+    if ui.is_interactive() {
+        println!("\n{}", style(question).cyan().bold());
+        if let Some(hint) = hint {
+            println!("{}", style(hint).cyan());
+        }
+    }
+    read_input(ui, Some(option))
+}
+
+/// Make sure one more task can enter the status. When the WIP limit is reached, offer to pause
+/// one of the tasks holding a slot. Returns whether there is room.
+async fn make_wip_slot<LLM: LlmClient, R: Repository>(
+    manager: &mut TaskManager<LLM, R>,
+    ui: &UI,
+    status: TaskStatus,
+) -> anyhow::Result<bool> {
+    match manager.ensure_wip_slot(&status).await {
+        Ok(()) => Ok(true),
+        Err(GewuError::WipLimitExceeded(limit)) => free_wip_slot(manager, ui, &limit).await,
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Offer to pause one of the tasks holding the slots of the limit. Returns whether one was paused.
+async fn free_wip_slot<LLM: LlmClient, R: Repository>(
+    manager: &mut TaskManager<LLM, R>,
+    ui: &UI,
+    limit: &WipLimitReached,
+) -> anyhow::Result<bool> {
+    // Picking the task to pause needs the terminal
+    if !ui.is_interactive() {
+        return Err(GewuError::from(limit.clone()).into());
+    }
+    ui.print_wip_limit_reached(limit);
+
+    let tasks = manager
+        .get_tasks_by_status(std::slice::from_ref(&limit.status))
+        .await?;
+    let mut items: Vec<String> = tasks
+        .iter()
+        .map(|t| format!("Pause #{} {}", t.id, t.topic))
+        .collect();
+    items.push("Keep them all".to_string());
+    let choice = Select::new()
+        .with_prompt(style("Pause a task to free a slot?").cyan().to_string())
+        .items(&items)
+        .default(items.len() - 1)
+        .interact()?;
+
+    match tasks.get(choice) {
+        None => Ok(false),
+        Some(task) => {
+            let today = chrono::Local::now().date_naive();
+            manager.pause_task(task.id, None, today).await?;
+            ui.print_task_paused(task.id);
+            Ok(true)
+        }
+    }
+}
+
+/// Ask whether to resume the paused tasks whose pause date has come.
+async fn prompt_resumptions<LLM: LlmClient, R: Repository>(
+    manager: &mut TaskManager<LLM, R>,
+    ui: &UI,
+) -> anyhow::Result<()> {
+    // Only a reminder, scripts are not stopped for it unless they answer yes
+    if !ui.is_interactive() && !ui.assumes_yes() {
+        return Ok(());
+    }
+    let today = chrono::Local::now().date_naive();
+    for task in manager.get_tasks_due_to_resume(today).await? {
+        ui.print_task_due_to_resume(&task);
+        let resume = ui.confirm("Resume it now?", true)?;
+        if !resume {
+            continue;
+        }
+
+        resume_task(manager, ui, task.id).await?;
+    }
+    Ok(())
+}
+
+/// Resume a paused task, offering to pause another one when its WIP limit is reached.
+async fn resume_task<LLM: LlmClient, R: Repository>(
+    manager: &mut TaskManager<LLM, R>,
+    ui: &UI,
+    id: i64,
+) -> anyhow::Result<()> {
+    let mut resumed = manager.resume_task(id).await;
+    if let Err(GewuError::WipLimitExceeded(limit)) = &resumed
+        && free_wip_slot(manager, ui, limit).await?
+    {
+        resumed = manager.resume_task(id).await;
+    }
+    let status = resumed?;
+    ui.print_task_resumed(id, &status);
+    ui.print_json(&json!({ "task": manager.get_task(id).await? }))?;
+    Ok(())
+}
+
+/// Match the record with the active tasks and let the user confirm or override the matches.
+async fn match_record<LLM: LlmClient, R: Repository>(
+    manager: &mut TaskManager<LLM, R>,
+    ui: &UI,
+    content: &str,
+) -> anyhow::Result<Vec<i64>> {
+    ui.print_matching_records();
+
+    let matched = manager.match_record_with_tasks(content).await?;
+    if matched.task_ids.is_empty() {
+        ui.print_no_matching_tasks();
+    } else {
+        ui.print_matched_tasks(&matched.task_ids);
+    }
+    if !matched.dropped.is_empty() {
+        ui.print_dropped_matches(&matched.dropped);
+    }
+
+    if ui.assumes_yes() {
+        Ok(matched.task_ids)
+    } else if ui.is_interactive() {
+        let items: Vec<String> = matched
+            .candidates
+            .iter()
+            .map(|t| format!("#{} {}", t.id, t.topic))
+            .collect();
+        let defaults: Vec<bool> = matched
+            .candidates
+            .iter()
+            .map(|t| matched.task_ids.contains(&t.id))
+            .collect();
+        let selected = MultiSelect::new()
+            .with_prompt(
+                style("Record this learning progress for (space to toggle)")
+                    .cyan()
+                    .to_string(),
+            )
+            .items(&items)
+            .defaults(&defaults)
+            .interact()?;
+        Ok(selected
+            .into_iter()
+            .map(|i| matched.candidates[i].id)
+            .collect())
+    } else {
+        Err(GewuError::invalid("Pass --tasks, or --yes to record it for the matched tasks").into())
+    }
+}
+
+/// Negotiate the SMART goal of a planning task with the strategist until the learner
+/// accepts a refined goal or gives up. Each message continues the same conversation.
+async fn negotiate_smart_goal<LLM: LlmClient, R: Repository>(
+    manager: &mut TaskManager<LLM, R>,
+    ui: &UI,
+    id: i64,
+    mut goal: Option<String>,
+) -> anyhow::Result<()> {
+    // The task becomes active once the goal is accepted, make room before negotiating
+    if !make_wip_slot(manager, ui, TaskStatus::Active).await? {
+        ui.print_plan_later(id);
+        return Ok(());
+    }
+
+    loop {
+        let message = match goal.take() {
+            Some(goal) => goal,
+            None => ask(
+                ui,
+                "What is your SMART goal? You can also ask the strategist a question.",
+                Some("(Specific, Measurable, Achievable, Relevant, Time-bound)"),
+                "--goal",
+            )?,
+        };
+
+        ui.print_checking_smart_goal();
+
+        let verdict = manager.negotiate_smart_goal(id, &message).await?;
+
+        // Without prompts no one can answer the strategist, the task stays in planning
+        let approved = verdict.passed && verdict.refined_goal.is_some();
+        if !approved && !ui.is_interactive() {
+            return Err(GewuError::Rejected {
+                verdict: Box::new(Verdict::SmartGoal {
+                    task_id: id,
+                    verdict,
+                }),
+            }
+            .into());
+        }
+
+        // Rejected or answered: show guidance and keep the conversation going
+        let Some(refined) = verdict.refined_goal.filter(|_| verdict.passed) else {
+            ui.print_smart_goal_rejected(&verdict.reason, &verdict.guidance.unwrap_or_default());
+            continue;
+        };
+
+        ui.print_smart_goal_approved(&verdict.reason, &refined, &verdict.milestones);
+
+        let confirmed = ui.confirm("Accept this refined SMART goal?", true)?;
+        if confirmed {
+            manager
+                .update_task_smart_goal(id, &refined, &verdict.milestones, verdict.deadline)
+                .await?;
+            ui.print_smart_goal_saved();
+            return Ok(());
+        }
+
+        let keep_going = ui.confirm("Keep negotiating with the strategist?", true)?;
+        if !keep_going {
+            ui.print_smart_goal_not_saved();
+            return Ok(());
+        }
+    }
+}
+
+/// Open the text in `$EDITOR`. Returns `None` when the editor is closed without saving
+/// or the text is left as it was.
+fn edit_text(ui: &UI, text: &str) -> anyhow::Result<Option<String>> {
+    if !ui.is_interactive() {
+        return Err(GewuError::invalid("Editing needs a terminal and table output").into());
+    }
+    let edited = Editor::new().edit(text)?;
+    Ok(edited
+        .map(|edited| edited.trim().to_string())
+        .filter(|edited| !edited.is_empty() && edited != text.trim()))
+}
+
+async fn edit_topic<LLM: LlmClient, R: Repository>(
+    manager: &mut TaskManager<LLM, R>,
+    ui: &UI,
+    task: &Task,
+) -> anyhow::Result<()> {
+    let Some(topic) = edit_text(ui, &task.topic)? else {
+        ui.print_edit_unchanged(task.id);
+        return Ok(());
+    };
+    manager.edit_task_topic(task.id, &topic).await?;
+    ui.print_task_edited(task.id, &TaskField::Topic);
+    Ok(())
+}
+
+/// Edit the motivation of a task, it is only replaced once the gatekeeper approves it.
+async fn edit_motivation<LLM: LlmClient, R: Repository>(
+    manager: &mut TaskManager<LLM, R>,
+    ui: &UI,
+    task: &Task,
+) -> anyhow::Result<()> {
+    let Some(motivation) = edit_text(ui, task.motivation.as_deref().unwrap_or_default())? else {
+        ui.print_edit_unchanged(task.id);
+        return Ok(());
+    };
+
+    ui.print_checking_motivation();
+    let verdict = manager.edit_task_motivation(task.id, &motivation).await?;
+    if !verdict.passed {
+        return Err(GewuError::Rejected {
+            verdict: Box::new(Verdict::Motivation(verdict)),
+        }
+        .into());
+    }
+    ui.print_task_edited(task.id, &TaskField::Motivation);
+    Ok(())
+}
+
+/// Edit the approved SMART goal of a task and send it to the strategist, until the learner
+/// accepts the refined goal or stops editing.
+async fn edit_smart_goal<LLM: LlmClient, R: Repository>(
+    manager: &mut TaskManager<LLM, R>,
+    ui: &UI,
+    task: &Task,
+) -> anyhow::Result<()> {
+    let Some(smart_goal) = &task.smart_goal else {
+        return Err(GewuError::status_conflict(format!(
+            "Task #{} has no SMART goal yet, run `gewu plan {}` first",
+            task.id, task.id
+        ))
+        .into());
+    };
+
+    let mut draft = smart_goal.to_string();
+    loop {
+        let Some(edited) = edit_text(ui, &draft)? else {
+            ui.print_edit_unchanged(task.id);
+            return Ok(());
+        };
+
+        ui.print_checking_smart_goal();
+        let verdict = manager.renegotiate_smart_goal(task.id, &edited).await?;
+
+        let Some(refined) = verdict.refined_goal.filter(|_| verdict.passed) else {
+            ui.print_smart_goal_rejected(&verdict.reason, &verdict.guidance.unwrap_or_default());
+            let again = ui.confirm("Edit the goal again?", true)?;
+            if !again {
+                ui.print_edit_unchanged(task.id);
+                return Ok(());
+            }
+            draft = edited;
+            continue;
+        };
+
+        // The milestones planned with the current goal are kept
+        ui.print_smart_goal_approved(&verdict.reason, &refined, &[]);
+        let confirmed = ui.confirm("Replace the SMART goal with this one?", true)?;
+        if confirmed {
+            manager
+                .amend_smart_goal(task.id, &refined, verdict.deadline)
+                .await?;
+            ui.print_task_edited(task.id, &TaskField::SmartGoal);
+        } else {
+            ui.print_edit_unchanged(task.id);
+        }
+        return Ok(());
+    }
+}
+
+/// Exit status when the gatekeeper or the strategist rejects the input
+const EXIT_REJECTED: u8 = 3;
+
 #[tokio::main]
-async fn main() -> anyhow::Result<()> {
+async fn main() -> ExitCode {
     dotenv::dotenv().ok();
 
-    let db_url = std::env::var("DATABASE_URL")?;
-    let pool = PgPool::connect(&db_url).await?;
-    let repo = PostgresRepo::new(pool);
-    let llm = DeepSeek::try_new(std::env::var("DEEPSEEK_API_KEY")?).await?;
-    let mut manager = TaskManager::new(llm, repo);
-
     let cli = Gewu::parse();
-    let ui = UI::new();
+    let ui = UI::new(cli.output, cli.yes).with_prompts(!cli.non_interactive);
+
+    match start(cli, &ui).await {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => report_error(&ui, e),
+    }
+}
+
+/// Show why the command failed with a hint on what to do about it, and pick the exit status.
+/// A rejection by the LLM shows its verdict instead of an error message.
+fn report_error(ui: &UI, e: anyhow::Error) -> ExitCode {
+    let Some(error) = e.downcast_ref::<GewuError>() else {
+        ui.print_error(format!("{:#}", e));
+        return ExitCode::FAILURE;
+    };
+    let hint = match error {
+        GewuError::Rejected { verdict } => {
+            match verdict.as_ref() {
+                Verdict::Motivation(verdict) => {
+                    ui.print_motivation_rejected(&verdict.reason, &verdict.recommendation)
+                }
+                Verdict::SmartGoal { task_id, verdict } => {
+                    ui.print_smart_goal_rejected(
+                        &verdict.reason,
+                        verdict.guidance.as_deref().unwrap_or_default(),
+                    );
+                    ui.print_goal_missing(*task_id);
+                }
+            }
+            ui.print_rejected(error, verdict);
+            return ExitCode::from(EXIT_REJECTED);
+        }
+        GewuError::NotFound { .. } => Some("Run `gewu list` to see the task ids"),
+        GewuError::WipLimitExceeded(_) => Some(
+            "Finish, pause or discard a task first, or raise the limit with WIP_MAX_PLANNING / WIP_MAX_ACTIVE",
+        ),
+        GewuError::LlmUnavailable(_) => Some(
+            "Check LLM_PROVIDER, its API key (e.g. DEEPSEEK_API_KEY, or the variable LLM_API_KEY_ENV names) and the network, then try again",
+        ),
+        GewuError::MalformedLlmResponse(_) => Some("Try again, or use another LLM_MODEL"),
+        GewuError::Storage(_) => {
+            Some("Check DATABASE_URL and that the database or the vault folder is reachable")
+        }
+        GewuError::InvalidTransition(_)
+        | GewuError::StatusConflict(_)
+        | GewuError::InvalidInput(_) => None,
+    };
+    ui.print_failure(error.kind(), error, hint);
+    ExitCode::from(exit_code(error))
+}
+
+/// The exit status of a failed command, documented in `gewu --help`
+fn exit_code(error: &GewuError) -> u8 {
+    match error {
+        GewuError::InvalidInput(_) => 1,
+        GewuError::Rejected { .. } => EXIT_REJECTED,
+        GewuError::NotFound { .. } => 4,
+        GewuError::InvalidTransition(_)
+        | GewuError::StatusConflict(_)
+        | GewuError::WipLimitExceeded(_) => 5,
+        GewuError::LlmUnavailable(_) | GewuError::MalformedLlmResponse(_) => 6,
+        GewuError::Storage(_) => 7,
+    }
+}
+
+/// The LLM client could not be set up, from its config or its cassette
+fn llm_unavailable(error: anyhow::Error) -> GewuError {
+    GewuError::LlmUnavailable(format!("{:#}", error))
+}
+
+fn live_llm() -> Result<OpenAiCompatible, GewuError> {
+    let config = LlmConfig::from_env().map_err(llm_unavailable)?;
+    OpenAiCompatible::try_new(config).map_err(llm_unavailable)
+}
+
+async fn start(cli: Gewu, ui: &UI) -> anyhow::Result<()> {
+    match LlmMode::from_env() {
+        LlmMode::Live => connect_and_run(live_llm()?, cli, ui).await,
+        LlmMode::Record(path) => {
+            let llm = Recorder::new(live_llm()?, path).map_err(llm_unavailable)?;
+            connect_and_run(llm, cli, ui).await
+        }
+        LlmMode::Replay(path) => {
+            let llm = MockLlm::resume_fixture(path).map_err(llm_unavailable)?;
+            connect_and_run(llm, cli, ui).await
+        }
+    }
+}
+
+async fn connect_and_run<LLM: LlmClient>(llm: LLM, cli: Gewu, ui: &UI) -> anyhow::Result<()> {
+    let wip = WipPolicy::from_env().map_err(|e| GewuError::invalid(format!("{:#}", e)))?;
+    match DatabaseConfig::from_env() {
+        DatabaseConfig::Postgres(url) => {
+            let repo = PostgresRepo::new(PgPool::connect(&url).await.map_err(GewuError::from)?);
+            run(TaskManager::new(llm, repo).with_wip_policy(wip), cli, ui).await
+        }
+        DatabaseConfig::Sqlite(url) => {
+            let repo = SqliteRepo::connect(&url)
+                .await
+                .map_err(GewuError::storage)?;
+            run(TaskManager::new(llm, repo).with_wip_policy(wip), cli, ui).await
+        }
+        DatabaseConfig::Memory => {
+            let repo = MemoryRepo::new();
+            run(TaskManager::new(llm, repo).with_wip_policy(wip), cli, ui).await
+        }
+    }
+}
 
+async fn run<LLM: LlmClient, R: Repository>(
+    mut manager: TaskManager<LLM, R>,
+    cli: Gewu,
+    ui: &UI,
+) -> anyhow::Result<()> {
     match cli.operation {
         Operation::Add(args) => {
-            // Only must 3 active tasks at most
-            let tasks = manager.get_tasks_by_status(&[TaskStatus::Active]).await?;
-            if tasks.len() >= 3 {
-                println!(
-                    "{}",
-                    style("You can only have at most 3 active tasks")
-                        .red()
-                        .bold()
-                );
+            prompt_resumptions(&mut manager, ui).await?;
+            if !make_wip_slot(&mut manager, ui, TaskStatus::Planning).await? {
                 return Ok(());
             }
 
             let topic = match args.topic {
                 Some(t) => t,
-                None => {
-                    println!("\n{}", style("What do you want to learn?").cyan().bold());
-                    read_input()?
-                }
+                None => ask(ui, "What do you want to learn?", None, "--topic")?,
             };
 
+            let attempts = manager.get_past_attempts(&topic).await?;
+            ui.print_past_attempts(&attempts);
+
             let motivation = match args.motivation {
                 Some(m) => m,
-                None => {
-                    println!(
-                        "\n{}\n{}",
-                        style(format!("Why do you want to learn \"{}\"?", topic))
-                            .cyan()
-                            .bold(),
-                        style("Please describe your specific motivation and use case:").cyan()
-                    );
-                    read_input()?
-                }
+                None => ask(
+                    ui,
+                    &format!("Why do you want to learn \"{}\"?", topic),
+                    Some("Please describe your specific motivation and use case:"),
+                    "--motivation",
+                )?,
             };
 
             ui.print_checking_motivation();
 
-            match manager.create_task(&topic, &motivation).await {
-                Err(e) => {
-                    if e.to_string() != "Motivation rejected" {
-                        eprintln!("\n{} {}", style("Error:").red().bold(), e);
-                    }
-                }
-                Ok(id) => {
-                    // Loop until user provides an approved SMART goal
-                    loop {
-                        println!(
-                            "\n{}\n{}",
-                            style("What is your SMART goal?").cyan().bold(),
-                            style("(Specific, Measurable, Achievable, Relevant, Time-bound)")
-                                .cyan()
-                        );
-                        let smart_goal = read_input()?;
-
-                        ui.print_checking_smart_goal();
-
-                        match manager.evaluate_smart_goal(id, &smart_goal).await {
-                            Err(e) => {
-                                eprintln!("\n{} {}", style("Error:").red().bold(), e);
-                                break;
-                            }
-                            Ok(verdict) => {
-                                if !verdict.passed {
-                                    // Rejected: show guidance and loop for re-entry
-                                    ui.print_smart_goal_rejected(
-                                        &verdict.reason,
-                                        &verdict.guidance.unwrap_or_default(),
-                                    );
-                                    continue;
-                                }
-
-                                // Approved: display the refined SMART goal table
-                                let refined = verdict.refined_goal.unwrap();
-                                ui.print_smart_goal_approved(&verdict.reason, &refined);
-
-                                // Ask for confirmation
-                                let confirmed = Confirm::new()
-                                    .with_prompt(
-                                        style("Accept this refined SMART goal?").cyan().to_string(),
-                                    )
-                                    .default(true)
-                                    .interact()?;
-
-                                if confirmed {
-                                    let goal_json = serde_json::to_string(&refined)?;
-                                    manager.update_task_smart_goal(id, &goal_json).await?;
-                                    ui.print_smart_goal_saved();
-                                } else {
-                                    ui.print_smart_goal_not_saved();
-                                }
-                                break;
-                            }
-                        }
-                    }
-                }
+            let (id, verdict) = manager.create_task(&topic, &motivation).await?;
+            ui.print_motivation_approved(&topic, &verdict.reason, &verdict.recommendation);
+            // Without a goal to propose, scripts plan it later
+            if ui.is_interactive() || args.goal.is_some() {
+                negotiate_smart_goal(&mut manager, ui, id, args.goal).await?;
+            } else {
+                ui.print_goal_missing(id);
             }
+            let task = manager.get_task(id).await?;
+            ui.print_json(&json!({ "task": task, "verdict": verdict }))?;
         }
         Operation::Describe(args) => {
-            let task = manager.get_task(args.id).await?;
-            if let Some(task) = task {
-                ui.print_task_detail(&task);
+            let task = manager.find_task(args.id).await?;
+            ui.print_task_detail(&task);
+            let milestones = manager.get_milestones(task.id).await?;
+            ui.print_milestones(&milestones);
+            let turns = manager.get_smart_goal_turns(task.id).await?;
+            ui.print_smart_goal_transcript(&turns);
+            let revisions = if args.history {
+                let revisions = manager.get_task_revisions(task.id).await?;
+                ui.print_task_revisions(&revisions);
+                Some(revisions)
             } else {
-                println!(
-                    "{}",
-                    style(format!("Task #{} not found", args.id)).red().bold()
-                );
+                None
+            };
+            let transitions = manager.get_task_transitions(task.id).await?;
+            ui.print_transition_list(&transitions);
+            ui.print_json(&json!({
+                "task": task,
+                "milestones": milestones,
+                "smart_goal_turns": turns,
+                "revisions": revisions,
+                "transitions": transitions,
+            }))?;
+        }
+        Operation::Edit(args) => {
+            let task = manager.find_task(args.id).await?;
+            ui.print_task_card(&task);
+
+            let field = match args.field {
+                Some(field) => field,
+                None if !ui.is_interactive() => {
+                    return Err(GewuError::invalid("Pass --field to choose what to edit").into());
+                }
+                None => {
+                    let fields = [EditField::Topic, EditField::Motivation, EditField::Goal];
+                    let selection = Select::new()
+                        .with_prompt(style("What do you want to edit?").cyan().to_string())
+                        .items(["Topic", "Motivation", "SMART goal"])
+                        .default(0)
+                        .interact()?;
+                    fields[selection]
+                }
+            };
+            match field {
+                EditField::Topic => edit_topic(&mut manager, ui, &task).await?,
+                EditField::Motivation => edit_motivation(&mut manager, ui, &task).await?,
+                EditField::Goal => edit_smart_goal(&mut manager, ui, &task).await?,
             }
         }
-        Operation::List => {
-            let tasks = manager
-                .get_tasks_by_status(&[TaskStatus::Planning, TaskStatus::Active])
-                .await?;
+        Operation::Search(args) => {
+            let query = args.query.join(" ");
+            let filter = SearchFilter {
+                statuses: args.status,
+                from: args.from,
+                to: args.to,
+            };
+            let hits = manager.search(&query, &filter, args.limit).await?;
+            ui.print_search_results(&query, &hits);
+            ui.print_json(&json!({ "query": query, "hits": hits }))?;
+        }
+        Operation::List(args) => {
+            prompt_resumptions(&mut manager, ui).await?;
+            let today = chrono::Local::now().date_naive();
+            let tasks = if args.overdue {
+                manager.get_overdue_tasks(today).await?
+            } else {
+                manager
+                    .get_tasks_by_status(&[
+                        TaskStatus::Planning,
+                        TaskStatus::Active,
+                        TaskStatus::Reviewing,
+                        TaskStatus::Paused,
+                    ])
+                    .await?
+            };
+            let candidates = manager.get_review_candidates(today).await?;
+            ui.print_json(&json!({ "tasks": tasks, "review_candidates": candidates }))?;
+
             ui.print_task_list(tasks);
+            ui.print_review_candidates(&candidates);
+        }
+        Operation::Pause(args) => {
+            let today = chrono::Local::now().date_naive();
+            manager.pause_task(args.id, args.until, today).await?;
+            ui.print_task_paused(args.id);
+            ui.print_json(&json!({ "task": manager.get_task(args.id).await? }))?;
+        }
+        Operation::Resume(args) => resume_task(&mut manager, ui, args.id).await?,
+        Operation::Deadline(args) => {
+            let deadline = if args.clear { None } else { args.date };
+            manager.set_task_deadline(args.id, deadline).await?;
+            let task = manager.find_task(args.id).await?;
+            ui.print_task_card(&task);
+            ui.print_json(&json!({ "task": task }))?;
         }
         Operation::Delete(args) => {
-            let task = manager.get_task(args.id).await?;
-            match task {
-                None => {
-                    println!(
-                        "{}",
-                        style(format!("Task #{} not found", args.id)).red().bold()
-                    );
-                }
-                Some(task) => {
-                    ui.print_task_card(&task);
-                    let confirmed = Confirm::new()
-                        .with_prompt(
-                            style("Are you sure you want to delete this task?")
-                                .cyan()
-                                .to_string(),
-                        )
-                        .default(false)
-                        .interact()?;
+            let task = manager.find_task(args.id).await?;
+            ui.print_task_card(&task);
+            if !args.purge {
+                ui.print_purge_required(task.id);
+                return Ok(());
+            }
 
-                    if confirmed {
-                        manager.delete_task(args.id).await?;
-                        println!(
-                            "{}",
-                            style(format!("Task #{} deleted", args.id)).green().bold()
-                        );
-                    }
-                }
+            let confirmed =
+                ui.confirm("Are you sure you want to delete this task for good?", false)?;
+
+            if confirmed {
+                manager.delete_task(args.id).await?;
+                ui.print_task_deleted(args.id);
+                ui.print_json(&json!({ "deleted": args.id }))?;
             }
         }
+        Operation::Discard(args) => {
+            let task = manager.find_task(args.id).await?;
+            ui.print_task_card(&task);
+
+            task.status
+                .transition_to(TaskStatus::Discarded)
+                .map_err(GewuError::from)?;
+
+            let reason = match args.reason {
+                Some(r) => r,
+                None => ask(ui, "Why are you abandoning this task?", None, "--reason")?,
+            };
+
+            manager.discard_task(task.id, &reason).await?;
+            ui.print_task_discarded(task.id);
+            ui.print_json(&json!({ "task": manager.get_task(task.id).await? }))?;
+        }
+        Operation::Graveyard => {
+            let entries = manager.get_graveyard().await?;
+            ui.print_graveyard(&entries);
+            ui.print_json(&json!({ "graveyard": entries }))?;
+        }
         Operation::Plan(args) => {
-            let task = manager.get_task(args.id).await?;
-            match task {
-                None => {
-                    println!(
-                        "{}",
-                        style(format!("Task #{} not found", args.id)).red().bold()
-                    );
-                }
-                Some(task) => {
-                    ui.print_task_card(&task);
-
-                    if task.status != TaskStatus::Planning {
-                        println!(
-                            "{}",
-                            style(format!("Task #{} is not in planning status", args.id))
-                                .red()
-                                .bold()
-                        );
-                        return Ok(());
-                    }
+            let task = manager.find_planning_task(args.id).await?;
+            ui.print_task_card(&task);
 
-                    loop {
-                        println!(
-                            "\n{}\n{}",
-                            style("What is your SMART goal?").cyan().bold(),
-                            style("(Specific, Measurable, Achievable, Relevant, Time-bound)")
-                                .cyan()
-                        );
-                        let smart_goal = read_input()?;
-
-                        ui.print_checking_smart_goal();
-
-                        match manager.evaluate_smart_goal(args.id, &smart_goal).await {
-                            Err(e) => {
-                                eprintln!("\n{} {}", style("Error:").red().bold(), e);
-                            }
-                            Ok(verdict) => {
-                                if !verdict.passed {
-                                    // Rejected: show guidance and loop for re-entry
-                                    ui.print_smart_goal_rejected(
-                                        &verdict.reason,
-                                        &verdict.guidance.unwrap_or_default(),
-                                    );
-                                }
-
-                                // Approved: display the refined SMART goal table
-                                let refined = verdict.refined_goal.unwrap();
-                                ui.print_smart_goal_approved(&verdict.reason, &refined);
-
-                                // Ask for confirmation
-                                let confirmed = Confirm::new()
-                                    .with_prompt(
-                                        style("Accept this refined SMART goal?").cyan().to_string(),
-                                    )
-                                    .default(true)
-                                    .interact()?;
-
-                                if confirmed {
-                                    let goal_json = serde_json::to_string(&refined)?;
-                                    manager.update_task_smart_goal(args.id, &goal_json).await?;
-                                    ui.print_smart_goal_saved();
-                                    break;
-                                }
-                            }
-                        }
-                    }
+            negotiate_smart_goal(&mut manager, ui, args.id, args.goal).await?;
+            ui.print_json(&json!({ "task": manager.get_task(args.id).await? }))?;
+        }
+        Operation::Milestones(args) => {
+            let task = manager.find_task(args.id).await?;
+            ui.print_task_card(&task);
+
+            let milestones = manager.get_milestones(task.id).await?;
+            if milestones.is_empty() {
+                ui.print_no_milestones(task.id);
+                return Ok(());
+            }
+            ui.print_milestones(&milestones);
+            ui.print_json(&json!({ "task": task, "milestones": milestones }))?;
+
+            // Checking milestones off needs the terminal and an active task,
+            // otherwise they are only listed
+            if !ui.is_interactive() || task.status != TaskStatus::Active {
+                return Ok(());
+            }
+
+            let items: Vec<String> = milestones
+                .iter()
+                .map(|m| format!("{} (due {})", m.title, m.due_date))
+                .collect();
+            let defaults: Vec<bool> = milestones.iter().map(|m| m.is_completed()).collect();
+            let checked = MultiSelect::new()
+                .with_prompt(
+                    style("Check off the reached milestones (space to toggle)")
+                        .cyan()
+                        .to_string(),
+                )
+                .items(&items)
+                .defaults(&defaults)
+                .interact()?;
+
+            let mut changed = false;
+            for (i, milestone) in milestones.iter().enumerate() {
+                let completed = checked.contains(&i);
+                if completed != milestone.is_completed() {
+                    manager
+                        .set_milestone_completed(task.id, milestone.id, completed)
+                        .await?;
+                    changed = true;
                 }
             }
+            if changed {
+                let milestones = manager.get_milestones(task.id).await?;
+                ui.print_milestones(&milestones);
+            }
         }
         Operation::Record(args) => {
             let tasks = manager.get_tasks_by_status(&[TaskStatus::Active]).await?;
             if tasks.is_empty() {
-                println!("{}", style("You have no active tasks").red().bold());
-                return Ok(());
+                return Err(GewuError::invalid("You have no active tasks").into());
             }
             for task in &tasks {
                 ui.print_task_card(task);
             }
 
-            ui.print_matching_records();
+            let task_ids = if args.tasks.is_empty() {
+                match_record(&mut manager, ui, &args.content).await?
+            } else {
+                args.tasks
+            };
 
-            let task_ids = manager.match_record_with_tasks(&args.content).await?;
             if task_ids.is_empty() {
-                ui.print_no_matching_tasks();
+                ui.print_record_skipped();
+                ui.print_json(&json!({ "record": null, "task_ids": task_ids }))?;
                 return Ok(());
             }
+            let record = manager
+                .record_learning_progress(&task_ids, &args.content)
+                .await?;
+            ui.print_record_success();
+            ui.print_json(&json!({ "record": record, "task_ids": task_ids }))?;
+        }
+        Operation::Learn(args) => {
+            let task = manager.find_task(args.id).await?;
+            ui.print_task_card(&task);
 
-            ui.print_matched_tasks(&task_ids);
+            let stored = if args.refresh {
+                None
+            } else {
+                manager.get_task_primer(task.id).await?
+            };
+            let (primer, was_stored) = match stored {
+                Some(primer) => {
+                    ui.print_primer(&primer);
+                    ui.print_primer_footer(task.id, true);
+                    (primer, true)
+                }
+                None => {
+                    ui.print_primer_header();
+                    let mut rx = manager.generate_primer_stream(&task).await?;
+                    let primer = ui.print_guide_streaming(&mut rx).await;
+                    manager.save_task_primer(task.id, &primer).await?;
+                    ui.print_primer_footer(task.id, false);
+                    (primer, false)
+                }
+            };
+            ui.print_json(&json!({ "task_id": task.id, "primer": primer, "stored": was_stored }))?;
+        }
+        Operation::Guide(args) => {
+            let task = manager.find_task(args.id).await?;
+            ui.print_task_card(&task);
 
-            let confirmed = Confirm::new()
-                .with_prompt(style("Record this learning progress?").cyan().to_string())
-                .default(true)
-                .interact()?;
+            let records = manager.get_task_records(task.id).await?;
+            ui.print_record_list(&records);
 
-            if confirmed {
-                manager
-                    .record_learning_progress(&task_ids, &args.content)
-                    .await?;
-                ui.print_record_success();
-            }
+            ui.print_guide_header();
+
+            let mut rx = manager.generate_guide_stream(&task, &records).await?;
+            let guide = ui.print_guide_streaming(&mut rx).await;
+
+            ui.print_guide_footer();
+            ui.print_json(&json!({ "task_id": task.id, "guide": guide }))?;
         }
-        Operation::Guide(args) => {
-            let task = manager.get_task(args.id).await?;
-            match task {
-                None => {
-                    println!(
-                        "{}",
-                        style(format!("Task #{} not found", args.id)).red().bold()
-                    );
+        Operation::Commit(args) => {
+            let task = manager.find_task(args.id).await?;
+            ui.print_task_card(&task);
+
+            task.status
+                .transition_to(TaskStatus::Reviewing)
+                .map_err(GewuError::from)?;
+
+            let summary = match args.summary {
+                Some(s) => s,
+                None => ask(
+                    ui,
+                    "What have you learned?",
+                    Some("Summarize the key ideas in your own words:"),
+                    "--summary",
+                )?,
+            };
+
+            ui.print_generating_questions();
+
+            let reviews = manager.commit_task(task.id, &summary).await?;
+            ui.print_review_questions(&reviews);
+            ui.print_json(&json!({ "task_id": task.id, "reviews": reviews }))?;
+        }
+        Operation::Review(args) => {
+            let task = manager.find_task(args.id).await?;
+            ui.print_task_card(&task);
+
+            task.status
+                .transition_to(TaskStatus::Completed)
+                .map_err(GewuError::from)?;
+
+            let reviews = manager.get_task_reviews(task.id).await?;
+            for (i, review) in reviews.iter().enumerate() {
+                // Passed questions do not need to be answered again
+                if review.is_passed {
+                    continue;
                 }
-                Some(task) => {
-                    ui.print_task_card(&task);
 
-                    let records = manager.get_task_records(task.id).await?;
-                    ui.print_record_list(&records);
+                ui.print_question(i + 1, reviews.len(), &review.question);
+                let answer = read_input(ui, None)?;
 
-                    ui.print_guide_header();
+                ui.print_scoring_answer();
 
-                    let mut rx = manager.generate_guide_stream(&task, &records).await?;
-                    ui.print_guide_streaming(&mut rx).await;
+                let verdict = manager.answer_review(&task, review, &answer).await?;
+                if verdict.passed {
+                    ui.print_answer_passed(&verdict.feedback);
+                } else {
+                    ui.print_answer_failed(&verdict.feedback);
+                }
+            }
 
-                    ui.print_guide_footer();
+            match manager.finish_review(task.id).await? {
+                ReviewOutcome::Completed(archive) => ui.print_review_completed(&archive),
+                ReviewOutcome::Incomplete { failed } => {
+                    ui.print_review_incomplete(task.id, failed);
+
+                    let back_to_study = ui.confirm(
+                        "Go back to studying before retrying? (Status → Active)",
+                        false,
+                    )?;
+
+                    if back_to_study && make_wip_slot(&mut manager, ui, TaskStatus::Active).await? {
+                        manager
+                            .transition_task(task.id, TaskStatus::Active, "Review failed")
+                            .await?;
+                        ui.print_back_to_active(task.id);
+                    }
+                }
+            }
+        }
+        Operation::Skills => {
+            let archives = manager.get_task_archives().await?;
+            ui.print_skill_tree(&archives);
+            ui.print_json(&json!({ "skills": archives }))?;
+        }
+        Operation::Export(args) => {
+            let backup = manager.export_backup().await?;
+            let content = match args.format {
+                ExportFormat::Json => serde_json::to_string_pretty(&backup)?,
+                ExportFormat::Markdown => backup.to_markdown(),
+            };
+            match args.path {
+                Some(path) => {
+                    std::fs::write(&path, content)?;
+                    ui.print_exported(&path, &backup);
+                    ui.print_json(&json!({
+                        "path": path,
+                        "tasks": backup.tasks.len(),
+                        "records": backup.records.len(),
+                    }))?;
                 }
+                None => println!("{}", content),
             }
         }
+        Operation::Import(args) => {
+            let content = std::fs::read_to_string(&args.path)?;
+            let backup: Backup = match serde_json::from_str(&content) {
+                Ok(backup) => backup,
+                Err(e) => {
+                    return Err(GewuError::invalid(format!(
+                        "{} is not a JSON backup written by `gewu export`: {}",
+                        args.path.display(),
+                        e
+                    ))
+                    .into());
+                }
+            };
+            let report = manager.import_backup(&backup, args.skip_existing).await?;
+            ui.print_import_report(&report);
+            ui.print_json(&report)?;
+        }
+        Operation::Sync(args) => {
+            let report = vault::sync_vault(&mut manager, &args.vault, args.force).await?;
+            ui.print_sync_report(&report);
+            ui.print_json(&report)?;
+        }
     }
     Ok(())
 }
@@ -0,0 +1,120 @@
+use std::fmt;
+
+use serde::Serialize;
+
+use crate::{
+    domain::{
+        backup::{ImportConflict, UnsupportedBackup},
+        policy::WipLimitReached,
+        state::InvalidTransition,
+    },
+    ports::llm::{GatekeeperVerdict, SmartGoalVerdict},
+};
+
+pub type Result<T, E = GewuError> = std::result::Result<T, E>;
+
+/// gewu 的领域错误，由 `TaskManager` 和各个端口返回，命令行据此选择退出码和提示
+#[derive(Debug, thiserror::Error)]
+pub enum GewuError {
+    /// 任务、记录或里程碑不存在
+    #[error("{kind} #{id} not found")]
+    NotFound { kind: &'static str, id: i64 },
+    /// 守门人或军师否决了输入
+    #[error("{verdict}")]
+    Rejected { verdict: Box<Verdict> },
+    /// 非法的状态流转
+    #[error(transparent)]
+    InvalidTransition(#[from] InvalidTransition),
+    /// 在制品名额已满
+    #[error(transparent)]
+    WipLimitExceeded(#[from] WipLimitReached),
+    /// 任务不在该操作要求的状态
+    #[error("{0}")]
+    StatusConflict(String),
+    /// 输入不合法
+    #[error("{0}")]
+    InvalidInput(String),
+    /// 大模型无法访问
+    #[error("The LLM is unavailable: {0}")]
+    LlmUnavailable(String),
+    /// 大模型的回复无法使用
+    #[error("The LLM returned a malformed response: {0}")]
+    MalformedLlmResponse(String),
+    /// 存储读写失败
+    #[error("Storage error: {0}")]
+    Storage(#[source] Box<dyn std::error::Error + Send + Sync>),
+}
+
+impl GewuError {
+    pub fn task_not_found(id: i64) -> Self {
+        Self::NotFound { kind: "Task", id }
+    }
+
+    pub fn invalid(message: impl Into<String>) -> Self {
+        Self::InvalidInput(message.into())
+    }
+
+    pub fn status_conflict(message: impl Into<String>) -> Self {
+        Self::StatusConflict(message.into())
+    }
+
+    pub fn storage(error: impl Into<Box<dyn std::error::Error + Send + Sync>>) -> Self {
+        Self::Storage(error.into())
+    }
+
+    /// A stable name of the variant for scripts reading the error
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::NotFound { .. } => "not_found",
+            Self::Rejected { .. } => "rejected",
+            Self::InvalidTransition(_) => "invalid_transition",
+            Self::WipLimitExceeded(_) => "wip_limit_exceeded",
+            Self::StatusConflict(_) => "status_conflict",
+            Self::InvalidInput(_) => "invalid_input",
+            Self::LlmUnavailable(_) => "llm_unavailable",
+            Self::MalformedLlmResponse(_) => "malformed_llm_response",
+            Self::Storage(_) => "storage",
+        }
+    }
+}
+
+impl From<sqlx::Error> for GewuError {
+    fn from(error: sqlx::Error) -> Self {
+        Self::storage(error)
+    }
+}
+
+impl From<UnsupportedBackup> for GewuError {
+    fn from(error: UnsupportedBackup) -> Self {
+        Self::invalid(error.to_string())
+    }
+}
+
+impl From<ImportConflict> for GewuError {
+    fn from(error: ImportConflict) -> Self {
+        Self::invalid(error.to_string())
+    }
+}
+
+/// 被否决时大模型给出的结论
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Verdict {
+    Motivation(GatekeeperVerdict),
+    SmartGoal {
+        task_id: i64,
+        #[serde(flatten)]
+        verdict: SmartGoalVerdict,
+    },
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Motivation(_) => write!(f, "Motivation rejected"),
+            Self::SmartGoal { task_id, .. } => {
+                write!(f, "SMART goal of task #{} rejected", task_id)
+            }
+        }
+    }
+}
@@ -1,11 +1,14 @@
-use anyhow::Result;
 use async_trait::async_trait;
+use chrono::NaiveDate;
 use serde::{Deserialize, Serialize};
 
-use crate::domain::models::{Record, Task};
+use crate::{
+    domain::models::{PastAttempt, Record, Review, SmartGoalDetail, SmartGoalTurn, Task},
+    ports::error::Result,
+};
 
 /// 审核动机的结果，包含是否通过以及原因说明
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct GatekeeperVerdict {
     /// 是否通过审核
     pub passed: bool,
@@ -15,23 +18,8 @@ pub struct GatekeeperVerdict {
     pub recommendation: String,
 }
 
-/// SMART 目标的结构化分解
-#[derive(Debug, Serialize, Deserialize, Clone)]
-pub struct SmartGoalDetail {
-    /// Specific - 具体的
-    pub specific: String,
-    /// Measurable - 可衡量的
-    pub measurable: String,
-    /// Achievable - 可实现的
-    pub achievable: String,
-    /// Relevant - 相关的
-    pub relevant: String,
-    /// Time-bound - 有时限的
-    pub time_bound: String,
-}
-
 /// 评估 SMART 目标的结果
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct SmartGoalVerdict {
     /// 是否通过评估
     pub passed: bool,
@@ -43,6 +31,23 @@ pub struct SmartGoalVerdict {
     /// 优化后的结构化 SMART 目标（通过时提供）
     #[serde(default)]
     pub refined_goal: Option<SmartGoalDetail>,
+    /// 拆分出的里程碑（通过时提供）
+    #[serde(default)]
+    pub milestones: Vec<MilestonePlan>,
+    /// 整个目标的截止日期（通过时提供）
+    #[serde(default)]
+    pub deadline: Option<NaiveDate>,
+}
+
+/// 军师建议的里程碑
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MilestonePlan {
+    /// 里程碑名称
+    pub title: String,
+    /// 截止日期
+    pub due_date: NaiveDate,
+    /// 可衡量的完成标准
+    pub criterion: String,
 }
 
 /// 匹配任务的结果
@@ -52,22 +57,50 @@ pub struct MatchTasksResult {
     pub task_ids: Vec<i64>,
 }
 
+/// 生成考题的结果
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GenerateQuestionsResult {
+    /// 情境考题
+    pub questions: Vec<String>,
+}
+
+/// 考题评分的结果
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AnswerVerdict {
+    /// 是否通过
+    pub passed: bool,
+    /// 评分反馈
+    pub feedback: String,
+}
+
+/// 结业总结的结果
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TaskSummary {
+    /// 学习成果总结
+    pub summary: String,
+    /// 技能标签，用于归入技能树
+    #[serde(default)]
+    pub tags: Vec<String>,
+}
+
 /// AI 客户端接口，定义了所有 AI 交互的抽象方法
 #[async_trait]
 pub trait LlmClient: Send + Sync {
-    /// 守门人模式：审核动机
+    /// 守门人模式：审核动机，参考相似主题的历史尝试识别重复的冲动
     async fn audit_motivation(
         &mut self,
         topic: &str,
         motivation: &str,
+        history: &[PastAttempt],
     ) -> Result<GatekeeperVerdict>;
 
-    /// 军师模式：评估并优化 SMART 目标
-    async fn evaluate_smart_goal(
+    /// 军师模式：多轮协商 SMART 目标，`transcript` 为此前的对话，`message` 为学习者的新目标或提问
+    async fn negotiate_smart_goal(
         &mut self,
         topic: &str,
         motivation: &str,
-        goal: &str,
+        transcript: &[SmartGoalTurn],
+        message: &str,
     ) -> Result<SmartGoalVerdict>;
 
     /// 匹配任务：将学习记录与任务进行匹配
@@ -80,23 +113,28 @@ pub trait LlmClient: Send + Sync {
         records: &[Record],
     ) -> Result<tokio::sync::mpsc::Receiver<String>>;
 
-    // /// 考官模式：生成考题
-    // async fn generate_questions(&self, goal: &str) -> Result<Vec<String>>;
+    /// 夫子模式：流式生成第一性原理的预习摘要
+    async fn generate_primer_stream(
+        &mut self,
+        task: &Task,
+    ) -> Result<tokio::sync::mpsc::Receiver<String>>;
+
+    /// 考官模式：根据学习总结生成考题
+    async fn generate_questions(&mut self, task: &Task, summary: &str) -> Result<Vec<String>>;
 
-    // /// 考官模式：评分
-    // async fn score_answer(&self, question: &str, answer: &str) -> Result<f32>;
-}
+    /// 考官模式：评分
+    async fn score_answer(
+        &mut self,
+        task: &Task,
+        question: &str,
+        answer: &str,
+    ) -> Result<AnswerVerdict>;
 
-impl GatekeeperVerdict {
-    pub fn print_passed(&self, topic: &str) {
-        use crate::cli::ui::UI;
-        let ui = UI::new();
-        ui.print_motivation_approved(topic, &self.reason, &self.recommendation);
-    }
-
-    pub fn print_rejected(&self) {
-        use crate::cli::ui::UI;
-        let ui = UI::new();
-        ui.print_motivation_rejected(&self.reason, &self.recommendation);
-    }
+    /// 考官模式：根据学习记录和考核结果生成结业总结
+    async fn summarize_task(
+        &mut self,
+        task: &Task,
+        records: &[Record],
+        reviews: &[Review],
+    ) -> Result<TaskSummary>;
 }
@@ -1,9 +1,18 @@
-use anyhow::Result;
+use std::collections::HashMap;
+
 use async_trait::async_trait;
+use chrono::NaiveDate;
 
-use crate::domain::{
-    models::{Record, Task},
-    state::TaskStatus,
+use crate::{
+    domain::{
+        backup::Backup,
+        models::{
+            Milestone, Record, RejectedAttempt, Review, SearchFilter, SearchHit, SmartGoalDetail,
+            SmartGoalTurn, Task, TaskArchive, TaskField, TaskRevision, TaskTransition, TurnRole,
+        },
+        state::TaskStatus,
+    },
+    ports::{error::Result, llm::MilestonePlan},
 };
 
 #[async_trait]
@@ -11,8 +20,88 @@ pub trait Repository: Send + Sync {
     /// Create a new task
     async fn create_task(&self, topic: &str, motivation: &str) -> Result<Task>;
 
+    /// Update the topic of a task
+    async fn update_task_topic(&self, id: i64, topic: &str) -> Result<()>;
+
+    /// Update the motivation of a task
+    async fn update_task_motivation(&self, id: i64, motivation: &str) -> Result<()>;
+
     /// Update the smart goal of a task
-    async fn update_task_smart_goal(&self, id: i64, smart_goal: &str) -> Result<()>;
+    async fn update_task_smart_goal(&self, id: i64, smart_goal: &SmartGoalDetail) -> Result<()>;
+
+    /// Save the approved SMART goal of a task with its milestones and deadline and move it from
+    /// `from` to Active, all or nothing. Without a deadline the current one is kept.
+    /// Fails if the task is no longer in the `from` status.
+    async fn approve_smart_goal(
+        &self,
+        id: i64,
+        from: TaskStatus,
+        smart_goal: &SmartGoalDetail,
+        milestones: &[MilestonePlan],
+        deadline: Option<NaiveDate>,
+        reason: &str,
+    ) -> Result<()>;
+
+    /// Set or clear the deadline of a task
+    async fn update_task_deadline(&self, id: i64, deadline: Option<NaiveDate>) -> Result<()>;
+
+    /// Move a task from `from` to `to` and set or clear the date it should be resumed, all or
+    /// nothing. Fails if the task is no longer in the `from` status.
+    async fn update_task_pause(
+        &self,
+        id: i64,
+        from: TaskStatus,
+        to: TaskStatus,
+        paused_until: Option<NaiveDate>,
+        reason: &str,
+    ) -> Result<()>;
+
+    /// Keep the value a task field had before it was edited
+    async fn create_task_revision(
+        &self,
+        task_id: i64,
+        field: TaskField,
+        previous: Option<&str>,
+    ) -> Result<TaskRevision>;
+
+    /// Get the edits of a task, oldest first
+    async fn get_task_revisions(&self, task_id: i64) -> Result<Vec<TaskRevision>>;
+
+    /// Append a message to the SMART goal negotiation of a task
+    async fn create_smart_goal_turn(
+        &self,
+        task_id: i64,
+        role: TurnRole,
+        content: &str,
+    ) -> Result<SmartGoalTurn>;
+
+    /// Get the SMART goal negotiation of a task, oldest first
+    async fn get_smart_goal_turns(&self, task_id: i64) -> Result<Vec<SmartGoalTurn>>;
+
+    /// Get the milestones of a task, earliest due first
+    async fn get_milestones(&self, task_id: i64) -> Result<Vec<Milestone>>;
+
+    /// Check a milestone off, or reopen it
+    async fn update_milestone_completed(&self, id: i64, completed: bool) -> Result<()>;
+
+    /// Get the first-principles primer stored on a task
+    async fn get_task_primer(&self, id: i64) -> Result<Option<String>>;
+
+    /// Store the primer of a task, replacing any previous one
+    async fn update_task_primer(&self, id: i64, primer: &str) -> Result<()>;
+
+    /// Move a task from one status to another and record the transition.
+    /// Fails if the task is no longer in the `from` status.
+    async fn update_task_status(
+        &self,
+        id: i64,
+        from: TaskStatus,
+        to: TaskStatus,
+        reason: Option<&str>,
+    ) -> Result<()>;
+
+    /// Get the status transitions of the given task, oldest first
+    async fn get_task_transitions(&self, task_id: i64) -> Result<Vec<TaskTransition>>;
 
     /// Get a task by id
     async fn get_task(&self, id: i64) -> Result<Option<Task>>;
@@ -20,15 +109,81 @@ pub trait Repository: Send + Sync {
     /// Get tasks by status, supports multiple statuses
     async fn get_tasks_by_status(&self, status: &[TaskStatus]) -> Result<Vec<Task>>;
 
+    /// Full-text search over the topic, motivation and SMART goal of the tasks and the
+    /// content of the learning records, best match first
+    async fn search(
+        &self,
+        query: &str,
+        filter: &SearchFilter,
+        limit: i64,
+    ) -> Result<Vec<SearchHit>>;
+
     /// Delete a task by id
     async fn delete_task(&self, id: i64) -> Result<()>;
 
     /// Create a new learning record
     async fn create_record(&self, content: &str) -> Result<Record>;
 
+    /// Get every learning record, oldest first
+    async fn get_records(&self) -> Result<Vec<Record>>;
+
     /// Create a new task record
     async fn create_task_record(&self, task_id: i64, record_id: i64) -> Result<()>;
 
     /// Get the learning records for the given task
     async fn get_task_records(&self, task_id: i64) -> Result<Vec<Record>>;
+
+    /// Get the review questions of the given task
+    async fn get_task_reviews(&self, task_id: i64) -> Result<Vec<Review>>;
+
+    /// Save the user's answer to a review question along with the AI feedback
+    async fn update_review_answer(
+        &self,
+        id: i64,
+        answer: &str,
+        feedback: &str,
+        passed: bool,
+    ) -> Result<()>;
+
+    /// Save the learning summary as a record of the task, replace its review questions and move
+    /// it from `from` to Reviewing, all or nothing. Fails if the task is no longer in the `from`
+    /// status.
+    async fn submit_summary(
+        &self,
+        task_id: i64,
+        from: TaskStatus,
+        summary: &str,
+        questions: &[String],
+        reason: &str,
+    ) -> Result<Vec<Review>>;
+
+    /// Archive a task with its completion summary and move it from `from` to Completed,
+    /// all or nothing. Fails if the task is no longer in the `from` status.
+    async fn complete_task(
+        &self,
+        task_id: i64,
+        from: TaskStatus,
+        summary: &str,
+        tags: &[String],
+        reason: &str,
+    ) -> Result<TaskArchive>;
+
+    /// Get every archived task, most recently completed first
+    async fn get_task_archives(&self) -> Result<Vec<TaskArchive>>;
+
+    /// Save a motivation the gatekeeper rejected
+    async fn create_rejected_attempt(
+        &self,
+        topic: &str,
+        motivation: &str,
+        reason: &str,
+    ) -> Result<RejectedAttempt>;
+
+    /// Get the rejected motivations, most recent first
+    async fn get_rejected_attempts(&self) -> Result<Vec<RejectedAttempt>>;
+
+    /// Insert the tasks, records and rejected attempts of a backup with new ids, keeping their
+    /// timestamps, all or nothing. Records in `existing_records`, by backup id, are already
+    /// stored and only linked.
+    async fn restore(&self, backup: &Backup, existing_records: &HashMap<i64, i64>) -> Result<()>;
 }
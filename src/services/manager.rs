@@ -1,72 +1,522 @@
 use crate::{
     domain::{
-        models::{Record, Task},
+        backup::{Backup, ImportConflict, ImportReport, TaskBackup},
+        models::{
+            AttemptOutcome, GraveyardEntry, Milestone, PastAttempt, Record, RejectedAttempt,
+            Review, SearchFilter, SearchHit, SmartGoalDetail, SmartGoalTurn, Task, TaskArchive,
+            TaskField, TaskRevision, TaskTransition, TurnRole,
+        },
+        policy::WipPolicy,
         state::TaskStatus,
     },
     ports::{
-        llm::{LlmClient, SmartGoalVerdict},
+        error::{GewuError, Result, Verdict},
+        llm::{AnswerVerdict, GatekeeperVerdict, LlmClient, MilestonePlan, SmartGoalVerdict},
         repository::Repository,
     },
 };
-use anyhow::Result;
+use chrono::{DateTime, NaiveDate, Utc};
+use std::collections::{HashMap, HashSet};
+
+/// How many earlier attempts the gatekeeper is shown at most
+const MAX_PAST_ATTEMPTS: usize = 10;
+
+/// Words too common in topics to tell two of them apart
+const TOPIC_STOP_WORDS: &[&str] = &[
+    "a",
+    "an",
+    "and",
+    "the",
+    "of",
+    "to",
+    "in",
+    "on",
+    "for",
+    "with",
+    "how",
+    "learn",
+    "learning",
+    "basics",
+    "intro",
+    "introduction",
+    "学习",
+    "入门",
+    "基础",
+];
+
+/// The keywords of a topic used to spot attempts on the same subject.
+/// Latin words are kept whole, CJK text has no spaces so it is split into bigrams.
+fn topic_keywords(topic: &str) -> HashSet<String> {
+    let topic = topic.to_lowercase();
+    let mut keywords = HashSet::new();
+
+    for word in topic.split(|c: char| !c.is_alphanumeric()) {
+        // Split mixed words like "学习kubernetes" into their latin and CJK runs
+        let mut runs: Vec<(bool, String)> = Vec::new();
+        for c in word.chars() {
+            match runs.last_mut() {
+                Some((ascii, run)) if *ascii == c.is_ascii() => run.push(c),
+                _ => runs.push((c.is_ascii(), c.to_string())),
+            }
+        }
+
+        for (ascii, run) in runs {
+            let chars: Vec<char> = run.chars().collect();
+            let candidates: Vec<String> = if ascii || chars.len() < 2 {
+                vec![run]
+            } else {
+                chars.windows(2).map(|w| w.iter().collect()).collect()
+            };
+            keywords.extend(
+                candidates
+                    .into_iter()
+                    .filter(|k| !TOPIC_STOP_WORDS.contains(&k.as_str())),
+            );
+        }
+    }
+
+    keywords
+}
+
+/// Whether two timestamps are the same instant, as precise as every backend stores them
+fn same_instant(a: DateTime<Utc>, b: DateTime<Utc>) -> bool {
+    a.timestamp_micros() == b.timestamp_micros()
+}
+
+/// The active tasks a learning record was matched with.
+#[derive(Debug)]
+pub struct RecordMatch {
+    /// The active tasks the record can be linked to
+    pub candidates: Vec<Task>,
+    /// Matched ids that belong to an active task
+    pub task_ids: Vec<i64>,
+    /// Ids returned by the LLM that are not active tasks
+    pub dropped: Vec<i64>,
+}
+
+/// The result of a review round.
+#[derive(Debug)]
+pub enum ReviewOutcome {
+    /// Every question is passed, the task is archived and completed
+    Completed(TaskArchive),
+    /// Some questions still have to be passed
+    Incomplete { failed: usize },
+}
 
 /// The manager of the task.
 pub struct TaskManager<LLM: LlmClient, R: Repository> {
     pub llm: LLM,
     pub repo: R,
+    pub wip: WipPolicy,
 }
 
 impl<LLM: LlmClient, R: Repository> TaskManager<LLM, R> {
     pub fn new(llm: LLM, repo: R) -> Self {
-        Self { llm, repo }
+        Self {
+            llm,
+            repo,
+            wip: WipPolicy::default(),
+        }
+    }
+
+    pub fn with_wip_policy(mut self, wip: WipPolicy) -> Self {
+        self.wip = wip;
+        self
     }
 
-    /// Create a new learning task.
-    pub async fn create_task(&mut self, topic: &str, motivation: &str) -> Result<i64> {
+    /// Fail with [`WipLimitReached`] when no more tasks may enter the status.
+    pub async fn ensure_wip_slot(&self, status: &TaskStatus) -> Result<()> {
+        if self.wip.limit(status).is_none() {
+            return Ok(());
+        }
+        let current = self
+            .repo
+            .get_tasks_by_status(std::slice::from_ref(status))
+            .await?
+            .len();
+        self.wip.check(status, current)?;
+        Ok(())
+    }
+
+    /// Create a new learning task once the gatekeeper approves the motivation, returns its id
+    /// with the verdict. A rejection fails with [`GewuError::Rejected`] and is kept on record.
+    pub async fn create_task(
+        &mut self,
+        topic: &str,
+        motivation: &str,
+    ) -> Result<(i64, GatekeeperVerdict)> {
         if topic.trim().is_empty() {
-            anyhow::bail!("Topic cannot be empty");
+            return Err(GewuError::invalid("Topic cannot be empty"));
         }
+        self.ensure_wip_slot(&TaskStatus::Planning).await?;
 
-        let verdict = self.llm.audit_motivation(topic, motivation).await?;
+        let history = self.get_past_attempts(topic).await?;
+        let verdict = self
+            .llm
+            .audit_motivation(topic, motivation, &history)
+            .await?;
         if !verdict.passed {
-            verdict.print_rejected();
-            anyhow::bail!("Motivation rejected");
+            self.repo
+                .create_rejected_attempt(topic, motivation, &verdict.reason)
+                .await?;
+            return Err(GewuError::Rejected {
+                verdict: Box::new(Verdict::Motivation(verdict)),
+            });
         }
 
         let task = self.repo.create_task(topic, motivation).await?;
-        verdict.print_passed(topic);
-        Ok(task.id)
+        Ok((task.id, verdict))
     }
 
-    /// Evaluate the user's SMART goal against the topic and motivation.
-    pub async fn evaluate_smart_goal(
+    /// Send the learner's draft goal or question to the strategist, continuing the earlier
+    /// conversation of the task. Both sides of the turn are stored.
+    pub async fn negotiate_smart_goal(
         &mut self,
         id: i64,
-        smart_goal: &str,
+        message: &str,
     ) -> Result<SmartGoalVerdict> {
-        let task = self.repo.get_task(id).await?;
-        match task {
-            None => anyhow::bail!("Task #{} not found", id),
-            Some(task) => {
-                let verdict = self
-                    .llm
-                    .evaluate_smart_goal(
-                        &task.topic,
-                        &task.motivation.unwrap_or_default(),
-                        smart_goal,
-                    )
-                    .await?;
-                Ok(verdict)
-            }
+        let task = self.find_planning_task(id).await?;
+        self.ask_strategist(&task, message).await
+    }
+
+    /// Send an amended SMART goal of an already planned task to the strategist, continuing
+    /// the planning conversation.
+    pub async fn renegotiate_smart_goal(
+        &mut self,
+        id: i64,
+        message: &str,
+    ) -> Result<SmartGoalVerdict> {
+        let task = self.find_editable_task(id).await?;
+        if task.smart_goal.is_none() {
+            return Err(GewuError::status_conflict(format!(
+                "Task #{} has no SMART goal yet, plan it first",
+                id
+            )));
         }
+        self.ask_strategist(&task, message).await
     }
 
-    /// Update the smart goal of a task.
-    pub async fn update_task_smart_goal(&mut self, id: i64, smart_goal: &str) -> Result<()> {
+    async fn ask_strategist(&mut self, task: &Task, message: &str) -> Result<SmartGoalVerdict> {
+        if message.trim().is_empty() {
+            return Err(GewuError::invalid("Message cannot be empty"));
+        }
+
+        let transcript = self.repo.get_smart_goal_turns(task.id).await?;
+        let verdict = self
+            .llm
+            .negotiate_smart_goal(
+                &task.topic,
+                task.motivation.as_deref().unwrap_or_default(),
+                &transcript,
+                message,
+            )
+            .await?;
+
+        self.repo
+            .create_smart_goal_turn(task.id, TurnRole::Learner, message)
+            .await?;
+        self.repo
+            .create_smart_goal_turn(
+                task.id,
+                TurnRole::Strategist,
+                &serde_json::to_string(&verdict).map_err(GewuError::storage)?,
+            )
+            .await?;
+        Ok(verdict)
+    }
+
+    /// The SMART goal conversation of a task, oldest turn first.
+    pub async fn get_smart_goal_turns(&self, task_id: i64) -> Result<Vec<SmartGoalTurn>> {
+        self.repo.get_smart_goal_turns(task_id).await
+    }
+
+    /// Save the approved smart goal of a planning task with its milestones and activate it.
+    /// Without an explicit deadline the last milestone's due date is used.
+    pub async fn update_task_smart_goal(
+        &mut self,
+        id: i64,
+        smart_goal: &SmartGoalDetail,
+        milestones: &[MilestonePlan],
+        deadline: Option<NaiveDate>,
+    ) -> Result<()> {
+        let task = self.find_planning_task(id).await?;
+        self.ensure_wip_slot(&TaskStatus::Active).await?;
+
+        let deadline = deadline.or_else(|| milestones.iter().map(|m| m.due_date).max());
+        self.repo
+            .approve_smart_goal(
+                id,
+                task.status,
+                smart_goal,
+                milestones,
+                deadline,
+                "SMART goal approved",
+            )
+            .await
+    }
+
+    /// Replace the approved SMART goal of a task, keeping the previous one in its history.
+    /// The milestones planned with it are left as they are.
+    pub async fn amend_smart_goal(
+        &mut self,
+        id: i64,
+        smart_goal: &SmartGoalDetail,
+        deadline: Option<NaiveDate>,
+    ) -> Result<()> {
+        let task = self.find_editable_task(id).await?;
+        let Some(previous) = &task.smart_goal else {
+            return Err(GewuError::status_conflict(format!(
+                "Task #{} has no SMART goal yet, plan it first",
+                id
+            )));
+        };
+
+        self.repo
+            .create_task_revision(
+                id,
+                TaskField::SmartGoal,
+                Some(&serde_json::to_string(previous).map_err(GewuError::storage)?),
+            )
+            .await?;
         self.repo.update_task_smart_goal(id, smart_goal).await?;
+        if deadline.is_some() {
+            self.repo.update_task_deadline(id, deadline).await?;
+        }
         Ok(())
     }
 
+    /// Fix the topic of a task, keeping the previous one in its history.
+    pub async fn edit_task_topic(&mut self, id: i64, topic: &str) -> Result<()> {
+        let topic = topic.trim();
+        if topic.is_empty() {
+            return Err(GewuError::invalid("Topic cannot be empty"));
+        }
+        let task = self.find_editable_task(id).await?;
+        if task.topic == topic {
+            return Err(GewuError::invalid(format!(
+                "The topic of task #{} is unchanged",
+                id
+            )));
+        }
+
+        self.repo
+            .create_task_revision(id, TaskField::Topic, Some(&task.topic))
+            .await?;
+        self.repo.update_task_topic(id, topic).await
+    }
+
+    /// Have the gatekeeper audit a new motivation for a task. It replaces the current one
+    /// only when approved, the previous one is kept in the task's history.
+    pub async fn edit_task_motivation(
+        &mut self,
+        id: i64,
+        motivation: &str,
+    ) -> Result<GatekeeperVerdict> {
+        let motivation = motivation.trim();
+        if motivation.is_empty() {
+            return Err(GewuError::invalid("Motivation cannot be empty"));
+        }
+        let task = self.find_editable_task(id).await?;
+        if task.motivation.as_deref() == Some(motivation) {
+            return Err(GewuError::invalid(format!(
+                "The motivation of task #{} is unchanged",
+                id
+            )));
+        }
+
+        let history = self.get_past_attempts(&task.topic).await?;
+        let verdict = self
+            .llm
+            .audit_motivation(&task.topic, motivation, &history)
+            .await?;
+        if verdict.passed {
+            self.repo
+                .create_task_revision(id, TaskField::Motivation, task.motivation.as_deref())
+                .await?;
+            self.repo.update_task_motivation(id, motivation).await?;
+        }
+        Ok(verdict)
+    }
+
+    /// The earlier values of the edited fields of a task, oldest edit first.
+    pub async fn get_task_revisions(&self, task_id: i64) -> Result<Vec<TaskRevision>> {
+        self.repo.get_task_revisions(task_id).await
+    }
+
+    /// Move the deadline of a task that is not finished yet, or clear it.
+    pub async fn set_task_deadline(&mut self, id: i64, deadline: Option<NaiveDate>) -> Result<()> {
+        self.find_editable_task(id).await?;
+        self.repo.update_task_deadline(id, deadline).await
+    }
+
+    /// Planning and active tasks past their deadline, most overdue first.
+    pub async fn get_overdue_tasks(&self, today: NaiveDate) -> Result<Vec<Task>> {
+        let mut tasks: Vec<Task> = self
+            .repo
+            .get_tasks_by_status(&[TaskStatus::Planning, TaskStatus::Active])
+            .await?
+            .into_iter()
+            .filter(|task| task.is_overdue(today))
+            .collect();
+        tasks.sort_by_key(|task| task.deadline);
+        Ok(tasks)
+    }
+
+    /// Active tasks past their deadline, which should be wrapped up with a review.
+    pub async fn get_review_candidates(&self, today: NaiveDate) -> Result<Vec<Task>> {
+        let mut tasks = self.get_overdue_tasks(today).await?;
+        tasks.retain(|task| task.status == TaskStatus::Active);
+        Ok(tasks)
+    }
+
+    /// Move a task to the given status, rejecting illegal transitions and the ones exceeding
+    /// the WIP policy.
+    pub async fn transition_task(&mut self, id: i64, to: TaskStatus, reason: &str) -> Result<()> {
+        let task = self.find_task(id).await?;
+        let to = task.status.transition_to(to)?;
+        self.ensure_wip_slot(&to).await?;
+        self.repo
+            .update_task_status(id, task.status, to, Some(reason))
+            .await
+    }
+
+    /// Park a planning or active task so it no longer takes a WIP slot, optionally until the
+    /// given date when the learner is asked to resume it. The date must be after today.
+    pub async fn pause_task(
+        &mut self,
+        id: i64,
+        until: Option<NaiveDate>,
+        today: NaiveDate,
+    ) -> Result<()> {
+        if let Some(until) = until
+            && until <= today
+        {
+            return Err(GewuError::invalid(format!(
+                "Pause until a date after today, {} has already come",
+                until
+            )));
+        }
+        let task = self.find_task(id).await?;
+        let to = task.status.transition_to(TaskStatus::Paused)?;
+        let reason = match until {
+            Some(until) => format!("Paused until {}", until),
+            None => "Paused".to_string(),
+        };
+        self.repo
+            .update_task_pause(id, task.status, to, until, &reason)
+            .await
+    }
+
+    /// Paused tasks whose pause date has come, earliest first.
+    pub async fn get_tasks_due_to_resume(&self, today: NaiveDate) -> Result<Vec<Task>> {
+        let mut tasks: Vec<Task> = self
+            .repo
+            .get_tasks_by_status(&[TaskStatus::Paused])
+            .await?
+            .into_iter()
+            .filter(|task| task.is_due_to_resume(today))
+            .collect();
+        tasks.sort_by_key(|task| task.paused_until);
+        Ok(tasks)
+    }
+
+    /// Bring a paused task back to where it was: active once it has a SMART goal,
+    /// planning otherwise. Returns the status it is resumed to.
+    pub async fn resume_task(&mut self, id: i64) -> Result<TaskStatus> {
+        let task = self.find_task(id).await?;
+        if task.status != TaskStatus::Paused {
+            return Err(GewuError::status_conflict(format!(
+                "Task #{} is not paused",
+                id
+            )));
+        }
+        let to = if task.smart_goal.is_some() {
+            TaskStatus::Active
+        } else {
+            TaskStatus::Planning
+        };
+        task.status.transition_to(to.clone())?;
+        self.ensure_wip_slot(&to).await?;
+        self.repo
+            .update_task_pause(id, task.status, to.clone(), None, "Resumed")
+            .await?;
+        Ok(to)
+    }
+
+    /// Earlier rejected motivations and discarded tasks on topics similar to the given one,
+    /// most recent first
+    pub async fn get_past_attempts(&self, topic: &str) -> Result<Vec<PastAttempt>> {
+        let keywords = topic_keywords(topic);
+        let is_similar = |other: &str| !keywords.is_disjoint(&topic_keywords(other));
+
+        let mut attempts: Vec<PastAttempt> = self
+            .repo
+            .get_rejected_attempts()
+            .await?
+            .into_iter()
+            .filter(|a| is_similar(&a.topic))
+            .map(|a| PastAttempt {
+                topic: a.topic,
+                motivation: Some(a.motivation),
+                outcome: AttemptOutcome::Rejected,
+                reason: Some(a.reason),
+                at: a.created_at,
+            })
+            .collect();
+        for entry in self.get_graveyard().await? {
+            if is_similar(&entry.task.topic) {
+                attempts.push(PastAttempt {
+                    topic: entry.task.topic,
+                    motivation: entry.task.motivation,
+                    outcome: AttemptOutcome::Discarded,
+                    reason: entry.reason,
+                    at: entry.discarded_at,
+                });
+            }
+        }
+
+        attempts.sort_by_key(|a| std::cmp::Reverse(a.at));
+        attempts.truncate(MAX_PAST_ATTEMPTS);
+        Ok(attempts)
+    }
+
+    /// Abandon a task, it is kept with its records and the reason in the graveyard
+    pub async fn discard_task(&mut self, id: i64, reason: &str) -> Result<()> {
+        let reason = reason.trim();
+        if reason.is_empty() {
+            return Err(GewuError::invalid("A reason is required to discard a task"));
+        }
+        self.transition_task(id, TaskStatus::Discarded, reason)
+            .await
+    }
+
+    /// Get the discarded tasks with why and when they were abandoned, most recent first
+    pub async fn get_graveyard(&self) -> Result<Vec<GraveyardEntry>> {
+        let tasks = self.get_tasks_by_status(&[TaskStatus::Discarded]).await?;
+        let mut entries = Vec::with_capacity(tasks.len());
+        for task in tasks {
+            let transitions = self.repo.get_task_transitions(task.id).await?;
+            let discard = transitions
+                .iter()
+                .rev()
+                .find(|t| t.to_status == TaskStatus::Discarded);
+            let records = self.repo.get_task_records(task.id).await?.len();
+            entries.push(GraveyardEntry {
+                reason: discard.and_then(|t| t.reason.clone()),
+                discarded_from: discard.map_or(TaskStatus::Planning, |t| t.from_status.clone()),
+                discarded_at: discard.map_or(task.updated_at, |t| t.created_at),
+                records,
+                task,
+            });
+        }
+        Ok(entries)
+    }
+
+    /// Get the status transitions of the given task, oldest first
+    pub async fn get_task_transitions(&self, task_id: i64) -> Result<Vec<TaskTransition>> {
+        self.repo.get_task_transitions(task_id).await
+    }
+
     /// Get a task by id
     pub async fn get_task(&self, id: i64) -> Result<Option<Task>> {
         let task = self.repo.get_task(id).await?;
@@ -79,25 +529,96 @@ impl<LLM: LlmClient, R: Repository> TaskManager<LLM, R> {
         Ok(tasks)
     }
 
-    /// Delete a task by id
+    /// Permanently delete a task with its links, reviews and history.
+    /// Prefer `discard_task` to keep it in the graveyard
     pub async fn delete_task(&mut self, id: i64) -> Result<()> {
         self.repo.delete_task(id).await?;
         Ok(())
     }
 
-    /// Match the learning record with the tasks
-    pub async fn match_record_with_tasks(&mut self, record: &str) -> Result<Vec<i64>> {
-        let tasks = self.get_tasks_by_status(&[TaskStatus::Active]).await?;
-        self.llm.match_tasks(&tasks, record).await
+    /// Match the learning record with the active tasks,
+    /// ids the LLM made up or that aren't active are dropped
+    pub async fn match_record_with_tasks(&mut self, record: &str) -> Result<RecordMatch> {
+        let candidates = self.get_tasks_by_status(&[TaskStatus::Active]).await?;
+        if candidates.is_empty() {
+            return Ok(RecordMatch {
+                candidates,
+                task_ids: vec![],
+                dropped: vec![],
+            });
+        }
+
+        let mut task_ids = Vec::new();
+        let mut dropped = Vec::new();
+        for id in self.llm.match_tasks(&candidates, record).await? {
+            let ids = if candidates.iter().any(|t| t.id == id) {
+                &mut task_ids
+            } else {
+                &mut dropped
+            };
+            if !ids.contains(&id) {
+                ids.push(id);
+            }
+        }
+
+        Ok(RecordMatch {
+            candidates,
+            task_ids,
+            dropped,
+        })
     }
 
-    /// Record the learning progress for the given tasks
-    pub async fn record_learning_progress(&mut self, task_ids: &[i64], record: &str) -> Result<()> {
+    /// Record the learning progress for the given active tasks
+    pub async fn record_learning_progress(
+        &mut self,
+        task_ids: &[i64],
+        record: &str,
+    ) -> Result<Record> {
+        if task_ids.is_empty() {
+            return Err(GewuError::invalid("No task selected for the record"));
+        }
+        let active = self.get_tasks_by_status(&[TaskStatus::Active]).await?;
+        if let Some(id) = task_ids
+            .iter()
+            .find(|id| !active.iter().any(|t| t.id == **id))
+        {
+            return Err(GewuError::status_conflict(format!(
+                "Task #{} is not active",
+                id
+            )));
+        }
+
         let record = self.repo.create_record(record).await?;
+        let mut linked = Vec::with_capacity(task_ids.len());
         for task_id in task_ids {
-            self.repo.create_task_record(*task_id, record.id).await?;
+            if !linked.contains(task_id) {
+                self.repo.create_task_record(*task_id, record.id).await?;
+                linked.push(*task_id);
+            }
         }
-        Ok(())
+        Ok(record)
+    }
+
+    /// Full-text search over the tasks and the learning records, best match first
+    pub async fn search(
+        &self,
+        query: &str,
+        filter: &SearchFilter,
+        limit: i64,
+    ) -> Result<Vec<SearchHit>> {
+        let query = query.trim();
+        if query.is_empty() {
+            return Err(GewuError::invalid("Query cannot be empty"));
+        }
+        if let (Some(from), Some(to)) = (filter.from, filter.to)
+            && from > to
+        {
+            return Err(GewuError::invalid(format!(
+                "The search range ends on {} before it starts on {}",
+                to, from
+            )));
+        }
+        self.repo.search(query, filter, limit).await
     }
 
     /// Get the learning records for the given task
@@ -113,4 +634,1162 @@ impl<LLM: LlmClient, R: Repository> TaskManager<LLM, R> {
     ) -> Result<tokio::sync::mpsc::Receiver<String>> {
         self.llm.generate_guide_stream(task, records).await
     }
+
+    /// Get the primer stored on the given task, if one was generated before
+    pub async fn get_task_primer(&self, id: i64) -> Result<Option<String>> {
+        self.repo.get_task_primer(id).await
+    }
+
+    /// Stream generate a first-principles primer for a task with an approved SMART goal
+    pub async fn generate_primer_stream(
+        &mut self,
+        task: &Task,
+    ) -> Result<tokio::sync::mpsc::Receiver<String>> {
+        if !matches!(task.status, TaskStatus::Active | TaskStatus::Reviewing) {
+            return Err(GewuError::status_conflict(format!(
+                "Task #{} is in {} status, only active tasks can be learned",
+                task.id, task.status
+            )));
+        }
+        self.llm.generate_primer_stream(task).await
+    }
+
+    /// Store the primer of the given task so it can be re-read without the LLM
+    pub async fn save_task_primer(&self, id: i64, primer: &str) -> Result<()> {
+        if primer.trim().is_empty() {
+            return Err(GewuError::MalformedLlmResponse(
+                "The primer is empty".to_string(),
+            ));
+        }
+        self.repo.update_task_primer(id, primer).await
+    }
+
+    /// Submit a learning summary for an active task, generate the review questions
+    /// and move the task into review
+    pub async fn commit_task(&mut self, id: i64, summary: &str) -> Result<Vec<Review>> {
+        let task = self.find_task(id).await?;
+        task.status.transition_to(TaskStatus::Reviewing)?;
+
+        let questions = self.llm.generate_questions(&task, summary).await?;
+        if questions.is_empty() {
+            return Err(GewuError::MalformedLlmResponse(
+                "No review questions were generated".to_string(),
+            ));
+        }
+
+        self.repo
+            .submit_summary(
+                id,
+                task.status,
+                summary,
+                &questions,
+                "Learning summary submitted",
+            )
+            .await
+    }
+
+    /// Get the review questions of the given task
+    pub async fn get_task_reviews(&self, task_id: i64) -> Result<Vec<Review>> {
+        self.repo.get_task_reviews(task_id).await
+    }
+
+    /// Score the user's answer to a review question and save the result
+    pub async fn answer_review(
+        &mut self,
+        task: &Task,
+        review: &Review,
+        answer: &str,
+    ) -> Result<AnswerVerdict> {
+        let verdict = self
+            .llm
+            .score_answer(task, &review.question, answer)
+            .await?;
+        self.repo
+            .update_review_answer(review.id, answer, &verdict.feedback, verdict.passed)
+            .await?;
+        Ok(verdict)
+    }
+
+    /// Finish a review round. Once every question is passed the task is summarized,
+    /// archived into the skill tree and completed.
+    pub async fn finish_review(&mut self, task_id: i64) -> Result<ReviewOutcome> {
+        let reviews = self.repo.get_task_reviews(task_id).await?;
+        let failed = reviews.iter().filter(|r| !r.is_passed).count();
+        if failed > 0 {
+            return Ok(ReviewOutcome::Incomplete { failed });
+        }
+
+        let task = self.find_task(task_id).await?;
+        task.status.transition_to(TaskStatus::Completed)?;
+        let records = self.repo.get_task_records(task_id).await?;
+        let summary = self.llm.summarize_task(&task, &records, &reviews).await?;
+
+        let mut tags: Vec<String> = Vec::new();
+        for tag in summary.tags.iter().map(|t| t.trim()) {
+            if !tag.is_empty() && !tags.iter().any(|t| t.eq_ignore_ascii_case(tag)) {
+                tags.push(tag.to_string());
+            }
+        }
+        if tags.is_empty() {
+            tags.push(task.topic.clone());
+        }
+
+        let archive = self
+            .repo
+            .complete_task(
+                task_id,
+                task.status,
+                summary.summary.trim(),
+                &tags,
+                "All review questions passed",
+            )
+            .await?;
+        Ok(ReviewOutcome::Completed(archive))
+    }
+
+    /// Get the completed tasks of the skill tree, most recently completed first
+    pub async fn get_task_archives(&self) -> Result<Vec<TaskArchive>> {
+        self.repo.get_task_archives().await
+    }
+
+    /// The milestones of a task, earliest due first.
+    pub async fn get_milestones(&self, task_id: i64) -> Result<Vec<Milestone>> {
+        self.repo.get_milestones(task_id).await
+    }
+
+    /// Check a milestone of an active task off, or reopen it.
+    pub async fn set_milestone_completed(
+        &mut self,
+        task_id: i64,
+        milestone_id: i64,
+        completed: bool,
+    ) -> Result<()> {
+        let task = self.find_task(task_id).await?;
+        if task.status != TaskStatus::Active {
+            return Err(GewuError::status_conflict(format!(
+                "Task #{} is not active",
+                task_id
+            )));
+        }
+        let milestones = self.repo.get_milestones(task_id).await?;
+        if !milestones.iter().any(|m| m.id == milestone_id) {
+            return Err(GewuError::NotFound {
+                kind: "Milestone",
+                id: milestone_id,
+            });
+        }
+
+        self.repo
+            .update_milestone_completed(milestone_id, completed)
+            .await
+    }
+
+    /// Gather every task with its history, the learning records and the rejected attempts
+    pub async fn export_backup(&self) -> Result<Backup> {
+        let mut tasks = self.repo.get_tasks_by_status(&TaskStatus::ALL).await?;
+        tasks.sort_by_key(|t| t.id);
+        let mut archives: HashMap<i64, TaskArchive> = self
+            .repo
+            .get_task_archives()
+            .await?
+            .into_iter()
+            .map(|a| (a.task_id, a))
+            .collect();
+
+        let mut entries = Vec::with_capacity(tasks.len());
+        for task in tasks {
+            let records = self.repo.get_task_records(task.id).await?;
+            entries.push(TaskBackup {
+                primer: self.repo.get_task_primer(task.id).await?,
+                record_ids: records.iter().map(|r| r.id).collect(),
+                transitions: self.repo.get_task_transitions(task.id).await?,
+                smart_goal_turns: self.repo.get_smart_goal_turns(task.id).await?,
+                milestones: self.repo.get_milestones(task.id).await?,
+                reviews: self.repo.get_task_reviews(task.id).await?,
+                revisions: self.repo.get_task_revisions(task.id).await?,
+                archive: archives.remove(&task.id),
+                task,
+            });
+        }
+
+        // oldest first, so the restored ones keep their order
+        let mut rejected_attempts = self.repo.get_rejected_attempts().await?;
+        rejected_attempts.reverse();
+        Ok(Backup::new(
+            entries,
+            self.repo.get_records().await?,
+            rejected_attempts,
+        ))
+    }
+
+    /// Restore a backup with new ids, bypassing the WIP limits.
+    ///
+    /// A task with the same topic and creation time as a stored one is a conflict, failing
+    /// with [`ImportConflict`] unless `skip_existing`. Records and rejected attempts already
+    /// stored are not copied again.
+    pub async fn import_backup(
+        &mut self,
+        backup: &Backup,
+        skip_existing: bool,
+    ) -> Result<ImportReport> {
+        backup.check_version()?;
+
+        let stored = self.repo.get_tasks_by_status(&TaskStatus::ALL).await?;
+        let (existing, tasks): (Vec<TaskBackup>, Vec<TaskBackup>) =
+            backup.tasks.iter().cloned().partition(|entry| {
+                stored.iter().any(|t| {
+                    t.topic == entry.task.topic && same_instant(t.created_at, entry.task.created_at)
+                })
+            });
+        if !existing.is_empty() && !skip_existing {
+            return Err(ImportConflict {
+                topics: existing.into_iter().map(|e| e.task.topic).collect(),
+            }
+            .into());
+        }
+
+        let stored_records = self.repo.get_records().await?;
+        let existing_records: HashMap<i64, i64> = backup
+            .records
+            .iter()
+            .filter_map(|record| {
+                stored_records
+                    .iter()
+                    .find(|r| {
+                        r.content == record.content && same_instant(r.created_at, record.created_at)
+                    })
+                    .map(|r| (record.id, r.id))
+            })
+            .collect();
+
+        let stored_attempts = self.repo.get_rejected_attempts().await?;
+        let rejected_attempts: Vec<RejectedAttempt> = backup
+            .rejected_attempts
+            .iter()
+            .filter(|attempt| {
+                !stored_attempts.iter().any(|a| {
+                    a.topic == attempt.topic
+                        && a.motivation == attempt.motivation
+                        && same_instant(a.created_at, attempt.created_at)
+                })
+            })
+            .cloned()
+            .collect();
+
+        let report = ImportReport {
+            tasks: tasks.len(),
+            records: backup.records.len() - existing_records.len(),
+            rejected_attempts: rejected_attempts.len(),
+            skipped_tasks: existing.iter().map(|e| e.task.id).collect(),
+            existing_records,
+        };
+        let backup = Backup {
+            tasks,
+            rejected_attempts,
+            ..backup.clone()
+        };
+        self.repo.restore(&backup, &report.existing_records).await?;
+        Ok(report)
+    }
+
+    /// Get the task, failing with [`GewuError::NotFound`] when there is none
+    pub async fn find_task(&self, id: i64) -> Result<Task> {
+        match self.repo.get_task(id).await? {
+            None => Err(GewuError::task_not_found(id)),
+            Some(task) => Ok(task),
+        }
+    }
+
+    /// A task still waiting for its SMART goal. Tasks under review or paused could move to
+    /// active too, but planning them again would replace the goal they are working on.
+    pub async fn find_planning_task(&self, id: i64) -> Result<Task> {
+        let task = self.find_task(id).await?;
+        if task.status != TaskStatus::Planning {
+            return Err(GewuError::status_conflict(format!(
+                "Task #{} is in {} status, only planning tasks can be planned",
+                id, task.status
+            )));
+        }
+        Ok(task)
+    }
+
+    /// A task that is not finished yet, so its details may still change
+    async fn find_editable_task(&self, id: i64) -> Result<Task> {
+        let task = self.find_task(id).await?;
+        if matches!(task.status, TaskStatus::Completed | TaskStatus::Discarded) {
+            return Err(GewuError::status_conflict(format!(
+                "Task #{} is already {}",
+                id, task.status
+            )));
+        }
+        Ok(task)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        adapters::{memory_repo::MemoryRepo, mock_llm::MockLlm},
+        domain::{
+            backup::{BACKUP_FORMAT, BACKUP_VERSION, UnsupportedBackup},
+            policy::WipLimitReached,
+        },
+        ports::llm::TaskSummary,
+    };
+
+    const TASK_FLOW_FIXTURE: &str = "tests/fixtures/llm/task_flow.json";
+
+    #[tokio::test]
+    async fn rejected_motivation_should_not_create_task() {
+        let llm = MockLlm::new().with_audit_motivation(GatekeeperVerdict {
+            passed: false,
+            reason: "Too vague".to_string(),
+            recommendation: "Name a project".to_string(),
+        });
+        let mut manager = TaskManager::new(llm, MemoryRepo::new());
+
+        let err = manager
+            .create_task("Rust", "I want to learn Rust")
+            .await
+            .unwrap_err();
+        let GewuError::Rejected { verdict } = err else {
+            panic!("expected a rejected motivation, got {:?}", err);
+        };
+        let Verdict::Motivation(verdict) = *verdict else {
+            panic!("expected a rejected motivation, got {:?}", verdict);
+        };
+        assert_eq!(verdict.reason, "Too vague".to_string());
+
+        let tasks = manager
+            .get_tasks_by_status(&[TaskStatus::Planning])
+            .await
+            .unwrap();
+        assert!(tasks.is_empty());
+    }
+
+    #[tokio::test]
+    async fn task_flow_should_go_from_add_to_completed() {
+        let mut manager = replay_manager(TASK_FLOW_FIXTURE).await;
+
+        // add: the motivation is approved and the task starts planning
+        let (id, verdict) = manager
+            .create_task("Tokio Runtime", "Build a Redis clone on Tokio")
+            .await
+            .unwrap();
+        assert!(verdict.passed);
+        let task = manager.get_task(id).await.unwrap().unwrap();
+        assert_eq!(task.status, TaskStatus::Planning);
+
+        // plan: the first goal is rejected with guidance, the second one is approved
+        let verdict = manager
+            .negotiate_smart_goal(id, "Learn Tokio")
+            .await
+            .unwrap();
+        assert!(!verdict.passed);
+        assert!(verdict.guidance.is_some());
+
+        let verdict = manager
+            .negotiate_smart_goal(id, "Build a Redis clone with GET/SET/EXPIRE in 4 weeks")
+            .await
+            .unwrap();
+        assert!(verdict.passed);
+        let turns = manager.get_smart_goal_turns(id).await.unwrap();
+        let roles: Vec<_> = turns.iter().map(|turn| turn.role).collect();
+        assert_eq!(
+            roles,
+            vec![
+                TurnRole::Learner,
+                TurnRole::Strategist,
+                TurnRole::Learner,
+                TurnRole::Strategist
+            ]
+        );
+        assert_eq!(turns[0].content, "Learn Tokio");
+        let goal = verdict.refined_goal.unwrap();
+        manager
+            .update_task_smart_goal(id, &goal, &verdict.milestones, verdict.deadline)
+            .await
+            .unwrap();
+        let task = manager.get_task(id).await.unwrap().unwrap();
+        assert_eq!(task.status, TaskStatus::Active);
+        assert_eq!(task.smart_goal, Some(goal.clone()));
+        let deadline = NaiveDate::from_ymd_opt(2026, 4, 28).unwrap();
+        assert_eq!(task.deadline, Some(deadline));
+
+        // deadline: overdue the day after, then moved by the learner
+        let day_after = deadline.succ_opt().unwrap();
+        assert_eq!(task.days_remaining(deadline), Some(0));
+        assert_eq!(
+            manager.get_review_candidates(day_after).await.unwrap()[0].id,
+            id
+        );
+        manager
+            .set_task_deadline(id, Some(day_after))
+            .await
+            .unwrap();
+        assert!(
+            manager
+                .get_overdue_tasks(day_after)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+
+        // re-planning an active task is rejected
+        assert!(
+            manager
+                .update_task_smart_goal(id, &goal, &[], None)
+                .await
+                .is_err()
+        );
+
+        // milestones: stored earliest due first and checked off one by one
+        let milestones = manager.get_milestones(id).await.unwrap();
+        let titles: Vec<_> = milestones.iter().map(|m| m.title.as_str()).collect();
+        assert_eq!(titles, vec!["GET and SET", "EXPIRE and benchmark"]);
+        manager
+            .set_milestone_completed(id, milestones[0].id, true)
+            .await
+            .unwrap();
+        assert!(
+            manager
+                .set_milestone_completed(id + 1, milestones[0].id, true)
+                .await
+                .is_err()
+        );
+        let milestones = manager.get_milestones(id).await.unwrap();
+        assert!(milestones[0].is_completed());
+        assert!(!milestones[1].is_completed());
+        assert!(milestones[1].is_overdue(milestones[1].due_date.succ_opt().unwrap()));
+        assert!(!milestones[0].is_overdue(milestones[0].due_date.succ_opt().unwrap()));
+
+        // learn: the primer is generated once and stored
+        let mut rx = manager.generate_primer_stream(&task).await.unwrap();
+        let mut primer = String::new();
+        while let Some(chunk) = rx.recv().await {
+            primer.push_str(&chunk);
+        }
+        assert!(primer.starts_with("### 🧩 The Problem"));
+        manager.save_task_primer(id, &primer).await.unwrap();
+        assert_eq!(manager.get_task_primer(id).await.unwrap(), Some(primer));
+
+        // record
+        let matched = manager
+            .match_record_with_tasks("Read the Tokio scheduler source")
+            .await
+            .unwrap();
+        assert_eq!(matched.task_ids, vec![id]);
+        assert!(matched.dropped.is_empty());
+        manager
+            .record_learning_progress(&matched.task_ids, "Read the Tokio scheduler source")
+            .await
+            .unwrap();
+        let records = manager.get_task_records(id).await.unwrap();
+        assert_eq!(records.len(), 1);
+
+        // guide
+        let mut rx = manager
+            .generate_guide_stream(&task, &records)
+            .await
+            .unwrap();
+        let mut guide = String::new();
+        while let Some(chunk) = rx.recv().await {
+            guide.push_str(&chunk);
+        }
+        assert!(guide.contains("### 🎯 Next Step"));
+
+        // commit: the summary is recorded and the task moves into review
+        let reviews = manager
+            .commit_task(id, "Tokio uses work stealing across worker threads")
+            .await
+            .unwrap();
+        assert_eq!(reviews.len(), 3);
+        assert_eq!(manager.get_task_records(id).await.unwrap().len(), 2);
+        let task = manager.get_task(id).await.unwrap().unwrap();
+        assert_eq!(task.status, TaskStatus::Reviewing);
+
+        // review: the second question fails and has to be retried
+        for review in &reviews {
+            manager
+                .answer_review(&task, review, "answer")
+                .await
+                .unwrap();
+        }
+        assert!(matches!(
+            manager.finish_review(id).await.unwrap(),
+            ReviewOutcome::Incomplete { failed: 1 }
+        ));
+
+        let reviews = manager.get_task_reviews(id).await.unwrap();
+        let failed = reviews.iter().find(|r| !r.is_passed).unwrap();
+        let verdict = manager
+            .answer_review(&task, failed, "a single timer task")
+            .await
+            .unwrap();
+        assert!(verdict.passed);
+        let ReviewOutcome::Completed(archive) = manager.finish_review(id).await.unwrap() else {
+            panic!("all questions are passed");
+        };
+        assert_eq!(archive.task_id, id);
+        assert_eq!(archive.topic, "Tokio Runtime");
+        assert_eq!(archive.tags, vec!["Rust", "Async Runtime"]);
+        assert_eq!(manager.get_task_archives().await.unwrap().len(), 1);
+
+        let task = manager.get_task(id).await.unwrap().unwrap();
+        assert_eq!(task.status, TaskStatus::Completed);
+        let transitions = manager.get_task_transitions(id).await.unwrap();
+        assert_eq!(
+            transitions
+                .iter()
+                .map(|t| t.to_status.clone())
+                .collect::<Vec<_>>(),
+            vec![
+                TaskStatus::Active,
+                TaskStatus::Reviewing,
+                TaskStatus::Completed
+            ]
+        );
+        assert!(manager.llm.is_exhausted());
+    }
+
+    #[tokio::test]
+    async fn record_should_only_link_active_tasks() {
+        let repo = MemoryRepo::new();
+        let active = repo.create_task("active", "motivation").await.unwrap();
+        let planning = repo.create_task("planning", "motivation").await.unwrap();
+        repo.update_task_status(active.id, TaskStatus::Planning, TaskStatus::Active, None)
+            .await
+            .unwrap();
+        let llm = MockLlm::new().with_match_tasks(vec![active.id, 404, planning.id, active.id]);
+        let mut manager = TaskManager::new(llm, repo);
+
+        let matched = manager.match_record_with_tasks("record").await.unwrap();
+        assert_eq!(ids(&matched.candidates), vec![active.id]);
+        assert_eq!(matched.task_ids, vec![active.id]);
+        assert_eq!(matched.dropped, vec![404, planning.id]);
+
+        // a manual pick is checked as well
+        assert!(
+            manager
+                .record_learning_progress(&[active.id, planning.id], "record")
+                .await
+                .is_err()
+        );
+        assert!(
+            manager
+                .record_learning_progress(&[], "record")
+                .await
+                .is_err()
+        );
+        assert!(
+            manager
+                .get_task_records(active.id)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+
+        manager
+            .record_learning_progress(&[active.id, active.id], "record")
+            .await
+            .unwrap();
+        assert_eq!(manager.get_task_records(active.id).await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn primer_should_need_an_active_task_and_be_replaced_on_refresh() {
+        let repo = MemoryRepo::new();
+        let task = repo.create_task("topic", "motivation").await.unwrap();
+        let llm = MockLlm::new()
+            .with_primer(vec!["first".to_string()])
+            .with_primer(vec!["second".to_string()]);
+        let mut manager = TaskManager::new(llm, repo);
+
+        assert!(manager.generate_primer_stream(&task).await.is_err());
+
+        manager
+            .transition_task(task.id, TaskStatus::Active, "goal approved")
+            .await
+            .unwrap();
+        let task = manager.get_task(task.id).await.unwrap().unwrap();
+        for expected in ["first", "second"] {
+            let mut rx = manager.generate_primer_stream(&task).await.unwrap();
+            let primer = rx.recv().await.unwrap();
+            manager.save_task_primer(task.id, &primer).await.unwrap();
+            assert_eq!(
+                manager.get_task_primer(task.id).await.unwrap(),
+                Some(expected.to_string())
+            );
+        }
+        assert!(manager.save_task_primer(task.id, "  ").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn completed_task_without_tags_should_be_filed_under_its_topic() {
+        let repo = MemoryRepo::new();
+        let task = repo.create_task("Raft", "motivation").await.unwrap();
+        repo.update_task_status(task.id, TaskStatus::Planning, TaskStatus::Active, None)
+            .await
+            .unwrap();
+        let reviews = repo
+            .submit_summary(
+                task.id,
+                TaskStatus::Active,
+                "summary",
+                &["question".to_string()],
+                "submitted",
+            )
+            .await
+            .unwrap();
+        repo.update_review_answer(reviews[0].id, "answer", "feedback", true)
+            .await
+            .unwrap();
+        let llm = MockLlm::new().with_summary(TaskSummary {
+            summary: " Can explain leader election. ".to_string(),
+            tags: vec!["  ".to_string()],
+        });
+        let mut manager = TaskManager::new(llm, repo);
+
+        let ReviewOutcome::Completed(archive) = manager.finish_review(task.id).await.unwrap()
+        else {
+            panic!("all questions are passed");
+        };
+        assert_eq!(archive.summary, "Can explain leader election.");
+        assert_eq!(archive.tags, vec!["Raft"]);
+        let task = manager.get_task(task.id).await.unwrap().unwrap();
+        assert_eq!(task.status, TaskStatus::Completed);
+    }
+
+    #[tokio::test]
+    async fn discarded_task_should_keep_records_in_graveyard() {
+        let repo = MemoryRepo::new();
+        let task = repo.create_task("Haskell", "motivation").await.unwrap();
+        let planning = repo.create_task("Zig", "motivation").await.unwrap();
+        repo.update_task_status(task.id, TaskStatus::Planning, TaskStatus::Active, None)
+            .await
+            .unwrap();
+        let mut manager = TaskManager::new(MockLlm::new(), repo);
+        manager
+            .record_learning_progress(&[task.id], "Read about monads")
+            .await
+            .unwrap();
+
+        assert!(manager.discard_task(task.id, "  ").await.is_err());
+        manager
+            .discard_task(task.id, " No project needs it ")
+            .await
+            .unwrap();
+        manager
+            .discard_task(planning.id, "Just curious")
+            .await
+            .unwrap();
+        // a discarded task cannot be discarded again
+        assert!(manager.discard_task(task.id, "again").await.is_err());
+
+        let graveyard = manager.get_graveyard().await.unwrap();
+        assert_eq!(graveyard.len(), 2);
+        let entry = graveyard.iter().find(|e| e.task.id == task.id).unwrap();
+        assert_eq!(entry.reason.as_deref(), Some("No project needs it"));
+        assert_eq!(entry.discarded_from, TaskStatus::Active);
+        assert_eq!(entry.records, 1);
+        let entry = graveyard.iter().find(|e| e.task.id == planning.id).unwrap();
+        assert_eq!(entry.discarded_from, TaskStatus::Planning);
+        assert_eq!(entry.records, 0);
+    }
+
+    #[tokio::test]
+    async fn rejected_motivations_should_be_shown_on_similar_topics() {
+        let rejected = GatekeeperVerdict {
+            passed: false,
+            reason: "No use case".to_string(),
+            recommendation: "Name a project".to_string(),
+        };
+        let llm = MockLlm::new()
+            .with_audit_motivation(rejected.clone())
+            .with_audit_motivation(rejected);
+        let mut manager = TaskManager::new(llm, MemoryRepo::new());
+
+        for topic in ["Kubernetes", "Learn Kubernetes basics"] {
+            assert!(
+                manager
+                    .create_task(topic, "Everyone uses it")
+                    .await
+                    .is_err()
+            );
+        }
+        let task = manager
+            .repo
+            .create_task("kubernetes operators", "motivation")
+            .await
+            .unwrap();
+        manager
+            .transition_task(task.id, TaskStatus::Discarded, "Lost interest")
+            .await
+            .unwrap();
+
+        let attempts = manager
+            .get_past_attempts("Kubernetes in practice")
+            .await
+            .unwrap();
+        assert_eq!(
+            attempts
+                .iter()
+                .map(|a| (a.outcome.clone(), a.topic.as_str()))
+                .collect::<Vec<_>>(),
+            vec![
+                (AttemptOutcome::Discarded, "kubernetes operators"),
+                (AttemptOutcome::Rejected, "Learn Kubernetes basics"),
+                (AttemptOutcome::Rejected, "Kubernetes"),
+            ]
+        );
+        assert_eq!(attempts[1].reason.as_deref(), Some("No use case"));
+        assert!(manager.get_past_attempts("Rust").await.unwrap().is_empty());
+        assert!(manager.get_past_attempts("Learn").await.unwrap().is_empty());
+    }
+
+    #[test]
+    fn topic_keywords_should_split_cjk_into_bigrams() {
+        assert!(!topic_keywords("学习 Kubernetes").is_disjoint(&topic_keywords("kubernetes 实战")));
+        assert!(!topic_keywords("分布式系统").is_disjoint(&topic_keywords("分布式事务")));
+        assert!(topic_keywords("学习Rust").is_disjoint(&topic_keywords("学习Go")));
+    }
+
+    #[tokio::test]
+    async fn wip_limits_should_block_new_work_until_a_task_is_paused() {
+        let approve = || GatekeeperVerdict {
+            passed: true,
+            reason: "Concrete use case".to_string(),
+            recommendation: "Set a goal".to_string(),
+        };
+        let llm = MockLlm::new()
+            .with_audit_motivation(approve())
+            .with_audit_motivation(approve())
+            .with_match_tasks(vec![1, 2]);
+        let mut manager = TaskManager::new(llm, MemoryRepo::new()).with_wip_policy(WipPolicy {
+            max_planning: 1,
+            max_active: 1,
+        });
+        let goal = SmartGoalDetail {
+            specific: "goal".to_string(),
+            measurable: String::new(),
+            achievable: String::new(),
+            relevant: String::new(),
+            time_bound: String::new(),
+        };
+        let is_wip_limit = |e: GewuError| match e {
+            GewuError::WipLimitExceeded(limit) => Some(limit),
+            _ => None,
+        };
+        let today = NaiveDate::from_ymd_opt(2026, 4, 1).unwrap();
+
+        // the limit is checked before the gatekeeper is asked
+        let (first, _) = manager.create_task("first", "motivation").await.unwrap();
+        assert_eq!(
+            is_wip_limit(
+                manager
+                    .create_task("second", "motivation")
+                    .await
+                    .unwrap_err()
+            ),
+            Some(WipLimitReached {
+                status: TaskStatus::Planning,
+                limit: 1
+            })
+        );
+
+        // pausing frees the slot, resuming needs it back
+        manager.pause_task(first, None, today).await.unwrap();
+        let (second, _) = manager.create_task("second", "motivation").await.unwrap();
+        assert!(is_wip_limit(manager.resume_task(first).await.unwrap_err()).is_some());
+
+        manager
+            .update_task_smart_goal(second, &goal, &[], None)
+            .await
+            .unwrap();
+        assert_eq!(
+            manager.resume_task(first).await.unwrap(),
+            TaskStatus::Planning
+        );
+
+        // activating a second task exceeds the active limit and changes nothing
+        let err = manager
+            .update_task_smart_goal(first, &goal, &[], None)
+            .await
+            .unwrap_err();
+        assert_eq!(
+            is_wip_limit(err).map(|e| e.status),
+            Some(TaskStatus::Active)
+        );
+        let task = manager.get_task(first).await.unwrap().unwrap();
+        assert_eq!(task.status, TaskStatus::Planning);
+        assert!(task.smart_goal.is_none());
+
+        // a paused active task comes back active once its date has come
+        let until = NaiveDate::from_ymd_opt(2026, 5, 1).unwrap();
+        for past in [today, today.pred_opt().unwrap()] {
+            assert!(matches!(
+                manager.pause_task(second, Some(past), today).await,
+                Err(GewuError::InvalidInput(_))
+            ));
+        }
+        assert_eq!(
+            manager.get_task(second).await.unwrap().unwrap().status,
+            TaskStatus::Active
+        );
+        manager
+            .pause_task(second, Some(until), today)
+            .await
+            .unwrap();
+        assert!(
+            manager
+                .get_tasks_due_to_resume(until.pred_opt().unwrap())
+                .await
+                .unwrap()
+                .is_empty()
+        );
+        assert_eq!(
+            manager.get_tasks_due_to_resume(until).await.unwrap()[0].id,
+            second
+        );
+        manager
+            .update_task_smart_goal(first, &goal, &[], None)
+            .await
+            .unwrap();
+        assert!(is_wip_limit(manager.resume_task(second).await.unwrap_err()).is_some());
+        manager.pause_task(first, None, today).await.unwrap();
+        assert_eq!(
+            manager.resume_task(second).await.unwrap(),
+            TaskStatus::Active
+        );
+        let task = manager.get_task(second).await.unwrap().unwrap();
+        assert_eq!(task.paused_until, None);
+        assert!(manager.resume_task(second).await.is_err());
+
+        // paused tasks are no candidates for records
+        let matched = manager.match_record_with_tasks("record").await.unwrap();
+        assert_eq!(
+            matched.candidates.iter().map(|t| t.id).collect::<Vec<_>>(),
+            vec![second]
+        );
+        assert_eq!(matched.task_ids, vec![second]);
+        assert_eq!(matched.dropped, vec![first]);
+    }
+
+    #[tokio::test]
+    async fn edits_should_be_audited_and_keep_previous_values() {
+        let verdict = |passed| GatekeeperVerdict {
+            passed,
+            reason: "reason".to_string(),
+            recommendation: "recommendation".to_string(),
+        };
+        let goal = |specific: &str| SmartGoalDetail {
+            specific: specific.to_string(),
+            measurable: String::new(),
+            achievable: String::new(),
+            relevant: String::new(),
+            time_bound: String::new(),
+        };
+        let deadline = NaiveDate::from_ymd_opt(2026, 6, 1).unwrap();
+        let llm = MockLlm::new()
+            .with_audit_motivation(verdict(true))
+            .with_audit_motivation(verdict(false))
+            .with_audit_motivation(verdict(true))
+            .with_negotiate_smart_goal(SmartGoalVerdict {
+                passed: true,
+                reason: "Good".to_string(),
+                guidance: None,
+                refined_goal: Some(goal("Build a CLI")),
+                milestones: vec![],
+                deadline: Some(deadline),
+            });
+        let mut manager = TaskManager::new(llm, MemoryRepo::new());
+        let (id, _) = manager.create_task("Rsut", "motivation").await.unwrap();
+
+        manager.edit_task_topic(id, " Rust ").await.unwrap();
+        assert!(manager.edit_task_topic(id, "Rust").await.is_err());
+        assert!(manager.edit_task_topic(id, " ").await.is_err());
+
+        // a rejected motivation leaves the task as it is
+        let rejected = manager.edit_task_motivation(id, "hype").await.unwrap();
+        assert!(!rejected.passed);
+        let approved = manager
+            .edit_task_motivation(id, "ship a CLI at work")
+            .await
+            .unwrap();
+        assert!(approved.passed);
+        let task = manager.get_task(id).await.unwrap().unwrap();
+        assert_eq!(task.topic, "Rust");
+        assert_eq!(task.motivation, Some("ship a CLI at work".to_string()));
+
+        // the goal can only be renegotiated once it was planned
+        assert!(manager.renegotiate_smart_goal(id, "goal").await.is_err());
+        assert!(
+            manager
+                .amend_smart_goal(id, &goal("x"), None)
+                .await
+                .is_err()
+        );
+        manager
+            .update_task_smart_goal(id, &goal("Read the book"), &[], None)
+            .await
+            .unwrap();
+        let verdict = manager
+            .renegotiate_smart_goal(id, "Build a CLI")
+            .await
+            .unwrap();
+        manager
+            .amend_smart_goal(id, &verdict.refined_goal.unwrap(), verdict.deadline)
+            .await
+            .unwrap();
+        let task = manager.get_task(id).await.unwrap().unwrap();
+        assert_eq!(task.status, TaskStatus::Active);
+        assert_eq!(task.smart_goal, Some(goal("Build a CLI")));
+        assert_eq!(task.deadline, Some(deadline));
+        assert_eq!(manager.get_smart_goal_turns(id).await.unwrap().len(), 2);
+
+        let revisions = manager.get_task_revisions(id).await.unwrap();
+        let fields: Vec<_> = revisions.iter().map(|r| r.field).collect();
+        assert_eq!(
+            fields,
+            vec![
+                TaskField::Topic,
+                TaskField::Motivation,
+                TaskField::SmartGoal
+            ]
+        );
+        assert_eq!(revisions[0].previous, Some("Rsut".to_string()));
+        assert_eq!(revisions[1].previous, Some("motivation".to_string()));
+        let previous: SmartGoalDetail =
+            serde_json::from_str(revisions[2].previous.as_deref().unwrap()).unwrap();
+        assert_eq!(previous, goal("Read the book"));
+
+        // finished tasks can no longer be edited
+        manager.discard_task(id, "no time").await.unwrap();
+        assert!(manager.edit_task_topic(id, "Go").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn failed_review_can_go_back_to_active_and_recommit() {
+        let llm = MockLlm::new()
+            .with_audit_motivation(GatekeeperVerdict {
+                passed: true,
+                reason: "Concrete use case".to_string(),
+                recommendation: "Set a goal".to_string(),
+            })
+            .with_negotiate_smart_goal(SmartGoalVerdict {
+                passed: true,
+                reason: "Good".to_string(),
+                guidance: None,
+                refined_goal: Some(SmartGoalDetail {
+                    specific: "goal".to_string(),
+                    measurable: String::new(),
+                    achievable: String::new(),
+                    relevant: String::new(),
+                    time_bound: String::new(),
+                }),
+                milestones: vec![MilestonePlan {
+                    title: "milestone".to_string(),
+                    due_date: NaiveDate::from_ymd_opt(2026, 5, 1).unwrap(),
+                    criterion: "criterion".to_string(),
+                }],
+                deadline: None,
+            })
+            .with_questions(vec!["q1".to_string()])
+            .with_score_answer(AnswerVerdict {
+                passed: false,
+                feedback: "Wrong".to_string(),
+            })
+            .with_questions(vec!["q2".to_string(), "q3".to_string()]);
+        let mut manager = TaskManager::new(llm, MemoryRepo::new());
+
+        let (id, _) = manager.create_task("topic", "motivation").await.unwrap();
+        let verdict = manager.negotiate_smart_goal(id, "goal").await.unwrap();
+        assert!(verdict.passed);
+        manager
+            .update_task_smart_goal(
+                id,
+                &verdict.refined_goal.unwrap(),
+                &verdict.milestones,
+                verdict.deadline,
+            )
+            .await
+            .unwrap();
+        // without a deadline from the strategist the last milestone is the deadline
+        let task = manager.get_task(id).await.unwrap().unwrap();
+        assert_eq!(task.deadline, Some(verdict.milestones[0].due_date));
+
+        // a task under review cannot be committed again
+        let reviews = manager.commit_task(id, "summary").await.unwrap();
+        assert!(manager.commit_task(id, "summary").await.is_err());
+        let task = manager.get_task(id).await.unwrap().unwrap();
+        let verdict = manager
+            .answer_review(&task, &reviews[0], "answer")
+            .await
+            .unwrap();
+        assert!(!verdict.passed);
+        assert!(matches!(
+            manager.finish_review(id).await.unwrap(),
+            ReviewOutcome::Incomplete { failed: 1 }
+        ));
+
+        manager
+            .transition_task(id, TaskStatus::Active, "Review failed")
+            .await
+            .unwrap();
+
+        // a new commit replaces the previous questions
+        let reviews = manager.commit_task(id, "better summary").await.unwrap();
+        assert_eq!(reviews.len(), 2);
+        assert_eq!(manager.get_task_reviews(id).await.unwrap().len(), 2);
+        assert!(manager.llm.is_exhausted());
+    }
+
+    #[tokio::test]
+    async fn only_planning_tasks_should_be_planned() {
+        let goal = SmartGoalDetail {
+            specific: "goal".to_string(),
+            measurable: String::new(),
+            achievable: String::new(),
+            relevant: String::new(),
+            time_bound: String::new(),
+        };
+        let milestones = [MilestonePlan {
+            title: "milestone".to_string(),
+            due_date: NaiveDate::from_ymd_opt(2026, 5, 1).unwrap(),
+            criterion: "criterion".to_string(),
+        }];
+        let repo = MemoryRepo::new();
+        let mut ids = vec![];
+        for to in [TaskStatus::Reviewing, TaskStatus::Paused] {
+            let task = repo.create_task("topic", "motivation").await.unwrap();
+            repo.approve_smart_goal(
+                task.id,
+                TaskStatus::Planning,
+                &goal,
+                &milestones,
+                None,
+                "approved",
+            )
+            .await
+            .unwrap();
+            repo.update_task_status(task.id, TaskStatus::Active, to, None)
+                .await
+                .unwrap();
+            ids.push(task.id);
+        }
+        let mut manager = TaskManager::new(MockLlm::new(), repo);
+
+        let new_goal = SmartGoalDetail {
+            specific: "another goal".to_string(),
+            ..goal.clone()
+        };
+        for id in ids {
+            let before = manager.get_task(id).await.unwrap().unwrap();
+            assert!(matches!(
+                manager.negotiate_smart_goal(id, "another goal").await,
+                Err(GewuError::StatusConflict(_))
+            ));
+            assert!(matches!(
+                manager
+                    .update_task_smart_goal(id, &new_goal, &milestones, None)
+                    .await,
+                Err(GewuError::StatusConflict(_))
+            ));
+
+            let task = manager.get_task(id).await.unwrap().unwrap();
+            assert_eq!(task.status, before.status);
+            assert_eq!(task.smart_goal, Some(goal.clone()));
+            assert_eq!(manager.get_milestones(id).await.unwrap().len(), 1);
+            assert!(manager.get_smart_goal_turns(id).await.unwrap().is_empty());
+            assert_eq!(manager.get_task_transitions(id).await.unwrap().len(), 2);
+        }
+    }
+
+    #[tokio::test]
+    async fn backup_should_round_trip_and_detect_conflicts() {
+        let repo = MemoryRepo::new();
+        let task = repo.create_task("Rust", "motivation").await.unwrap();
+        repo.update_task_status(task.id, TaskStatus::Planning, TaskStatus::Active, None)
+            .await
+            .unwrap();
+        let record = repo.create_record("ownership").await.unwrap();
+        repo.create_task_record(task.id, record.id).await.unwrap();
+        repo.create_rejected_attempt("Kubernetes", "Everyone uses it", "No use case")
+            .await
+            .unwrap();
+        let source = TaskManager::new(MockLlm::new(), repo);
+
+        let backup = source.export_backup().await.unwrap();
+        let json = serde_json::to_string(&backup).unwrap();
+        let backup: Backup = serde_json::from_str(&json).unwrap();
+        assert!(backup.to_markdown().contains("## #1 Rust (Active)"));
+
+        // the target already has a task, the backup gets new ids
+        let repo = MemoryRepo::new();
+        repo.create_task("Go", "motivation").await.unwrap();
+        let mut target = TaskManager::new(MockLlm::new(), repo).with_wip_policy(WipPolicy {
+            max_planning: 0,
+            max_active: 0,
+        });
+        let report = target.import_backup(&backup, false).await.unwrap();
+        assert_eq!(
+            (report.tasks, report.records, report.rejected_attempts),
+            (1, 1, 1)
+        );
+        let imported = target.get_task(2).await.unwrap().unwrap();
+        assert_eq!(imported.topic, "Rust".to_string());
+        assert_eq!(imported.status, TaskStatus::Active);
+        assert_eq!(target.get_task_records(2).await.unwrap().len(), 1);
+        assert_eq!(target.get_task_transitions(2).await.unwrap().len(), 1);
+
+        // a second import finds the same tasks
+        let conflict = target.import_backup(&backup, false).await.unwrap_err();
+        assert!(matches!(conflict, GewuError::InvalidInput(_)));
+        assert_eq!(
+            conflict.to_string(),
+            ImportConflict {
+                topics: vec!["Rust".to_string()]
+            }
+            .to_string()
+        );
+        let report = target.import_backup(&backup, true).await.unwrap();
+        assert_eq!(
+            (report.tasks, report.records, report.rejected_attempts),
+            (0, 0, 0)
+        );
+        assert_eq!(report.skipped_tasks, vec![task.id]);
+        assert_eq!(report.existing_records.len(), 1);
+        assert_eq!(
+            target
+                .get_tasks_by_status(&TaskStatus::ALL)
+                .await
+                .unwrap()
+                .len(),
+            2
+        );
+
+        let newer = Backup {
+            version: BACKUP_VERSION + 1,
+            ..backup
+        };
+        let err = target.import_backup(&newer, true).await.unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            UnsupportedBackup {
+                format: BACKUP_FORMAT.to_string(),
+                version: BACKUP_VERSION + 1,
+            }
+            .to_string()
+        );
+    }
+
+    async fn replay_manager(fixture: &str) -> TaskManager<MockLlm, MemoryRepo> {
+        let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join(fixture);
+        TaskManager::new(MockLlm::from_fixture(path).unwrap(), MemoryRepo::new())
+    }
+
+    fn ids(tasks: &[Task]) -> Vec<i64> {
+        tasks.iter().map(|t| t.id).collect()
+    }
 }
@@ -1,2 +1,3 @@
 pub mod manager;
 pub mod prompts;
+pub mod vault;
@@ -20,6 +20,15 @@ APPROVE if:
 - Can answer: "What will you do differently after learning this?"
 - Has some time sensitivity (not "someday maybe")
 
+The learner's earlier attempts on similar topics that never turned into learning
+(rejected motivations and abandoned tasks), most recent first:
+{history}
+
+If there are earlier attempts, be stricter: a motivation that repeats the same vague
+impulse must be rejected, and the reason must call out the pattern explicitly, e.g.
+"this is your fourth time trying to learn Kubernetes without a use case".
+Only approve a repeat if the motivation now has what the earlier ones were missing.
+
 Response format:
 {
     "passed": true/false,
@@ -28,41 +37,40 @@ Response format:
 }
 "#);
 
-    static ref EVALUATE_SMART_GOAL_PROMPT: String = String::from(r#"
-You are a supportive mentor who helps users refine their SMART goals while respecting their ambitions.
+    static ref NEGOTIATE_SMART_GOAL_PROMPT: String = String::from(r#"
+You are "The Strategist", a mentor versed in agile planning who negotiates a SMART goal with
+the learner over several turns, while respecting their ambitions.
 
 Topic: {topic}
 Motivation: {motivation}
-User's SMART Goal: {goal}
+Today: {today}
+
+The conversation so far follows this message. Each learner message is either a new draft of
+their goal or a question about your previous guidance. Your previous replies are the JSON
+verdicts you gave; build on them instead of starting over, and don't repeat guidance the
+learner has already addressed.
 
-Evaluate the goal against the SMART criteria with a permissive approach:
+Evaluate the latest draft against the SMART criteria with a permissive approach:
 - Specific: Is there enough clarity about what will be done?
 - Measurable: Can we identify some way to track progress?
-- Achievable: Does the user have a reasonable plan or timeframe?
+- Achievable: Does the learner have a reasonable plan or timeframe?
 - Relevant: Does it connect to the topic and motivation?
 - Time-bound: Is there some deadline or timeframe mentioned?
 
-ONLY REJECT if:
-- The goal is extremely vague (e.g. "learn stuff", "get better") with no concrete direction
-- It completely contradicts the topic or motivation
-- It has absolutely no timeframe or measurability
-- It's clearly impossible (e.g. "master 10 programming languages in 1 day")
+ONLY REJECT if the goal is extremely vague, contradicts the motivation, has no timeframe
+or measurability at all, or is clearly impossible. Approve ambitious goals with a clear
+direction and timeframe, refining and structuring them without reducing their scope.
 
-BE SUPPORTIVE and APPROVE if:
-- The goal has clear intent and direction, even if ambitious
-- There's a reasonable timeframe and plan, even if challenging
-- The user has thought about how to approach it
-- It shows genuine commitment with time allocation
-
-Remember: Users can set ambitious goals. Your job is to refine and structure them, not to reduce them to minimal MVPs. Respect their aspirations.
+If the latest message is a question, answer it in "guidance" and keep "passed" false.
 
 IMPORTANT: You MUST respond with valid JSON only, no extra text.
+Write the reason and guidance in the same language as the learner.
 
-If REJECTED, respond with:
+If REJECTED or answering a question, respond with:
 {
     "passed": false,
-    "reason": "Brief explanation of what critical SMART elements are missing",
-    "guidance": "Gentle suggestions to add the missing elements without reducing scope"
+    "reason": "Brief explanation of what critical SMART elements are still missing",
+    "guidance": "The answer to the learner's question, or suggestions to add the missing elements"
 }
 
 If APPROVED, respond with:
@@ -70,13 +78,23 @@ If APPROVED, respond with:
     "passed": true,
     "reason": "Brief acknowledgment of the goal's strengths",
     "refined_goal": {
-        "specific": "A clear, refined description of WHAT will be accomplished (keep the user's scope)",
+        "specific": "A clear, refined description of WHAT will be accomplished (keep the learner's scope)",
         "measurable": "Concrete metrics or indicators to track progress and completion",
-        "achievable": "Why this goal is realistic given the user's commitment and plan",
+        "achievable": "Why this goal is realistic given the learner's commitment and plan",
         "relevant": "How this goal connects to the topic and serves the motivation",
-        "time_bound": "Specific deadline and suggested milestones to help track progress"
-    }
+        "time_bound": "Specific deadline of the whole goal"
+    },
+    "deadline": "YYYY-MM-DD, the deadline of the whole goal",
+    "milestones": [
+        {
+            "title": "A short name of the checkpoint",
+            "due_date": "YYYY-MM-DD, between today and the deadline",
+            "criterion": "The measurable result that proves the milestone is reached"
+        }
+    ]
 }
+
+Split the goal into 2 to 6 milestones in chronological order, the last one due on the deadline.
 "#);
 
     static ref MATCH_TASKS_PROMPT: String = String::from(r#"
@@ -134,19 +152,140 @@ Here is the task:
 Here are the learning records:
 {records}
 "#);
+
+    static ref GENERATE_PRIMER_PROMPT: String = String::from(r#"
+You are "The Master", a teacher who knows both the history and the foundations of computing.
+You explain new concepts from first principles, with analogies to the fundamentals
+(operating systems, compilers, networking, data structures) the learner already knows.
+
+Write a primer the learner reads before starting to work on the task below.
+
+## Rules:
+1. Start from the problem the topic exists to solve, not from its API or syntax
+2. Build up the core concepts one by one, each resting on the previous ones
+3. Use at least one analogy to a more fundamental concept
+4. Point out the common misconceptions and where beginners usually get stuck
+5. Tie everything back to the SMART goal so the learner knows what matters for it
+6. Keep it under 800 words, it is a primer, not a textbook
+7. IMPORTANT: Always use English for section headers (### headings), but write the content in the same language as the task topic
+8. Use markdown formatting for readability
+9. Add blank lines between paragraphs for better readability
+
+## Response structure (use exactly these English headers):
+
+### 🧩 The Problem
+
+### 🧱 Core Concepts
+
+### 🔗 Analogy
+
+### ⚠️ Common Pitfalls
+
+### 🎯 What Matters For Your Goal
+
+---
+
+Here is the task:
+{task}
+"#);
+
+    static ref GENERATE_QUESTIONS_PROMPT: String = String::from(r#"
+You are a demanding examiner. The learner claims to have finished studying the task below
+and has submitted a summary of what they learned. Your job is to verify that they truly
+understand it, not that they can recite it.
+
+## Rules:
+1. Generate exactly 3 scenario-based questions
+2. Each question must describe a concrete situation and ask the learner to reason about it
+3. Probe corner cases, failure modes and error handling rather than definitions
+4. Base the questions on the SMART goal and the summary, not on unrelated material
+5. Write the questions in the same language as the learner's summary
+
+IMPORTANT: You MUST respond with valid JSON only, no extra text.
+
+Response format:
+{
+    "questions": ["question 1", "question 2", "question 3"]
 }
 
-pub fn audit_motivation_prompt(topic: &str, motivation: &str) -> String {
+Here is the task:
+{task}
+
+Here is the learner's summary:
+{summary}
+"#);
+
+    static ref SCORE_ANSWER_PROMPT: String = String::from(r#"
+You are a demanding but fair examiner grading a learner's answer to a scenario question.
+
+Here is the task being examined:
+{task}
+
+Question: {question}
+Learner's answer: {answer}
+
+PASS if:
+- The answer addresses the actual scenario in the question
+- The reasoning is correct, even if the wording is informal
+- Key corner cases or failure modes are acknowledged
+
+FAIL if:
+- The answer is vague, off-topic or only restates the question
+- The reasoning contains a fundamental misunderstanding
+- It ignores the corner case the question is probing
+
+IMPORTANT: You MUST respond with valid JSON only, no extra text.
+Write the feedback in the same language as the learner's answer.
+
+Response format:
+{
+    "passed": true/false,
+    "feedback": "What was right, what was missing, and a hint towards the correct reasoning if failed"
+}
+"#);
+
+    static ref SUMMARIZE_TASK_PROMPT: String = String::from(r#"
+You are the examiner who just watched the learner pass every review question of the task below.
+Write the entry this task gets in the learner's skill tree.
+
+## Rules:
+1. Summarize what the learner can now do, based on the learning records and their review answers
+2. Mention the SMART goal and whether the records show it was reached
+3. Keep the summary under 120 words, written in the same language as the task topic
+4. Give 1 to 3 short tags naming the skill areas this task belongs to (e.g. "Rust", "Async Runtime", "Databases").
+   Tags are used to group tasks in the skill tree, so prefer broad, reusable areas over task-specific phrases.
+
+IMPORTANT: You MUST respond with valid JSON only, no extra text.
+
+Response format:
+{
+    "summary": "What the learner has mastered",
+    "tags": ["area 1", "area 2"]
+}
+
+Here is the task:
+{task}
+
+Here are the learning records:
+{records}
+
+Here are the review questions and answers:
+{reviews}
+"#);
+}
+
+pub fn audit_motivation_prompt(topic: &str, motivation: &str, history: &str) -> String {
     AUDIT_MOTIVATION_PROMPT
         .replace("{topic}", topic)
         .replace("{motivation}", motivation)
+        .replace("{history}", history)
 }
 
-pub fn evaluate_smart_goal_prompt(topic: &str, motivation: &str, goal: &str) -> String {
-    EVALUATE_SMART_GOAL_PROMPT
+pub fn negotiate_smart_goal_prompt(topic: &str, motivation: &str, today: &str) -> String {
+    NEGOTIATE_SMART_GOAL_PROMPT
         .replace("{topic}", topic)
         .replace("{motivation}", motivation)
-        .replace("{goal}", goal)
+        .replace("{today}", today)
 }
 
 pub fn match_tasks_prompt(tasks: &str, record: &str) -> String {
@@ -160,3 +299,27 @@ pub fn generate_guide_prompt(task: &str, records: &str) -> String {
         .replace("{task}", task)
         .replace("{records}", records)
 }
+
+pub fn generate_primer_prompt(task: &str) -> String {
+    GENERATE_PRIMER_PROMPT.replace("{task}", task)
+}
+
+pub fn generate_questions_prompt(task: &str, summary: &str) -> String {
+    GENERATE_QUESTIONS_PROMPT
+        .replace("{task}", task)
+        .replace("{summary}", summary)
+}
+
+pub fn score_answer_prompt(task: &str, question: &str, answer: &str) -> String {
+    SCORE_ANSWER_PROMPT
+        .replace("{task}", task)
+        .replace("{question}", question)
+        .replace("{answer}", answer)
+}
+
+pub fn summarize_task_prompt(task: &str, records: &str, reviews: &str) -> String {
+    SUMMARIZE_TASK_PROMPT
+        .replace("{task}", task)
+        .replace("{records}", records)
+        .replace("{reviews}", reviews)
+}
@@ -0,0 +1,637 @@
+//! Two-way sync between the tasks and a folder of Markdown notes, e.g. an Obsidian vault.
+//!
+//! Every task gets a note with its status, goal and deadline in the front matter and its
+//! milestones, primer and records in the body. Everything above the `## Log` heading is written
+//! by gewu, list items added under it become learning records on the next sync.
+
+use std::{
+    collections::HashMap,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use serde::Serialize;
+
+use crate::{
+    domain::{
+        models::{Milestone, Record, Task},
+        state::TaskStatus,
+    },
+    ports::{
+        error::{GewuError, Result},
+        llm::LlmClient,
+        repository::Repository,
+    },
+    services::manager::TaskManager,
+};
+
+/// The heading new learning records are written under
+pub const LOG_HEADING: &str = "## Log";
+/// The note whose log entries are matched with the active tasks
+pub const INBOX_FILE: &str = "Inbox.md";
+
+const ID_KEY: &str = "gewu_id";
+const HASH_KEY: &str = "gewu_hash";
+const TIME: &str = "%Y-%m-%d %H:%M";
+const TASK_LOG_HINT: &str =
+    "<!-- Add what you learned as list items, `gewu sync` turns them into records of this task -->";
+const INBOX_LOG_HINT: &str = "<!-- Add what you learned as list items, `gewu sync` links them to the matching active tasks -->";
+
+/// A note read back from the vault.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Note {
+    /// The task the note belongs to, `None` for notes gewu did not write
+    pub task_id: Option<i64>,
+    /// Whether the part gewu writes was changed since the last sync
+    pub edited: bool,
+    /// The entries under the log heading
+    pub log: Vec<String>,
+}
+
+/// What a sync did to the vault and the repository.
+#[derive(Debug, Default, Serialize)]
+pub struct SyncReport {
+    /// Notes created or rewritten
+    pub written: usize,
+    /// Notes already up to date
+    pub unchanged: usize,
+    /// Learning records created from log entries
+    pub records: usize,
+    /// Log entries kept for the next sync, no active task could take them
+    pub pending: usize,
+    /// Notes left alone because the part gewu writes was edited, overwritten with `force`,
+    /// or because several notes claim the same task
+    pub conflicts: Vec<PathBuf>,
+    /// Notes of tasks that no longer exist
+    pub orphans: Vec<PathBuf>,
+}
+
+/// Import the log entries of the vault as learning records, then write a note per task.
+/// Running it again without changes on either side leaves the vault untouched.
+///
+/// A note is rewritten without the entries imported from it even when a later entry fails,
+/// so the next sync neither loses nor duplicates records.
+pub async fn sync_vault<LLM: LlmClient, R: Repository>(
+    manager: &mut TaskManager<LLM, R>,
+    dir: &Path,
+    force: bool,
+) -> Result<SyncReport> {
+    fs::create_dir_all(dir).map_err(storage(dir))?;
+    let mut report = SyncReport::default();
+
+    let mut paths: Vec<PathBuf> = fs::read_dir(dir)
+        .and_then(|entries| entries.map(|entry| entry.map(|e| e.path())).collect())
+        .map_err(storage(dir))?;
+    paths.sort();
+    let mut notes: HashMap<i64, Vec<(PathBuf, String)>> = HashMap::new();
+    for path in paths {
+        if path.extension().is_none_or(|ext| ext != "md") {
+            continue;
+        }
+        let content = fs::read_to_string(&path).map_err(storage(&path))?;
+        if let Some(id) = parse_note(&content).task_id {
+            notes.entry(id).or_default().push((path, content));
+        }
+    }
+
+    // The inbox first, so the records it links show up in the task notes
+    let inbox = dir.join(INBOX_FILE);
+    let existing = fs::read_to_string(&inbox).ok();
+    let mut entries = existing
+        .as_deref()
+        .map(parse_note)
+        .map_or(vec![], |n| n.log)
+        .into_iter();
+    let mut pending = Vec::new();
+    let mut failed = None;
+    for entry in entries.by_ref() {
+        let imported = async {
+            let matched = manager.match_record_with_tasks(&entry).await?;
+            if matched.task_ids.is_empty() {
+                return Ok(false);
+            }
+            manager
+                .record_learning_progress(&matched.task_ids, &entry)
+                .await?;
+            Ok::<_, GewuError>(true)
+        }
+        .await;
+        match imported {
+            Ok(true) => report.records += 1,
+            Ok(false) => pending.push(entry),
+            Err(e) => {
+                pending.push(entry);
+                failed = Some(e);
+                break;
+            }
+        }
+    }
+    pending.extend(entries);
+    report.pending += pending.len();
+    write_note(
+        &inbox,
+        existing.as_deref(),
+        &render_inbox(&pending),
+        &mut report,
+    )?;
+    if let Some(e) = failed {
+        return Err(e);
+    }
+
+    let mut tasks = manager.get_tasks_by_status(&TaskStatus::ALL).await?;
+    tasks.sort_by_key(|t| t.id);
+    for task in tasks {
+        let existing = match notes.remove(&task.id) {
+            // Which copy is the right one is up to the learner
+            Some(copies) if copies.len() > 1 => {
+                report
+                    .conflicts
+                    .extend(copies.into_iter().map(|(path, _)| path));
+                continue;
+            }
+            copies => copies.and_then(|mut c| c.pop()),
+        };
+        let mut log = Vec::new();
+        let mut failed = None;
+        if let Some((path, content)) = &existing {
+            let note = parse_note(content);
+            if note.edited && !force {
+                report.conflicts.push(path.clone());
+                continue;
+            }
+            let mut entries = note.log.into_iter();
+            for entry in entries.by_ref() {
+                if task.status != TaskStatus::Active {
+                    log.push(entry);
+                    continue;
+                }
+                match manager.record_learning_progress(&[task.id], &entry).await {
+                    Ok(_) => report.records += 1,
+                    Err(e) => {
+                        log.push(entry);
+                        failed = Some(e);
+                        break;
+                    }
+                }
+            }
+            log.extend(entries);
+        }
+        report.pending += log.len();
+
+        let primer = manager.get_task_primer(task.id).await?;
+        let milestones = manager.get_milestones(task.id).await?;
+        let records = manager.get_task_records(task.id).await?;
+        let content = render_note(&task, primer.as_deref(), &milestones, &records, &log);
+
+        // A new topic renames the note
+        let path = dir.join(note_file_name(&task));
+        match existing {
+            Some((old, _)) if old != path => {
+                write_note(&path, None, &content, &mut report)?;
+                fs::remove_file(&old).map_err(storage(&old))?;
+            }
+            Some((_, old)) => write_note(&path, Some(&old), &content, &mut report)?,
+            None => write_note(&path, None, &content, &mut report)?,
+        }
+        if let Some(e) = failed {
+            return Err(e);
+        }
+    }
+
+    report.orphans = notes
+        .into_values()
+        .flatten()
+        .map(|(path, _)| path)
+        .collect();
+    report.orphans.sort();
+    Ok(report)
+}
+
+fn write_note(
+    path: &Path,
+    existing: Option<&str>,
+    content: &str,
+    report: &mut SyncReport,
+) -> Result<()> {
+    if existing == Some(content) {
+        report.unchanged += 1;
+    } else {
+        fs::write(path, content).map_err(storage(path))?;
+        report.written += 1;
+    }
+    Ok(())
+}
+
+/// A failed read or write of the vault, naming the file
+fn storage(path: &Path) -> impl FnOnce(io::Error) -> GewuError + '_ {
+    move |e| GewuError::storage(format!("{}: {}", path.display(), e))
+}
+
+/// The file name of a task note, the id keeps it unique when topics repeat
+pub fn note_file_name(task: &Task) -> String {
+    let topic: String = task
+        .topic
+        .chars()
+        .map(|c| match c {
+            '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' | '#' | '^' | '[' | ']' => ' ',
+            c if c.is_control() => ' ',
+            c => c,
+        })
+        .take(80)
+        .collect();
+    format!(
+        "{} {}.md",
+        task.id,
+        topic.split_whitespace().collect::<Vec<_>>().join(" ")
+    )
+}
+
+/// Render the note of a task, keeping the log entries that are not imported yet
+pub fn render_note(
+    task: &Task,
+    primer: Option<&str>,
+    milestones: &[Milestone],
+    records: &[Record],
+    log: &[String],
+) -> String {
+    let mut front = vec![
+        format!("{}: {}", ID_KEY, task.id),
+        format!("topic: {}", quote(&task.topic)),
+        format!("status: {}", task.status),
+    ];
+    if let Some(deadline) = task.deadline {
+        front.push(format!("deadline: {}", deadline));
+    }
+    if let Some(until) = task.paused_until {
+        front.push(format!("paused_until: {}", until));
+    }
+    if let Some(goal) = &task.smart_goal {
+        front.push("goal:".to_string());
+        front.push(format!("  specific: {}", quote(&goal.specific)));
+        front.push(format!("  measurable: {}", quote(&goal.measurable)));
+        front.push(format!("  achievable: {}", quote(&goal.achievable)));
+        front.push(format!("  relevant: {}", quote(&goal.relevant)));
+        front.push(format!("  time_bound: {}", quote(&goal.time_bound)));
+    }
+    front.push(format!("created: {}", task.created_at.format("%Y-%m-%d")));
+
+    let mut body = format!("# {}\n\n", task.topic);
+    if let Some(motivation) = &task.motivation {
+        for line in motivation.lines() {
+            body.push_str(&format!("> {}\n", line));
+        }
+        body.push('\n');
+    }
+    if !milestones.is_empty() {
+        body.push_str("## Milestones\n\n");
+        for milestone in milestones {
+            body.push_str(&format!(
+                "- [{}] {}, by {}: {}\n",
+                if milestone.is_completed() { "x" } else { " " },
+                milestone.title,
+                milestone.due_date,
+                milestone.criterion
+            ));
+        }
+        body.push('\n');
+    }
+    if let Some(primer) = primer {
+        body.push_str(&format!("## Primer\n\n{}\n\n", primer.trim()));
+    }
+    if !records.is_empty() {
+        body.push_str("## Records\n\n");
+        for record in records {
+            let entry = format!("{}: {}", record.created_at.format(TIME), record.content);
+            body.push_str(&list_item(&entry));
+        }
+        body.push('\n');
+    }
+
+    let managed = format!("---\n{}\n---\n\n{}", front.join("\n"), body);
+    front.push(format!("{}: {}", HASH_KEY, fingerprint(&managed)));
+    format!(
+        "---\n{}\n---\n\n{}{}",
+        front.join("\n"),
+        body,
+        render_log(TASK_LOG_HINT, log)
+    )
+}
+
+/// Render the inbox note with the entries no active task took
+pub fn render_inbox(log: &[String]) -> String {
+    format!("# Inbox\n\n{}", render_log(INBOX_LOG_HINT, log))
+}
+
+fn render_log(hint: &str, log: &[String]) -> String {
+    let entries: String = log.iter().map(|entry| list_item(entry)).collect();
+    format!("{}\n\n{}\n{}", LOG_HEADING, hint, entries)
+}
+
+/// A list item, continuation lines indented so they stay in the item
+fn list_item(text: &str) -> String {
+    format!("- {}\n", text.trim().replace('\n', "\n  "))
+}
+
+/// Read the front matter and the log entries of a note
+pub fn parse_note(content: &str) -> Note {
+    let content = content.replace("\r\n", "\n");
+    let lines: Vec<&str> = content.lines().collect();
+    // The last one, a primer may have a heading of the same name
+    let log_start = lines
+        .iter()
+        .rposition(|line| line.trim_end() == LOG_HEADING);
+    let (managed, log) = match log_start {
+        Some(i) => (&lines[..i], &lines[i + 1..]),
+        None => (&lines[..], &[][..]),
+    };
+
+    let mut task_id = None;
+    let mut hash = None;
+    if managed.first() == Some(&"---") {
+        for line in managed.iter().skip(1).take_while(|line| **line != "---") {
+            if let Some(value) = front_matter_value(line, ID_KEY) {
+                task_id = value.parse().ok();
+            } else if let Some(value) = front_matter_value(line, HASH_KEY) {
+                hash = Some(value.to_string());
+            }
+        }
+    }
+
+    let unhashed: Vec<&str> = managed
+        .iter()
+        .filter(|line| front_matter_value(line, HASH_KEY).is_none())
+        .copied()
+        .collect();
+    let edited = task_id.is_some() && hash.as_deref() != Some(&fingerprint(&unhashed.join("\n")));
+
+    Note {
+        task_id,
+        edited,
+        log: parse_log(log),
+    }
+}
+
+fn front_matter_value<'a>(line: &'a str, key: &str) -> Option<&'a str> {
+    line.strip_prefix(key)?.strip_prefix(':').map(str::trim)
+}
+
+/// A line that doesn't start with a space opens an entry, an indented one continues it
+fn parse_log(lines: &[&str]) -> Vec<String> {
+    let mut entries: Vec<String> = Vec::new();
+    let mut in_comment = false;
+    for line in lines {
+        let trimmed = line.trim();
+        if in_comment || trimmed.starts_with("<!--") {
+            in_comment = !trimmed.ends_with("-->");
+            continue;
+        }
+        if trimmed.is_empty() {
+            continue;
+        }
+        if line.starts_with(char::is_whitespace)
+            && let Some(entry) = entries.last_mut()
+        {
+            entry.push('\n');
+            entry.push_str(trimmed);
+            continue;
+        }
+        let text = ["- [ ] ", "- [x] ", "- ", "* ", "+ "]
+            .iter()
+            .find_map(|bullet| trimmed.strip_prefix(bullet))
+            .unwrap_or(trimmed)
+            .trim();
+        if !text.is_empty() {
+            entries.push(text.to_string());
+        }
+    }
+    entries
+}
+
+/// A quoted front matter string, JSON strings are valid YAML
+fn quote(text: &str) -> String {
+    serde_json::Value::String(text.to_string()).to_string()
+}
+
+/// FNV-1a of the text without trailing whitespace, so editors that trim lines don't turn a
+/// note into a conflict
+fn fingerprint(text: &str) -> String {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for line in text.trim_end().lines() {
+        for byte in line.trim_end().bytes().chain(std::iter::once(b'\n')) {
+            hash ^= u64::from(byte);
+            hash = hash.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+    format!("{:016x}", hash)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::{memory_repo::MemoryRepo, mock_llm::MockLlm};
+
+    fn vault(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("gewu-vault-{}-{}", std::process::id(), name));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    fn append(path: &Path, text: &str) {
+        let content = fs::read_to_string(path).unwrap();
+        fs::write(path, content + text).unwrap();
+    }
+
+    #[tokio::test]
+    async fn note_should_round_trip_and_spot_edits() {
+        let repo = MemoryRepo::new();
+        let task = repo
+            .create_task("Tokio: Runtime", "Build a Redis clone")
+            .await
+            .unwrap();
+        let log = vec!["first".to_string(), "second\nover two lines".to_string()];
+        let content = render_note(&task, Some("## Log\nprimer"), &[], &[], &log);
+        assert_eq!(note_file_name(&task), "1 Tokio Runtime.md");
+
+        let note = parse_note(&content);
+        assert_eq!(
+            note,
+            Note {
+                task_id: Some(task.id),
+                edited: false,
+                log,
+            }
+        );
+
+        // trimmed lines and new entries are fine, edits above the log are not
+        let trimmed = content.replace("Tokio: Runtime\n", "Tokio: Runtime   \n") + "* third\n";
+        let note = parse_note(&trimmed);
+        assert!(!note.edited);
+        assert_eq!(note.log.len(), 3);
+        assert!(parse_note(&content.replace("status: Planning", "status: Active")).edited);
+        assert!(parse_note(&content.replace("> Build", "> Rebuild")).edited);
+
+        assert_eq!(parse_note(&render_inbox(&[])).task_id, None);
+        assert!(!parse_note("# Some note\n").edited);
+    }
+
+    #[tokio::test]
+    async fn sync_should_import_log_entries_and_be_idempotent() {
+        let repo = MemoryRepo::new();
+        let active = repo.create_task("Tokio", "motivation").await.unwrap();
+        repo.update_task_status(active.id, TaskStatus::Planning, TaskStatus::Active, None)
+            .await
+            .unwrap();
+        let planning = repo.create_task("Raft", "motivation").await.unwrap();
+        let llm = MockLlm::new()
+            .with_match_tasks(vec![active.id])
+            .with_match_tasks(vec![]);
+        let mut manager = TaskManager::new(llm, repo);
+        let dir = vault("sync");
+
+        let report = sync_vault(&mut manager, &dir, false).await.unwrap();
+        assert_eq!((report.written, report.unchanged), (3, 0));
+        let report = sync_vault(&mut manager, &dir, false).await.unwrap();
+        assert_eq!((report.written, report.unchanged), (0, 3));
+
+        let active_note = dir.join(note_file_name(&active));
+        let planning_note = dir.join(note_file_name(&planning));
+        append(&active_note, "- spawned many tasks\n  they are cheap\n");
+        append(&planning_note, "- read the paper\n");
+        append(
+            &dir.join(INBOX_FILE),
+            "- select! races futures\n- unrelated\n",
+        );
+
+        let report = sync_vault(&mut manager, &dir, false).await.unwrap();
+        assert_eq!((report.records, report.pending), (2, 2));
+        let records = manager.get_task_records(active.id).await.unwrap();
+        assert_eq!(
+            records
+                .iter()
+                .map(|r| r.content.as_str())
+                .collect::<Vec<_>>(),
+            vec![
+                "select! races futures",
+                "spawned many tasks\nthey are cheap"
+            ]
+        );
+        let content = fs::read_to_string(&active_note).unwrap();
+        assert!(content.contains("## Records"));
+        assert!(parse_note(&content).log.is_empty());
+        // a planning task can't take records yet, the entry waits in its log
+        assert_eq!(
+            parse_note(&fs::read_to_string(&planning_note).unwrap()).log,
+            vec!["read the paper".to_string()]
+        );
+        fs::write(dir.join(INBOX_FILE), render_inbox(&[])).unwrap();
+
+        let report = sync_vault(&mut manager, &dir, false).await.unwrap();
+        assert_eq!((report.written, report.records), (0, 0));
+
+        // hand edits of the part gewu writes are a conflict until forced
+        let content = fs::read_to_string(&active_note).unwrap();
+        fs::write(
+            &active_note,
+            content.replace("status: Active", "status: Done"),
+        )
+        .unwrap();
+        let report = sync_vault(&mut manager, &dir, false).await.unwrap();
+        assert_eq!(report.conflicts, vec![active_note.clone()]);
+        let report = sync_vault(&mut manager, &dir, true).await.unwrap();
+        assert!(report.conflicts.is_empty());
+        assert_eq!(report.written, 1);
+
+        // a new topic renames the note, a deleted task leaves an orphan
+        manager
+            .edit_task_topic(active.id, "Tokio runtime")
+            .await
+            .unwrap();
+        manager.delete_task(planning.id).await.unwrap();
+        let report = sync_vault(&mut manager, &dir, false).await.unwrap();
+        assert!(!active_note.exists());
+        assert!(dir.join("1 Tokio runtime.md").exists());
+        assert_eq!(report.orphans, vec![planning_note]);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn failed_import_should_keep_only_the_remaining_entries() {
+        let repo = MemoryRepo::new();
+        let active = repo.create_task("Tokio", "motivation").await.unwrap();
+        repo.update_task_status(active.id, TaskStatus::Planning, TaskStatus::Active, None)
+            .await
+            .unwrap();
+        // the LLM answers the first match only
+        let llm = MockLlm::new().with_match_tasks(vec![active.id]);
+        let mut manager = TaskManager::new(llm, repo);
+        let dir = vault("failed");
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(
+            dir.join(INBOX_FILE),
+            render_inbox(&["first".to_string(), "second".to_string()]),
+        )
+        .unwrap();
+
+        assert!(sync_vault(&mut manager, &dir, false).await.is_err());
+        assert_eq!(manager.get_task_records(active.id).await.unwrap().len(), 1);
+        assert_eq!(
+            parse_note(&fs::read_to_string(dir.join(INBOX_FILE)).unwrap()).log,
+            vec!["second".to_string()]
+        );
+
+        manager.llm = MockLlm::new().with_match_tasks(vec![active.id]);
+        let report = sync_vault(&mut manager, &dir, false).await.unwrap();
+        assert_eq!((report.records, report.pending), (1, 0));
+        let records = manager.get_task_records(active.id).await.unwrap();
+        assert_eq!(
+            records
+                .iter()
+                .map(|r| r.content.as_str())
+                .collect::<Vec<_>>(),
+            vec!["first", "second"]
+        );
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn notes_sharing_a_task_should_be_a_conflict() {
+        let repo = MemoryRepo::new();
+        let task = repo.create_task("Tokio", "motivation").await.unwrap();
+        repo.update_task_status(task.id, TaskStatus::Planning, TaskStatus::Active, None)
+            .await
+            .unwrap();
+        let mut manager = TaskManager::new(MockLlm::new(), repo);
+        let dir = vault("copies");
+
+        sync_vault(&mut manager, &dir, false).await.unwrap();
+        let note = dir.join(note_file_name(&task));
+        let copy = dir.join("Tokio copy.md");
+        fs::copy(&note, &copy).unwrap();
+        append(&note, "- from the note\n");
+        append(&copy, "- from the copy\n");
+
+        let report = sync_vault(&mut manager, &dir, true).await.unwrap();
+        assert_eq!(report.conflicts, vec![note.clone(), copy.clone()]);
+        assert!(report.orphans.is_empty());
+        assert_eq!(report.records, 0);
+        assert!(manager.get_task_records(task.id).await.unwrap().is_empty());
+        assert_eq!(parse_note(&fs::read_to_string(&copy).unwrap()).log.len(), 1);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn unreadable_vault_should_be_a_storage_error() {
+        let mut manager = TaskManager::new(MockLlm::new(), MemoryRepo::new());
+        let file = vault("not-a-folder");
+        fs::write(&file, "a file, not a vault").unwrap();
+
+        let error = sync_vault(&mut manager, &file, false).await.unwrap_err();
+        assert!(matches!(error, GewuError::Storage(_)));
+        assert!(error.to_string().contains(&file.display().to_string()));
+
+        fs::remove_file(&file).unwrap();
+    }
+}
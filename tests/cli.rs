@@ -0,0 +1,399 @@
+use std::{
+    path::{Path, PathBuf},
+    process::{Command, Output},
+};
+
+use serde_json::{Value, json};
+
+/// A gewu home for one test: a SQLite file and a copy of the cassette, whose replay
+/// cursor is written next to it, in a directory removed once the test ends.
+struct Sandbox {
+    dir: PathBuf,
+}
+
+impl Sandbox {
+    fn new(name: &str) -> Self {
+        Self::with_cassette(name, |_| {})
+    }
+
+    /// A sandbox replaying the fixture as changed by `edit`
+    fn with_cassette(name: &str, edit: impl FnOnce(&mut Value)) -> Self {
+        let dir = std::env::temp_dir().join(format!("gewu-cli-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let fixture =
+            Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/llm/task_flow.json");
+        let mut cassette: Value =
+            serde_json::from_str(&std::fs::read_to_string(fixture).unwrap()).unwrap();
+        edit(&mut cassette);
+        std::fs::write(dir.join("task_flow.json"), cassette.to_string()).unwrap();
+        Self { dir }
+    }
+
+    fn gewu(&self, args: &[&str]) -> Output {
+        Command::new(env!("CARGO_BIN_EXE_gewu"))
+            .args(args)
+            .current_dir(&self.dir)
+            .env(
+                "DATABASE_URL",
+                format!("sqlite://{}", self.dir.join("data.db").display()),
+            )
+            .env("LLM_REPLAY", self.dir.join("task_flow.json"))
+            .env_remove("LLM_RECORD")
+            .env_remove("WIP_MAX_PLANNING")
+            .env_remove("WIP_MAX_ACTIVE")
+            .output()
+            .unwrap()
+    }
+
+    /// Run with `--output json` and parse what it printed
+    fn json(&self, args: &[&str]) -> (i32, Value) {
+        let output = self.gewu(&[&["--output", "json"], args].concat());
+        let stdout = String::from_utf8(output.stdout).unwrap();
+        let value = serde_json::from_str(&stdout)
+            .unwrap_or_else(|e| panic!("{} is not a JSON document: {}", stdout, e));
+        (output.status.code().unwrap(), value)
+    }
+
+    fn status(&self, id: &str) -> Value {
+        let (code, detail) = self.json(&["describe", id]);
+        assert_eq!(code, 0);
+        detail["task"]["status"].clone()
+    }
+
+    fn add_task(&self) {
+        let (code, _) = self.json(&[
+            "add",
+            "--topic",
+            "Tokio",
+            "--motivation",
+            "I am building a Redis clone and want to know how the scheduler works",
+        ]);
+        assert_eq!(code, 0);
+    }
+}
+
+impl Drop for Sandbox {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.dir);
+    }
+}
+
+#[test]
+fn a_task_in_the_wrong_status_should_exit_5() {
+    let sandbox = Sandbox::new("status-conflict");
+    sandbox.add_task();
+
+    let (code, error) = sandbox.json(&["resume", "1"]);
+    assert_eq!(code, 5);
+    assert_eq!(error["kind"], "status_conflict");
+    assert_eq!(error["error"], "Task #1 is not paused");
+}
+
+#[test]
+fn a_missing_answer_should_name_its_option() {
+    let sandbox = Sandbox::new("missing-answer");
+    sandbox.add_task();
+
+    let (code, error) = sandbox.json(&["discard", "1"]);
+    assert_eq!(code, 1);
+    assert_eq!(error["kind"], "invalid_input");
+    assert_eq!(
+        error["error"],
+        "An answer is needed on the terminal, pass --reason instead"
+    );
+}
+
+const TASK_KEYS: [&str; 9] = [
+    "created_at",
+    "deadline",
+    "id",
+    "motivation",
+    "paused_until",
+    "smart_goal",
+    "status",
+    "topic",
+    "updated_at",
+];
+
+/// The sorted keys of a JSON object
+fn keys(value: &Value) -> Vec<&str> {
+    let mut keys: Vec<&str> = value
+        .as_object()
+        .unwrap_or_else(|| panic!("{} is not an object", value))
+        .keys()
+        .map(String::as_str)
+        .collect();
+    keys.sort();
+    keys
+}
+
+/// Bring task #1 to active, the cassette rejects the first goal and approves the second
+fn activate_task(sandbox: &Sandbox) {
+    sandbox.add_task();
+    assert_eq!(sandbox.json(&["plan", "1", "--goal", "Learn Tokio"]).0, 3);
+    let (code, _) = sandbox.json(&[
+        "--yes",
+        "plan",
+        "1",
+        "--goal",
+        "Build a Redis clone on Tokio with GET, SET and EXPIRE within 4 weeks",
+    ]);
+    assert_eq!(code, 0);
+}
+
+#[test]
+fn list_should_print_tasks_and_review_candidates() {
+    let sandbox = Sandbox::new("list-json");
+    activate_task(&sandbox);
+
+    let (code, list) = sandbox.json(&["list"]);
+    assert_eq!(code, 0);
+    assert_eq!(keys(&list), ["review_candidates", "tasks"]);
+    let tasks = list["tasks"].as_array().unwrap();
+    assert_eq!(tasks.len(), 1);
+    assert_eq!(keys(&tasks[0]), TASK_KEYS);
+    assert_eq!(tasks[0]["status"], "Active");
+    assert_eq!(
+        keys(&tasks[0]["smart_goal"]),
+        [
+            "achievable",
+            "measurable",
+            "relevant",
+            "specific",
+            "time_bound"
+        ]
+    );
+}
+
+#[test]
+fn describe_should_print_the_task_with_its_history() {
+    let sandbox = Sandbox::new("describe-json");
+    activate_task(&sandbox);
+
+    let (code, detail) = sandbox.json(&["describe", "1"]);
+    assert_eq!(code, 0);
+    assert_eq!(
+        keys(&detail),
+        [
+            "milestones",
+            "revisions",
+            "smart_goal_turns",
+            "task",
+            "transitions"
+        ]
+    );
+    assert_eq!(keys(&detail["task"]), TASK_KEYS);
+    assert_eq!(
+        keys(&detail["milestones"][0]),
+        [
+            "completed_at",
+            "created_at",
+            "criterion",
+            "due_date",
+            "id",
+            "task_id",
+            "title"
+        ]
+    );
+    let turns = detail["smart_goal_turns"].as_array().unwrap();
+    assert_eq!(turns.len(), 4);
+    assert_eq!(
+        keys(&turns[0]),
+        ["content", "created_at", "id", "role", "task_id"]
+    );
+    assert_eq!(turns[0]["role"], "Learner");
+    assert_eq!(turns[1]["role"], "Strategist");
+    assert_eq!(
+        keys(&detail["transitions"][0]),
+        [
+            "created_at",
+            "from_status",
+            "id",
+            "reason",
+            "task_id",
+            "to_status"
+        ]
+    );
+}
+
+#[test]
+fn record_should_print_the_record_and_its_tasks() {
+    let sandbox = Sandbox::new("record-json");
+    activate_task(&sandbox);
+
+    let (code, recorded) = sandbox.json(&["record", "Read the scheduler source", "--tasks", "1"]);
+    assert_eq!(code, 0);
+    assert_eq!(keys(&recorded), ["record", "task_ids"]);
+    assert_eq!(keys(&recorded["record"]), ["content", "created_at", "id"]);
+    assert_eq!(recorded["record"]["content"], "Read the scheduler source");
+    assert_eq!(recorded["task_ids"], json!([1]));
+}
+
+#[test]
+fn a_failure_should_print_the_error_and_its_kind() {
+    let sandbox = Sandbox::new("error-json");
+
+    let (code, error) = sandbox.json(&["describe", "9"]);
+    assert_eq!(code, 4);
+    assert_eq!(
+        error,
+        json!({ "error": "Task #9 not found", "kind": "not_found" })
+    );
+}
+
+/// The SMART goal the strategist approves on the first attempt
+fn approve_first_goal(cassette: &mut Value) {
+    cassette["negotiate_smart_goal"]
+        .as_array_mut()
+        .unwrap()
+        .remove(0);
+}
+
+#[test]
+fn add_with_a_rejected_goal_should_exit_3_and_stay_in_planning() {
+    let sandbox = Sandbox::new("add-rejected-goal");
+
+    let output = sandbox.gewu(&[
+        "--non-interactive",
+        "add",
+        "--topic",
+        "Tokio",
+        "--motivation",
+        "I am building a Redis clone and want to know how the scheduler works",
+        "--goal",
+        "Learn Tokio",
+    ]);
+    assert_eq!(output.status.code(), Some(3));
+    assert_eq!(sandbox.status("1"), "Planning");
+}
+
+#[test]
+fn add_with_an_approved_goal_should_exit_0_and_activate_the_task() {
+    let sandbox = Sandbox::with_cassette("add-approved-goal", approve_first_goal);
+
+    let output = sandbox.gewu(&[
+        "--non-interactive",
+        "--yes",
+        "add",
+        "--topic",
+        "Tokio",
+        "--motivation",
+        "I am building a Redis clone and want to know how the scheduler works",
+        "--goal",
+        "Build a Redis clone on Tokio with GET, SET and EXPIRE within 4 weeks",
+    ]);
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(sandbox.status("1"), "Active");
+}
+
+#[test]
+fn plan_should_exit_3_on_a_rejected_goal_and_0_on_an_approved_one() {
+    let sandbox = Sandbox::new("plan-goal");
+    sandbox.add_task();
+
+    let (code, rejected) = sandbox.json(&["plan", "1", "--goal", "Learn Tokio"]);
+    assert_eq!(code, 3);
+    assert_eq!(rejected["verdict"]["kind"], "smart_goal");
+    assert_eq!(rejected["verdict"]["passed"], false);
+    assert_eq!(sandbox.status("1"), "Planning");
+
+    let goal = "Build a Redis clone on Tokio with GET, SET and EXPIRE within 4 weeks";
+    let (code, _) = sandbox.json(&["--yes", "plan", "1", "--goal", goal]);
+    assert_eq!(code, 0);
+    assert_eq!(sandbox.status("1"), "Active");
+}
+
+#[test]
+fn plan_should_accept_an_approved_goal_only_with_yes() {
+    let sandbox = Sandbox::with_cassette("plan-unconfirmed-goal", approve_first_goal);
+    sandbox.add_task();
+
+    let goal = "Build a Redis clone on Tokio with GET, SET and EXPIRE within 4 weeks";
+    let (code, unconfirmed) = sandbox.json(&["plan", "1", "--goal", goal]);
+    assert_eq!(code, 1);
+    assert_eq!(unconfirmed["kind"], "invalid_input");
+    assert_eq!(sandbox.status("1"), "Planning");
+}
+
+#[test]
+fn non_interactive_should_fail_fast_instead_of_asking() {
+    let sandbox = Sandbox::new("non-interactive");
+    sandbox.add_task();
+
+    let output = sandbox.gewu(&["--non-interactive", "discard", "1"]);
+    assert_eq!(output.status.code(), Some(1));
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("pass --reason instead"), "{}", stderr);
+    assert_eq!(sandbox.status("1"), "Planning");
+
+    let output = sandbox.gewu(&["--non-interactive", "plan", "1"]);
+    assert_eq!(output.status.code(), Some(1));
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("pass --goal instead"), "{}", stderr);
+}
+
+#[test]
+fn record_should_link_the_tasks_passed_with_tasks() {
+    let sandbox = Sandbox::with_cassette("record-tasks", |cassette| {
+        let audit = cassette["audit_motivation"][0].clone();
+        cassette["audit_motivation"]
+            .as_array_mut()
+            .unwrap()
+            .push(audit);
+    });
+    activate_task(&sandbox);
+    sandbox.add_task();
+
+    // the matched tasks are only taken with --yes, --tasks picks them instead
+    let (code, error) = sandbox.json(&["record", "Read the scheduler source"]);
+    assert_eq!(code, 1);
+    assert_eq!(error["kind"], "invalid_input");
+
+    let (code, error) = sandbox.json(&["record", "Read the scheduler source", "--tasks", "2"]);
+    assert_eq!(code, 5);
+    assert_eq!(
+        error,
+        json!({ "error": "Task #2 is not active", "kind": "status_conflict" })
+    );
+
+    let (code, recorded) = sandbox.json(&["record", "Read the scheduler source", "--tasks", "1"]);
+    assert_eq!(code, 0);
+    assert_eq!(recorded["task_ids"], json!([1]));
+}
+
+#[test]
+fn plan_should_exit_5_once_the_task_left_planning() {
+    let reviewing = Sandbox::new("plan-reviewing");
+    activate_task(&reviewing);
+    let (code, _) = reviewing.json(&["commit", "1", "--summary", "Tokio parks idle workers"]);
+    assert_eq!(code, 0);
+
+    let paused = Sandbox::new("plan-paused");
+    activate_task(&paused);
+    assert_eq!(paused.json(&["pause", "1"]).0, 0);
+
+    for (sandbox, status) in [(&reviewing, "Reviewing"), (&paused, "Paused")] {
+        let (code, error) = sandbox.json(&["--yes", "plan", "1", "--goal", "Learn Tokio"]);
+        assert_eq!(code, 5);
+        assert_eq!(error["kind"], "status_conflict");
+        assert_eq!(sandbox.status("1"), status);
+    }
+}
+
+#[test]
+fn search_should_reject_a_limit_below_1() {
+    let sandbox = Sandbox::new("search-limit");
+
+    for limit in ["0", "-1"] {
+        let output = sandbox.gewu(&["search", "tokio", "--limit", limit]);
+        assert_eq!(output.status.code(), Some(2), "--limit {}", limit);
+    }
+    assert_eq!(
+        sandbox
+            .gewu(&["search", "tokio", "--limit", "1"])
+            .status
+            .code(),
+        Some(0)
+    );
+}